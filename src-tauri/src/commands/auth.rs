@@ -1,16 +1,30 @@
 use std::path::Path;
 
-use reqwest::Method;
+use rsa::traits::PublicKeyParts;
+use tauri::{AppHandle, State};
+use tauri_plugin_opener::OpenerExt;
 
-use crate::models::profile::{ConnectionResult, OciProfile, ValidationResult};
+use crate::models::profile::{
+    ConnectionResult, OciProfile, PrincipalType, SessionStatus, ValidationResult,
+};
+use crate::oci::api_keys;
+use crate::oci::client;
 use crate::oci::config_parser;
-use crate::oci::regions::region_to_endpoint;
-use crate::oci::signature::{sign_request, SigningRequest};
+use crate::oci::error::OciError;
+use crate::oci::principals;
+use crate::oci::session;
+use crate::oci::signature;
+use crate::oci::sso::{self, SsoLoginRequest};
+use crate::state::AppState;
+
+/// OCI が受け付ける最小の鍵長
+const MIN_KEY_BITS: usize = 2048;
 
 /// プロファイルの入力値を検証
 #[tauri::command]
 pub fn validate_profile(profile: OciProfile) -> ValidationResult {
     let mut errors = Vec::new();
+    let mut warnings = Vec::new();
     let ocid_pattern = regex_lite::Regex::new(r"^ocid1\.[a-z]+\.oc[0-9]+\.").unwrap();
 
     // プロファイル名チェック
@@ -18,16 +32,54 @@ pub fn validate_profile(profile: OciProfile) -> ValidationResult {
         errors.push("プロファイル名を入力してください。".to_string());
     }
 
+    // 改行などの制御文字は設定ファイルの行を壊すため拒否する
+    let has_control = [
+        &profile.name,
+        &profile.user,
+        &profile.tenancy,
+        &profile.region,
+        &profile.fingerprint,
+        &profile.key_file,
+    ]
+    .into_iter()
+    .chain(&profile.security_token_file)
+    .chain(&profile.endpoint)
+    .chain(profile.service_endpoints.values())
+    .any(|value| value.chars().any(char::is_control));
+    if has_control {
+        errors.push("入力値に改行などの制御文字を含めることはできません。".to_string());
+    }
+
+    // プリンシパル認証では認証情報を設定ファイルに持たない
+    if profile.principal.is_some() {
+        let region_from_env = profile.principal == Some(PrincipalType::ResourcePrincipal)
+            && principals::resource_principal_region().is_some();
+        if profile.region.trim().is_empty() && !region_from_env {
+            errors.push("リージョンを選択してください。".to_string());
+        }
+        if !profile.tenancy.is_empty() && !ocid_pattern.is_match(&profile.tenancy) {
+            errors.push(
+                "テナンシー OCID の形式が無効です。（例: ocid1.tenancy.oc1..xxx）".to_string(),
+            );
+        }
+        return ValidationResult {
+            valid: errors.is_empty(),
+            errors,
+            warnings,
+        };
+    }
+
+    // セッショントークン認証では user / fingerprint は不要
+    let uses_session_token = profile.security_token_file.is_some();
+
     // ユーザー OCID チェック
-    if !ocid_pattern.is_match(&profile.user) {
+    if !uses_session_token && !ocid_pattern.is_match(&profile.user) {
         errors.push("ユーザー OCID の形式が無効です。（例: ocid1.user.oc1..xxx）".to_string());
     }
 
     // テナンシー OCID チェック
     if !ocid_pattern.is_match(&profile.tenancy) {
-        errors.push(
-            "テナンシー OCID の形式が無効です。（例: ocid1.tenancy.oc1..xxx）".to_string(),
-        );
+        errors.push("テナンシー OCID の形式が無効です。（例: ocid1.tenancy.oc1..xxx）".to_string());
     }
 
     // リージョンチェック
@@ -36,12 +88,9 @@ pub fn validate_profile(profile: OciProfile) -> ValidationResult {
     }
 
     // フィンガープリントチェック
-    let fp_pattern =
-        regex_lite::Regex::new(r"^([0-9a-fA-F]{2}:){15}[0-9a-fA-F]{2}$").unwrap();
-    if !fp_pattern.is_match(&profile.fingerprint) {
-        errors.push(
-            "フィンガープリントの形式が無効です。（例: aa:bb:cc:dd:...）".to_string(),
-        );
+    let fp_pattern = regex_lite::Regex::new(r"^([0-9a-fA-F]{2}:){15}[0-9a-fA-F]{2}$").unwrap();
+    if !uses_session_token && !fp_pattern.is_match(&profile.fingerprint) {
+        errors.push("フィンガープリントの形式が無効です。（例: aa:bb:cc:dd:...）".to_string());
     }
 
     // 秘密鍵ファイルチェック
@@ -49,88 +98,272 @@ pub fn validate_profile(profile: OciProfile) -> ValidationResult {
         errors.push("秘密鍵ファイルのパスを指定してください。".to_string());
     } else if !Path::new(&profile.key_file).exists() {
         errors.push("指定された秘密鍵ファイルが見つかりません。".to_string());
+    } else {
+        validate_key_file(&profile, &mut errors, &mut warnings);
+    }
+
+    // セッショントークンファイルチェック
+    if let Some(token_file) = &profile.security_token_file {
+        if !Path::new(token_file).exists() {
+            errors.push("指定されたセッショントークンファイルが見つかりません。".to_string());
+        }
     }
 
     ValidationResult {
         valid: errors.is_empty(),
         errors,
+        warnings,
+    }
+}
+
+/// 秘密鍵ファイルの内容を検証（鍵の種類・長さ・フィンガープリント・パーミッション）
+fn validate_key_file(profile: &OciProfile, errors: &mut Vec<String>, warnings: &mut Vec<String>) {
+    // パーミッションチェック（OCI CLI と同様に警告のみ）
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if let Ok(metadata) = std::fs::metadata(&profile.key_file) {
+            let mode = metadata.permissions().mode() & 0o777;
+            if mode & 0o077 != 0 {
+                warnings.push(format!(
+                    "秘密鍵ファイルのパーミッション（{:o}）がグループまたは他のユーザーに読み取りを許可しています。chmod 600 で制限してください。",
+                    mode
+                ));
+            }
+        }
+    }
+
+    let content = std::fs::read_to_string(&profile.key_file).unwrap_or_default();
+    if content.contains("PUBLIC KEY-----") && !content.contains("PRIVATE KEY-----") {
+        errors
+            .push("指定されたファイルは公開鍵です。秘密鍵ファイルを指定してください。".to_string());
+        return;
+    }
+
+    let private_key =
+        match signature::load_private_key(&profile.key_file, profile.pass_phrase.as_deref()) {
+            Ok(key) => key,
+            // パスフレーズは接続時に入力できるため、鍵の内容は検証しない
+            Err(OciError::PassphraseRequired { .. }) => return,
+            Err(e) => {
+                errors.push(e.to_string());
+                return;
+            }
+        };
+
+    let bits = private_key.size() * 8;
+    if bits < MIN_KEY_BITS {
+        errors.push(format!(
+            "秘密鍵の長さが {} ビットです。{} ビット以上の鍵を使用してください。",
+            bits, MIN_KEY_BITS
+        ));
+    }
+
+    if !profile.fingerprint.is_empty() {
+        match signature::public_key_fingerprint(&private_key.to_public_key()) {
+            Ok(actual) if !actual.eq_ignore_ascii_case(profile.fingerprint.trim()) => {
+                errors.push(format!(
+                    "フィンガープリントが秘密鍵と一致しません。（設定値: {}、秘密鍵から算出した値: {}）",
+                    profile.fingerprint, actual
+                ));
+            }
+            Ok(_) => {}
+            Err(e) => errors.push(e.to_string()),
+        }
     }
 }
 
 /// 接続テスト: OCI Identity API を呼び出して認証を検証
 #[tauri::command]
 pub async fn test_connection(
+    state: State<'_, AppState>,
     profile_name: String,
     config_path: Option<String>,
-) -> Result<ConnectionResult, String> {
+) -> Result<ConnectionResult, OciError> {
     // プロファイルを読み込み
-    let cfg_path = match config_path {
-        Some(p) => std::path::PathBuf::from(p),
-        None => config_parser::default_config_path(),
-    };
-
-    let profiles = config_parser::parse_config(&cfg_path)?;
-    let profile = profiles
-        .into_iter()
-        .find(|p| p.name == profile_name)
-        .ok_or_else(|| format!("プロファイル '{}' が見つかりません。", profile_name))?;
+    let cfg_path = state.resolve_config_path(config_path);
+    let profile = client::load_profile(&cfg_path, &profile_name)?;
 
-    // Identity API エンドポイント
-    let host = region_to_endpoint(&profile.region, "identity");
+    // Identity API でテナンシー情報を取得
     let api_path = format!("/20160918/tenancies/{}", profile.tenancy);
 
-    // 署名付きヘッダー生成
-    let signing_request = SigningRequest {
-        method: Method::GET,
-        path: api_path.clone(),
-        host: host.clone(),
-        body: None,
-    };
+    match client::oci_get_request(&state.oci, &profile, "identity", &api_path, None).await {
+        Ok(_) => Ok(ConnectionResult {
+            success: true,
+            message: "接続テスト成功。認証が正常に確認されました。".to_string(),
+        }),
+        Err(OciError::Service { status: 401, .. }) => Ok(ConnectionResult {
+            success: false,
+            message: "認証エラー。OCID、フィンガープリント、秘密鍵を確認してください。".to_string(),
+        }),
+        Err(OciError::Transport { message }) => Ok(ConnectionResult {
+            success: false,
+            message: format!("接続に失敗しました: {}", message),
+        }),
+        Err(e @ OciError::Service { .. }) => Ok(ConnectionResult {
+            success: false,
+            message: match e.opc_request_id() {
+                Some(id) => format!("{}（opc-request-id: {}）", e, id),
+                None => e.to_string(),
+            },
+        }),
+        Err(e) => Err(e),
+    }
+}
+
+/// 暗号化された秘密鍵のパスフレーズをセッション中のみ保持する（設定ファイルには保存しない）
+#[tauri::command]
+pub fn set_session_passphrase(
+    state: State<'_, AppState>,
+    profile_name: String,
+    passphrase: String,
+) -> Result<(), OciError> {
+    let profile = client::load_profile(&state.config_path(), &profile_name)?;
+    state.oci.keys.set_session_passphrase(&profile, &passphrase)
+}
+
+/// セッショントークンの有効期限を取得
+#[tauri::command]
+pub fn get_session_status(
+    state: State<'_, AppState>,
+    profile_name: String,
+) -> Result<SessionStatus, OciError> {
+    let profile = client::load_profile(&state.config_path(), &profile_name)?;
+    session::session_status(&profile)
+}
 
-    let signed_headers = sign_request(&profile, &signing_request)?;
+/// セッショントークンを更新し、更新後の状態を返す
+#[tauri::command]
+pub async fn refresh_session(
+    state: State<'_, AppState>,
+    profile_name: String,
+) -> Result<SessionStatus, OciError> {
+    let profile = client::load_profile(&state.config_path(), &profile_name)?;
+    let private_key = state.oci.keys.get(&profile)?;
+    let token = session::read_security_token(session::security_token_path(&profile)?)?;
+    session::refresh_security_token(&state.oci, &profile, &private_key, &token).await?;
+    session::session_status(&profile)
+}
 
-    // HTTP リクエスト送信
-    let url = format!("https://{}{}", host, api_path);
-    let client = reqwest::Client::new();
-    let mut request_builder = client.get(&url);
+/// ブラウザでログインし、セッショントークン認証のプロファイルを作成する
+#[tauri::command]
+pub async fn sso_login(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    profile_name: String,
+    region: String,
+    tenancy_name: Option<String>,
+) -> Result<OciProfile, OciError> {
+    let request = SsoLoginRequest {
+        profile_name,
+        region,
+        tenancy_name,
+    };
+    let config_path = state.config_path();
 
-    for (name, value) in &signed_headers {
-        request_builder = request_builder.header(name, value);
+    let profile = sso::login(&request, &config_path, |url| {
+        app.opener()
+            .open_url(url, None::<&str>)
+            .map_err(|e| OciError::config(format!("ブラウザを開けませんでした: {}", e)))
+    })
+    .await?;
+    state.oci.keys.clear();
+    Ok(profile)
+}
+
+/// 新しい API キーを生成してユーザーに登録し、その鍵を使うプロファイルを作成する
+///
+/// 登録には `source_profile` の認証情報を使う。
+#[tauri::command]
+pub async fn generate_api_key(
+    state: State<'_, AppState>,
+    source_profile: String,
+    profile_name: String,
+    key_size: usize,
+) -> Result<OciProfile, OciError> {
+    let profile_name = profile_name.trim().to_string();
+    if profile_name.is_empty() {
+        return Err(OciError::config("プロファイル名を入力してください。"));
     }
+    config_parser::check_profile_name(&profile_name)?;
 
-    match request_builder
-        .timeout(std::time::Duration::from_secs(30))
-        .send()
-        .await
+    let config_path = state.config_path();
+    let profiles = config_parser::parse_config(&config_path).map_err(OciError::config)?;
+    if config_parser::read_document(&config_path)
+        .map_err(OciError::config)?
+        .has_section(&profile_name)
     {
-        Ok(response) => {
-            let status = response.status();
-            if status.is_success() {
-                Ok(ConnectionResult {
-                    success: true,
-                    message: "接続テスト成功。認証が正常に確認されました。".to_string(),
-                })
-            } else if status.as_u16() == 401 {
-                Ok(ConnectionResult {
-                    success: false,
-                    message: "認証エラー。OCID、フィンガープリント、秘密鍵を確認してください。"
-                        .to_string(),
-                })
-            } else {
-                let body = response.text().await.unwrap_or_default();
-                Ok(ConnectionResult {
-                    success: false,
-                    message: format!(
-                        "API エラー（ステータス: {}）: {}",
-                        status.as_u16(),
-                        body
-                    ),
-                })
-            }
+        return Err(OciError::config(format!(
+            "プロファイル '{}' は既に存在します。",
+            profile_name
+        )));
+    }
+    let source = profiles
+        .iter()
+        .find(|p| p.name == source_profile)
+        .cloned()
+        .ok_or_else(|| {
+            OciError::config(format!(
+                "プロファイル '{}' が見つかりません。",
+                source_profile
+            ))
+        })?;
+
+    let key_dir = config_path.parent().unwrap_or(Path::new("."));
+    let profile =
+        api_keys::create_api_key_profile(&state.oci, &source, &profile_name, key_size, key_dir)
+            .await?;
+
+    config_parser::upsert_profile(&config_path, None, &profile).map_err(OciError::config)?;
+    state.oci.keys.clear();
+    Ok(profile)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/keys");
+
+    #[test]
+    fn test_validate_key_file_reports_key_problems() {
+        let dir = tempfile::tempdir().unwrap();
+        let key_file = dir.path().join("key.pem");
+        std::fs::copy(format!("{}/plain_pkcs1.pem", FIXTURES), &key_file).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&key_file, std::fs::Permissions::from_mode(0o644)).unwrap();
         }
-        Err(e) => Ok(ConnectionResult {
-            success: false,
-            message: format!("接続に失敗しました: {}", e),
-        }),
+
+        let mut profile = OciProfile {
+            key_file: key_file.to_string_lossy().to_string(),
+            fingerprint: "00:11:22:33:44:55:66:77:88:99:aa:bb:cc:dd:ee:ff".to_string(),
+            ..Default::default()
+        };
+        let (mut errors, mut warnings) = (Vec::new(), Vec::new());
+        validate_key_file(&profile, &mut errors, &mut warnings);
+
+        // テスト用の鍵は 1024 ビット
+        assert!(errors.iter().any(|e| e.contains("1024 ビット")));
+        assert!(errors.iter().any(|e| e.contains("2f:35:22:36")));
+        #[cfg(unix)]
+        assert!(warnings.iter().any(|w| w.contains("644")));
+
+        // 公開鍵を指定した場合
+        let public_key = signature::load_private_key(&profile.key_file, None)
+            .unwrap()
+            .to_public_key();
+        let public_file = dir.path().join("key_public.pem");
+        std::fs::write(
+            &public_file,
+            rsa::pkcs8::EncodePublicKey::to_public_key_pem(&public_key, Default::default())
+                .unwrap(),
+        )
+        .unwrap();
+        profile.key_file = public_file.to_string_lossy().to_string();
+        let mut errors = Vec::new();
+        validate_key_file(&profile, &mut errors, &mut Vec::new());
+        assert!(errors.iter().any(|e| e.contains("公開鍵")));
     }
 }
@@ -1,16 +1,183 @@
-use crate::models::compute::ComputeInstance;
+use futures_util::stream::{self, StreamExt};
+use tauri::{AppHandle, Emitter, State};
+
+use crate::commands::work_request::WORK_REQUEST_PROGRESS_EVENT;
+use crate::models::compute::{
+    ComputeInstance, InstanceAction, InstanceActionProgress, InstanceActionResult,
+};
+use crate::models::iam::CompartmentListing;
+use crate::models::profile::OciProfile;
 use crate::oci::client;
+use crate::oci::error::OciError;
+use crate::oci::fan_out::{self, ListScope, DEFAULT_CONCURRENCY};
+use crate::oci::instance_actions;
+use crate::oci::region_catalog::{self, RegionScope};
+use crate::oci::waiter::{self, Poller, WaitPolicy};
+use crate::oci::work_requests;
+use crate::state::AppState;
+
+/// 電源操作の進捗を通知するイベント
+pub const INSTANCE_ACTION_PROGRESS_EVENT: &str = "instance-action-progress";
 
 /// コンピュートインスタンス一覧を取得
+///
+/// `include_subtree` を指定すると子孫コンパートメントも含める。`regions` で複数のリージョン
+/// （またはサブスクライブしているすべてのリージョン）を指定できる。各行にリージョンとコンパートメントのパスを付ける。
 #[tauri::command]
 pub async fn list_instances(
+    state: State<'_, AppState>,
     profile_name: String,
     compartment_id: String,
-) -> Result<Vec<ComputeInstance>, String> {
-    let profile = client::load_profile(&profile_name)?;
-    let query = format!("compartmentId={}", compartment_id);
-    let body = client::oci_get_request(&profile, "iaas", "/20160918/instances", Some(&query)).await?;
-    let instances: Vec<ComputeInstance> =
-        serde_json::from_str(&body).map_err(|e| format!("レスポンスの解析に失敗しました: {}", e))?;
-    Ok(instances)
+    include_subtree: Option<bool>,
+    regions: Option<RegionScope>,
+    max_items: Option<usize>,
+) -> Result<CompartmentListing<ComputeInstance>, OciError> {
+    let profile = client::load_profile(&state.config_path(), &profile_name)?;
+    let regions = region_catalog::resolve_regions(
+        &state.oci,
+        &profile,
+        &state.cache_dir(),
+        &regions.unwrap_or_default(),
+    )
+    .await?;
+    fan_out::list_in_compartments(
+        &state.oci,
+        &state.compartments,
+        &profile,
+        &ListScope {
+            compartment_id,
+            include_subtree: include_subtree.unwrap_or(false),
+            regions,
+        },
+        max_items,
+        |profile, compartment_id| {
+            let state = &state;
+            async move {
+                let query = format!("compartmentId={}", compartment_id);
+                client::oci_list_request(
+                    &state.oci,
+                    &profile,
+                    "iaas",
+                    "/20160918/instances",
+                    Some(&query),
+                    max_items,
+                )
+                .await
+            }
+        },
+    )
+    .await
+}
+
+/// インスタンスの電源操作（START / STOP / SOFTSTOP / RESET / SOFTRESET）を実行
+///
+/// 複数のインスタンスを並行して操作し、インスタンスごとの結果（失敗した場合はエラー）を返す。
+/// `wait` を指定すると操作が完了する（RUNNING / STOPPED になる）まで待ち、状態を確認するたびに
+/// `instance-action-progress` イベントを送る。待機はインスタンス OCID を指定した `cancel_wait` で中止できる。
+#[tauri::command]
+pub async fn instance_action(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    profile_name: String,
+    instance_ids: Vec<String>,
+    action: InstanceAction,
+    wait: Option<bool>,
+) -> Result<Vec<InstanceActionResult>, OciError> {
+    let profile = client::load_profile(&state.config_path(), &profile_name)?;
+    let wait = wait.unwrap_or(false);
+    let results = stream::iter(instance_ids)
+        .map(|instance_id| {
+            let (app, state, profile) = (&app, &*state, &profile);
+            async move {
+                match run_instance_action(app, state, profile, &instance_id, action, wait).await {
+                    Ok((lifecycle_state, work_request_id)) => InstanceActionResult {
+                        instance_id,
+                        lifecycle_state: Some(lifecycle_state),
+                        work_request_id,
+                        error: None,
+                    },
+                    Err(error) => InstanceActionResult {
+                        instance_id,
+                        lifecycle_state: None,
+                        work_request_id: None,
+                        error: Some(error),
+                    },
+                }
+            }
+        })
+        .buffered(DEFAULT_CONCURRENCY)
+        .collect()
+        .await;
+    Ok(results)
+}
+
+/// 1 台のインスタンスを操作し、ライフサイクル状態とワークリクエスト OCID を返す
+///
+/// 待機する場合、ワークリクエストが返されたときはその完了を待ち（進捗は
+/// `work-request-progress` イベントでも送る）、なければインスタンスの状態を確認する。
+async fn run_instance_action(
+    app: &AppHandle,
+    state: &AppState,
+    profile: &OciProfile,
+    instance_id: &str,
+    action: InstanceAction,
+    wait: bool,
+) -> Result<(String, Option<String>), OciError> {
+    let profile = waiter::profile_for_resource(profile, instance_id);
+    let emit_progress = |lifecycle_state: &str, done: bool| {
+        let progress = InstanceActionProgress {
+            instance_id: instance_id.to_string(),
+            action,
+            lifecycle_state: lifecycle_state.to_string(),
+            done,
+        };
+        app.emit(INSTANCE_ACTION_PROGRESS_EVENT, &progress).ok();
+    };
+
+    let (instance, work_request_id) =
+        instance_actions::instance_action(&state.oci, &profile, instance_id, action).await?;
+    if !wait {
+        return Ok((instance.lifecycle_state, work_request_id));
+    }
+
+    emit_progress(&instance.lifecycle_state, false);
+    let handle = state.waits.register(instance_id);
+    let poller = Poller::new(WaitPolicy::default(), handle.token());
+    let instance = match &work_request_id {
+        Some(work_request_id) => {
+            let work_request = work_requests::wait_for_work_request(
+                &state.oci,
+                &profile,
+                work_request_id,
+                poller,
+                |progress| {
+                    app.emit(WORK_REQUEST_PROGRESS_EVENT, progress).ok();
+                },
+            )
+            .await?;
+            if work_request.status != "SUCCEEDED" {
+                return Err(OciError::wait(format!(
+                    "ワークリクエストが {} で終了しました。",
+                    work_request.status
+                )));
+            }
+            let instance =
+                instance_actions::get_instance(&state.oci, &profile, instance_id).await?;
+            emit_progress(&instance.lifecycle_state, true);
+            instance
+        }
+        None => {
+            instance_actions::wait_for_state(
+                &state.oci,
+                &profile,
+                instance_id,
+                action,
+                &instance.lifecycle_state,
+                poller,
+                |instance, done| emit_progress(&instance.lifecycle_state, done),
+            )
+            .await?
+        }
+    };
+    Ok((instance.lifecycle_state, work_request_id))
 }
@@ -1,5 +1,11 @@
-use crate::models::profile::OciProfile;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::models::profile::{ConfigBackup, ConfigDiagnostics, DiffLine, OciProfile};
+use crate::oci::config_backup;
 use crate::oci::config_parser;
+use crate::oci::config_watcher::ConfigWatcher;
+use crate::oci::error::OciError;
+use crate::state::AppState;
 
 /// OCI 設定ファイルのデフォルトパスを取得
 #[tauri::command]
@@ -9,27 +15,129 @@ pub fn get_default_config_path() -> String {
         .to_string()
 }
 
-/// OCI 設定ファイルからプロファイル一覧を読み込む
+/// 設定ファイルの変更でプロファイルが変化したときに送るイベント
+pub const PROFILES_CHANGED_EVENT: &str = "profiles-changed";
+
+/// 現在の設定ファイルの監視を開始し、変更をフロントエンドに通知する
+pub fn watch_active_config(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let handle = app.clone();
+    let watcher = ConfigWatcher::start(&state.config_path(), move |changes| {
+        // 鍵ファイルが差し替えられている可能性があるため、キャッシュを破棄する
+        handle.state::<AppState>().oci.keys.clear();
+        handle.emit(PROFILES_CHANGED_EVENT, &changes).ok();
+    });
+
+    let mut current = state
+        .config_watcher
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    match watcher {
+        Ok(watcher) => *current = Some(watcher),
+        Err(e) => {
+            log::warn!("{}", e);
+            *current = None;
+        }
+    }
+}
+
+/// 現在使用している設定ファイルのパスを取得
 #[tauri::command]
-pub fn load_oci_config(path: Option<String>) -> Result<Vec<OciProfile>, String> {
+pub fn get_active_config_path(state: State<'_, AppState>) -> String {
+    state.config_path().to_string_lossy().to_string()
+}
+
+/// 使用する設定ファイルを切り替える（未指定の場合は環境変数またはデフォルトのパスに戻す）
+#[tauri::command]
+pub fn set_active_config_path(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    path: Option<String>,
+) -> Result<String, OciError> {
     let config_path = match path {
-        Some(p) => std::path::PathBuf::from(p),
+        Some(p) => {
+            let config_path = std::path::PathBuf::from(p);
+            if !config_path.is_file() {
+                return Err(OciError::config("指定されたファイルが見つかりません。"));
+            }
+            config_path
+        }
         None => config_parser::default_config_path(),
     };
 
+    state.set_config_path(config_path.clone());
+    watch_active_config(&app);
+    Ok(config_path.to_string_lossy().to_string())
+}
+
+/// 起動時に選択するプロファイル名（OCI_CLI_PROFILE 環境変数）を取得
+#[tauri::command]
+pub fn get_default_profile_name() -> Option<String> {
+    config_parser::default_profile_name()
+}
+
+/// OCI 設定ファイルからプロファイル一覧を読み込む
+#[tauri::command]
+pub fn load_oci_config(
+    state: State<'_, AppState>,
+    path: Option<String>,
+) -> Result<Vec<OciProfile>, OciError> {
+    let config_path = state.resolve_config_path(path);
+
     if !config_path.exists() {
         return Ok(Vec::new());
     }
 
-    config_parser::parse_config(&config_path)
+    Ok(config_parser::parse_config(&config_path)?)
+}
+
+/// OCI 設定ファイルを検査し、読み込めないセクションとその理由を返す
+#[tauri::command]
+pub fn diagnose_config(
+    state: State<'_, AppState>,
+    path: Option<String>,
+) -> Result<ConfigDiagnostics, OciError> {
+    let config_path = state.resolve_config_path(path);
+
+    if !config_path.exists() {
+        return Ok(ConfigDiagnostics {
+            sections: Vec::new(),
+            orphan_lines: Vec::new(),
+        });
+    }
+
+    Ok(config_parser::diagnose_config(&config_path)?)
+}
+
+/// 現在の設定ファイルのバックアップ一覧を取得（新しい順）
+#[tauri::command]
+pub fn list_config_backups(state: State<'_, AppState>) -> Result<Vec<ConfigBackup>, OciError> {
+    Ok(config_backup::list_backups(&state.config_path())?)
+}
+
+/// バックアップから現在の設定ファイルへの差分を取得
+#[tauri::command]
+pub fn diff_config_backup(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<Vec<DiffLine>, OciError> {
+    Ok(config_backup::diff_backup(&state.config_path(), &id)?)
+}
+
+/// バックアップを復元する（復元前の設定ファイルもバックアップされる）
+#[tauri::command]
+pub fn restore_config_backup(state: State<'_, AppState>, id: String) -> Result<(), OciError> {
+    config_backup::restore_backup(&state.config_path(), &id)?;
+    state.oci.keys.clear();
+    Ok(())
 }
 
 /// OCI CLI 設定ファイルをインポート
 #[tauri::command]
-pub fn import_oci_cli_config(path: String) -> Result<Vec<OciProfile>, String> {
+pub fn import_oci_cli_config(path: String) -> Result<Vec<OciProfile>, OciError> {
     let config_path = std::path::PathBuf::from(path);
     if !config_path.exists() {
-        return Err("指定されたファイルが見つかりません。".to_string());
+        return Err(OciError::config("指定されたファイルが見つかりません。"));
     }
-    config_parser::parse_config(&config_path)
+    Ok(config_parser::parse_config(&config_path)?)
 }
@@ -1,17 +1,59 @@
+use tauri::State;
+
 use crate::models::database::DbSystem;
+use crate::models::iam::CompartmentListing;
 use crate::oci::client;
+use crate::oci::error::OciError;
+use crate::oci::fan_out::{self, ListScope};
+use crate::oci::region_catalog::{self, RegionScope};
+use crate::state::AppState;
 
 /// DB システム一覧を取得
+///
+/// `include_subtree` を指定すると子孫コンパートメントも含める。`regions` で複数のリージョン
+/// （またはサブスクライブしているすべてのリージョン）を指定できる。各行にリージョンとコンパートメントのパスを付ける。
 #[tauri::command]
 pub async fn list_db_systems(
+    state: State<'_, AppState>,
     profile_name: String,
     compartment_id: String,
-) -> Result<Vec<DbSystem>, String> {
-    let profile = client::load_profile(&profile_name)?;
-    let query = format!("compartmentId={}", compartment_id);
-    let body =
-        client::oci_get_request(&profile, "database", "/20160918/dbSystems", Some(&query)).await?;
-    let db_systems: Vec<DbSystem> =
-        serde_json::from_str(&body).map_err(|e| format!("レスポンスの解析に失敗しました: {}", e))?;
-    Ok(db_systems)
+    include_subtree: Option<bool>,
+    regions: Option<RegionScope>,
+    max_items: Option<usize>,
+) -> Result<CompartmentListing<DbSystem>, OciError> {
+    let profile = client::load_profile(&state.config_path(), &profile_name)?;
+    let regions = region_catalog::resolve_regions(
+        &state.oci,
+        &profile,
+        &state.cache_dir(),
+        &regions.unwrap_or_default(),
+    )
+    .await?;
+    fan_out::list_in_compartments(
+        &state.oci,
+        &state.compartments,
+        &profile,
+        &ListScope {
+            compartment_id,
+            include_subtree: include_subtree.unwrap_or(false),
+            regions,
+        },
+        max_items,
+        |profile, compartment_id| {
+            let state = &state;
+            async move {
+                let query = format!("compartmentId={}", compartment_id);
+                client::oci_list_request(
+                    &state.oci,
+                    &profile,
+                    "database",
+                    "/20160918/dbSystems",
+                    Some(&query),
+                    max_items,
+                )
+                .await
+            }
+        },
+    )
+    .await
 }
@@ -1,26 +1,72 @@
-use crate::models::iam::{IamGroup, IamUser};
+use tauri::State;
+
+use crate::models::iam::{CompartmentNode, IamGroup, IamUser};
 use crate::oci::client;
+use crate::oci::compartments;
+use crate::oci::error::OciError;
+use crate::state::AppState;
 
 /// IAM ユーザー一覧を取得
 #[tauri::command]
-pub async fn list_users(profile_name: String) -> Result<Vec<IamUser>, String> {
-    let profile = client::load_profile(&profile_name)?;
+pub async fn list_users(
+    state: State<'_, AppState>,
+    profile_name: String,
+    max_items: Option<usize>,
+) -> Result<Vec<IamUser>, OciError> {
+    let profile = client::load_profile(&state.config_path(), &profile_name)?;
     let query = format!("compartmentId={}", profile.tenancy);
-    let body =
-        client::oci_get_request(&profile, "identity", "/20160918/users", Some(&query)).await?;
-    let users: Vec<IamUser> =
-        serde_json::from_str(&body).map_err(|e| format!("レスポンスの解析に失敗しました: {}", e))?;
-    Ok(users)
+    client::oci_list_request(
+        &state.oci,
+        &profile,
+        "identity",
+        "/20160918/users",
+        Some(&query),
+        max_items,
+    )
+    .await
 }
 
 /// IAM グループ一覧を取得
 #[tauri::command]
-pub async fn list_groups(profile_name: String) -> Result<Vec<IamGroup>, String> {
-    let profile = client::load_profile(&profile_name)?;
+pub async fn list_groups(
+    state: State<'_, AppState>,
+    profile_name: String,
+    max_items: Option<usize>,
+) -> Result<Vec<IamGroup>, OciError> {
+    let profile = client::load_profile(&state.config_path(), &profile_name)?;
     let query = format!("compartmentId={}", profile.tenancy);
-    let body =
-        client::oci_get_request(&profile, "identity", "/20160918/groups", Some(&query)).await?;
-    let groups: Vec<IamGroup> =
-        serde_json::from_str(&body).map_err(|e| format!("レスポンスの解析に失敗しました: {}", e))?;
-    Ok(groups)
+    client::oci_list_request(
+        &state.oci,
+        &profile,
+        "identity",
+        "/20160918/groups",
+        Some(&query),
+        max_items,
+    )
+    .await
+}
+
+/// コンパートメントツリーを取得
+///
+/// `query` を指定した場合は名前（"/" を含む場合はパス）が一致するコンパートメントとその祖先のみを返す。
+/// 一致するものがない場合は None。
+#[tauri::command]
+pub async fn list_compartments(
+    state: State<'_, AppState>,
+    profile_name: String,
+    query: Option<String>,
+    refresh: Option<bool>,
+) -> Result<Option<CompartmentNode>, OciError> {
+    let profile = client::load_profile(&state.config_path(), &profile_name)?;
+    let tree = compartments::compartment_tree(
+        &state.oci,
+        &state.compartments,
+        &profile,
+        refresh.unwrap_or(false),
+    )
+    .await?;
+    Ok(compartments::filter_tree(
+        &tree,
+        query.as_deref().unwrap_or_default(),
+    ))
 }
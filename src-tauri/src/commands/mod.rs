@@ -7,3 +7,4 @@ pub mod iam;
 pub mod network;
 pub mod profile;
 pub mod storage;
+pub mod work_request;
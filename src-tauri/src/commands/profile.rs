@@ -1,14 +1,23 @@
-use crate::models::profile::OciProfile;
+use tauri::State;
+
+use std::path::PathBuf;
+
+use crate::models::profile::{ImportedProfile, OciProfile};
+use crate::oci::client;
 use crate::oci::config_parser;
+use crate::oci::error::OciError;
+use crate::oci::profile_bundle::{self, KdfParams};
+use crate::oci::region_catalog;
 use crate::oci::regions::{get_available_regions, OciRegion};
+use crate::state::AppState;
 
 /// プロファイル名の一覧を取得
 #[tauri::command]
-pub fn list_profiles(path: Option<String>) -> Result<Vec<String>, String> {
-    let config_path = match path {
-        Some(p) => std::path::PathBuf::from(p),
-        None => config_parser::default_config_path(),
-    };
+pub fn list_profiles(
+    state: State<'_, AppState>,
+    path: Option<String>,
+) -> Result<Vec<String>, OciError> {
+    let config_path = state.resolve_config_path(path);
 
     if !config_path.exists() {
         return Ok(Vec::new());
@@ -20,89 +29,136 @@ pub fn list_profiles(path: Option<String>) -> Result<Vec<String>, String> {
 
 /// 指定名のプロファイルを取得
 #[tauri::command]
-pub fn get_profile(name: String, path: Option<String>) -> Result<OciProfile, String> {
-    let config_path = match path {
-        Some(p) => std::path::PathBuf::from(p),
-        None => config_parser::default_config_path(),
-    };
+pub fn get_profile(
+    state: State<'_, AppState>,
+    name: String,
+    path: Option<String>,
+) -> Result<OciProfile, OciError> {
+    let config_path = state.resolve_config_path(path);
 
     let profiles = config_parser::parse_config(&config_path)?;
     profiles
         .into_iter()
         .find(|p| p.name == name)
-        .ok_or_else(|| format!("プロファイル '{}' が見つかりません。", name))
+        .ok_or_else(|| OciError::config(format!("プロファイル '{}' が見つかりません。", name)))
 }
 
 /// プロファイルを作成・追加
 #[tauri::command]
-pub fn create_profile(profile: OciProfile, path: Option<String>) -> Result<(), String> {
-    let config_path = match path {
-        Some(p) => std::path::PathBuf::from(p),
-        None => config_parser::default_config_path(),
-    };
-
-    let mut profiles = if config_path.exists() {
-        config_parser::parse_config(&config_path)?
-    } else {
-        Vec::new()
-    };
-
-    // 同名プロファイルが存在する場合はエラー
-    if profiles.iter().any(|p| p.name == profile.name) {
-        return Err(format!(
+pub fn create_profile(
+    state: State<'_, AppState>,
+    profile: OciProfile,
+    path: Option<String>,
+) -> Result<(), OciError> {
+    let config_path = state.resolve_config_path(path);
+
+    // 同名のセクションが存在する場合はエラー（不完全なセクションも上書きしない）
+    if config_path.exists()
+        && config_parser::read_document(&config_path)?.has_section(&profile.name)
+    {
+        return Err(OciError::config(format!(
             "プロファイル '{}' は既に存在します。",
             profile.name
-        ));
+        )));
     }
 
-    profiles.push(profile);
-    config_parser::write_config(&config_path, &profiles)
+    config_parser::upsert_profile(&config_path, None, &profile)?;
+    state.oci.keys.clear();
+    Ok(())
 }
 
 /// プロファイルを更新
 #[tauri::command]
 pub fn update_profile(
+    state: State<'_, AppState>,
     name: String,
     profile: OciProfile,
     path: Option<String>,
-) -> Result<(), String> {
-    let config_path = match path {
-        Some(p) => std::path::PathBuf::from(p),
-        None => config_parser::default_config_path(),
-    };
-
-    let mut profiles = config_parser::parse_config(&config_path)?;
-
-    let idx = profiles
-        .iter()
-        .position(|p| p.name == name)
-        .ok_or_else(|| format!("プロファイル '{}' が見つかりません。", name))?;
+) -> Result<(), OciError> {
+    let config_path = state.resolve_config_path(path);
+
+    if !config_parser::read_document(&config_path)?.has_section(&name) {
+        return Err(OciError::config(format!(
+            "プロファイル '{}' が見つかりません。",
+            name
+        )));
+    }
 
-    profiles[idx] = profile;
-    config_parser::write_config(&config_path, &profiles)
+    config_parser::upsert_profile(&config_path, Some(&name), &profile)?;
+    state.oci.keys.clear();
+    Ok(())
 }
 
 /// プロファイルを削除
 #[tauri::command]
-pub fn delete_profile(name: String, path: Option<String>) -> Result<(), String> {
-    let config_path = match path {
-        Some(p) => std::path::PathBuf::from(p),
-        None => config_parser::default_config_path(),
-    };
+pub fn delete_profile(
+    state: State<'_, AppState>,
+    name: String,
+    path: Option<String>,
+) -> Result<(), OciError> {
+    let config_path = state.resolve_config_path(path);
 
-    let mut profiles = config_parser::parse_config(&config_path)?;
-    let original_len = profiles.len();
-    profiles.retain(|p| p.name != name);
+    config_parser::remove_profile(&config_path, &name)?;
+    state.oci.keys.clear();
+    Ok(())
+}
 
-    if profiles.len() == original_len {
-        return Err(format!("プロファイル '{}' が見つかりません。", name));
-    }
+/// プロファイルを鍵・トークンファイルとともに暗号化したバンドルに書き出す
+#[tauri::command]
+pub fn export_profile_bundle(
+    state: State<'_, AppState>,
+    names: Vec<String>,
+    passphrase: String,
+    path: String,
+) -> Result<(), OciError> {
+    profile_bundle::export_bundle(
+        &state.config_path(),
+        &names,
+        &passphrase,
+        &PathBuf::from(path),
+        KdfParams::default(),
+    )
+    .map_err(OciError::config)
+}
 
-    config_parser::write_config(&config_path, &profiles)
+/// 暗号化したバンドルからプロファイルをインポート
+///
+/// エンドポイントの上書きは `allow_endpoints` を指定した場合のみ取り込む。
+#[tauri::command]
+pub fn import_profile_bundle(
+    state: State<'_, AppState>,
+    path: String,
+    passphrase: String,
+    allow_endpoints: Option<bool>,
+) -> Result<Vec<ImportedProfile>, OciError> {
+    let imported = profile_bundle::import_bundle(
+        &state.config_path(),
+        &PathBuf::from(path),
+        &passphrase,
+        allow_endpoints.unwrap_or(false),
+    )?;
+    state.oci.keys.clear();
+    Ok(imported)
 }
 
 /// 利用可能なリージョン一覧を取得
+///
+/// プロファイルを指定した場合はテナンシのサブスクリプションを含む一覧（サブスクライブ済みが先頭）。
 #[tauri::command]
-pub fn get_regions() -> Vec<OciRegion> {
-    get_available_regions()
+pub async fn get_regions(
+    state: State<'_, AppState>,
+    profile_name: Option<String>,
+    refresh: Option<bool>,
+) -> Result<Vec<OciRegion>, OciError> {
+    let Some(profile_name) = profile_name else {
+        return Ok(get_available_regions());
+    };
+    let profile = client::load_profile(&state.config_path(), &profile_name)?;
+    region_catalog::region_catalog(
+        &state.oci,
+        &profile,
+        &state.cache_dir(),
+        refresh.unwrap_or(false),
+    )
+    .await
 }
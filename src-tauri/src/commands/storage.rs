@@ -1,31 +1,107 @@
-use crate::models::storage::ObjectStorageBucket;
-use crate::oci::client;
+use tauri::State;
+
+use crate::models::iam::CompartmentListing;
+use crate::models::storage::{ObjectStorageBucket, ObjectSummary};
+use crate::oci::client::{self, Pagination};
+use crate::oci::error::OciError;
+use crate::oci::fan_out::{self, ListScope};
+use crate::oci::region_catalog::{self, RegionScope};
+use crate::state::AppState;
 
 /// オブジェクトストレージのネームスペースを取得
 #[tauri::command]
-pub async fn get_namespace(profile_name: String) -> Result<String, String> {
-    let profile = client::load_profile(&profile_name)?;
-    let body =
-        client::oci_get_request(&profile, "objectstorage", "/n/", None).await?;
+pub async fn get_namespace(
+    state: State<'_, AppState>,
+    profile_name: String,
+) -> Result<String, OciError> {
+    let profile = client::load_profile(&state.config_path(), &profile_name)?;
+    let body = client::oci_get_request(&state.oci, &profile, "objectstorage", "/n/", None).await?;
     // レスポンスはクォートされた文字列
-    let ns: String = serde_json::from_str(&body)
-        .map_err(|e| format!("レスポンスの解析に失敗しました: {}", e))?;
+    let ns: String = serde_json::from_str(&body).map_err(|e| OciError::parse(e.to_string()))?;
     Ok(ns)
 }
 
 /// バケット一覧を取得
+///
+/// `include_subtree` を指定すると子孫コンパートメントも含める。`regions` で複数のリージョン
+/// （またはサブスクライブしているすべてのリージョン）を指定できる。各行にリージョンとコンパートメントのパスを付ける。
 #[tauri::command]
 pub async fn list_buckets(
+    state: State<'_, AppState>,
     profile_name: String,
     compartment_id: String,
     namespace: String,
-) -> Result<Vec<ObjectStorageBucket>, String> {
-    let profile = client::load_profile(&profile_name)?;
+    include_subtree: Option<bool>,
+    regions: Option<RegionScope>,
+    max_items: Option<usize>,
+) -> Result<CompartmentListing<ObjectStorageBucket>, OciError> {
+    let profile = client::load_profile(&state.config_path(), &profile_name)?;
     let path = format!("/n/{}/b/", namespace);
-    let query = format!("compartmentId={}", compartment_id);
-    let body =
-        client::oci_get_request(&profile, "objectstorage", &path, Some(&query)).await?;
-    let buckets: Vec<ObjectStorageBucket> =
-        serde_json::from_str(&body).map_err(|e| format!("レスポンスの解析に失敗しました: {}", e))?;
-    Ok(buckets)
+    let regions = region_catalog::resolve_regions(
+        &state.oci,
+        &profile,
+        &state.cache_dir(),
+        &regions.unwrap_or_default(),
+    )
+    .await?;
+    fan_out::list_in_compartments(
+        &state.oci,
+        &state.compartments,
+        &profile,
+        &ListScope {
+            compartment_id,
+            include_subtree: include_subtree.unwrap_or(false),
+            regions,
+        },
+        max_items,
+        |profile, compartment_id| {
+            let (state, path) = (&state, &path);
+            async move {
+                let query = format!("compartmentId={}", compartment_id);
+                client::oci_list_request(
+                    &state.oci,
+                    &profile,
+                    "objectstorage",
+                    path,
+                    Some(&query),
+                    max_items,
+                )
+                .await
+            }
+        },
+    )
+    .await
+}
+
+/// バケット内のオブジェクト一覧を取得
+#[tauri::command]
+pub async fn list_objects(
+    state: State<'_, AppState>,
+    profile_name: String,
+    namespace: String,
+    bucket_name: String,
+    prefix: Option<String>,
+    max_items: Option<usize>,
+) -> Result<Vec<ObjectSummary>, OciError> {
+    let profile = client::load_profile(&state.config_path(), &profile_name)?;
+    let path = format!("/n/{}/b/{}/o", namespace, bucket_name);
+    let mut query = "fields=name,size,timeCreated".to_string();
+    if let Some(prefix) = prefix.filter(|p| !p.is_empty()) {
+        query.push_str(&format!(
+            "&prefix={}",
+            percent_encoding::utf8_percent_encode(&prefix, percent_encoding::NON_ALPHANUMERIC)
+        ));
+    }
+    client::oci_paginated_request(
+        &state.oci,
+        &profile,
+        "objectstorage",
+        &path,
+        Some(&query),
+        Pagination::NextStartWith {
+            items_field: "objects",
+        },
+        max_items,
+    )
+    .await
 }
@@ -0,0 +1,101 @@
+use tauri::{AppHandle, Emitter, State};
+
+use crate::models::work_request::{LifecycleStateProgress, WorkRequest};
+use crate::oci::client;
+use crate::oci::error::OciError;
+use crate::oci::waiter::{self, Poller, WaitPolicy};
+use crate::oci::work_requests;
+use crate::state::AppState;
+
+/// ワークリクエストの進捗を通知するイベント
+pub const WORK_REQUEST_PROGRESS_EVENT: &str = "work-request-progress";
+
+/// リソースのライフサイクル状態の進捗を通知するイベント
+pub const LIFECYCLE_STATE_PROGRESS_EVENT: &str = "lifecycle-state-progress";
+
+/// コンパートメントのワークリクエスト一覧を取得
+#[tauri::command]
+pub async fn list_work_requests(
+    state: State<'_, AppState>,
+    profile_name: String,
+    compartment_id: String,
+    resource_id: Option<String>,
+    max_items: Option<usize>,
+) -> Result<Vec<WorkRequest>, OciError> {
+    let profile = client::load_profile(&state.config_path(), &profile_name)?;
+    work_requests::list_work_requests(
+        &state.oci,
+        &profile,
+        &compartment_id,
+        resource_id.as_deref(),
+        max_items,
+    )
+    .await
+}
+
+/// ワークリクエストが完了するまで待つ
+///
+/// 状態を確認するたびに `work-request-progress` イベントを送る。
+/// 待機はワークリクエスト OCID を指定した `cancel_wait` で中止できる。
+#[tauri::command]
+pub async fn wait_for_work_request(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    profile_name: String,
+    work_request_id: String,
+    timeout_secs: Option<u64>,
+) -> Result<WorkRequest, OciError> {
+    let profile = client::load_profile(&state.config_path(), &profile_name)?;
+    let profile = waiter::profile_for_resource(&profile, &work_request_id);
+    let handle = state.waits.register(&work_request_id);
+    work_requests::wait_for_work_request(
+        &state.oci,
+        &profile,
+        &work_request_id,
+        Poller::new(WaitPolicy::with_timeout_secs(timeout_secs), handle.token()),
+        |progress| {
+            app.emit(WORK_REQUEST_PROGRESS_EVENT, progress).ok();
+        },
+    )
+    .await
+}
+
+/// リソースが `target_states` のいずれかになるまで待ち、その状態を返す
+///
+/// 状態を確認するたびに `lifecycle-state-progress` イベントを送る。
+/// 待機はリソース OCID を指定した `cancel_wait` で中止できる。
+#[tauri::command]
+pub async fn wait_for_lifecycle_state(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    profile_name: String,
+    resource_id: String,
+    target_states: Vec<String>,
+    timeout_secs: Option<u64>,
+) -> Result<String, OciError> {
+    let profile = client::load_profile(&state.config_path(), &profile_name)?;
+    let profile = waiter::profile_for_resource(&profile, &resource_id);
+    let handle = state.waits.register(&resource_id);
+    waiter::wait_for_lifecycle_state(
+        &state.oci,
+        &profile,
+        &resource_id,
+        &target_states,
+        Poller::new(WaitPolicy::with_timeout_secs(timeout_secs), handle.token()),
+        |lifecycle_state| {
+            let progress = LifecycleStateProgress {
+                resource_id: resource_id.clone(),
+                lifecycle_state: lifecycle_state.to_string(),
+                done: target_states.iter().any(|s| s == lifecycle_state),
+            };
+            app.emit(LIFECYCLE_STATE_PROGRESS_EVENT, &progress).ok();
+        },
+    )
+    .await
+}
+
+/// 実行中の待機を中止する（該当する待機がなければ false）
+#[tauri::command]
+pub fn cancel_wait(state: State<'_, AppState>, id: String) -> bool {
+    state.waits.cancel(&id)
+}
@@ -1,17 +1,30 @@
 mod commands;
-mod models;
-mod oci;
+pub mod models;
+pub mod oci;
+mod state;
 
-use commands::auth::{test_connection, validate_profile};
-use commands::compute::list_instances;
-use commands::config::{get_default_config_path, import_oci_cli_config, load_oci_config};
+use commands::auth::{
+    generate_api_key, get_session_status, refresh_session, set_session_passphrase, sso_login,
+    test_connection, validate_profile,
+};
+use commands::compute::{instance_action, list_instances};
+use commands::config::{
+    diagnose_config, diff_config_backup, get_active_config_path, get_default_config_path,
+    get_default_profile_name, import_oci_cli_config, list_config_backups, load_oci_config,
+    restore_config_backup, set_active_config_path, watch_active_config,
+};
 use commands::database::list_db_systems;
-use commands::iam::{list_groups, list_users};
+use commands::iam::{list_compartments, list_groups, list_users};
 use commands::network::list_vcns;
 use commands::profile::{
-    create_profile, delete_profile, get_profile, get_regions, list_profiles, update_profile,
+    create_profile, delete_profile, export_profile_bundle, get_profile, get_regions,
+    import_profile_bundle, list_profiles, update_profile,
 };
-use commands::storage::{get_namespace, list_buckets};
+use commands::storage::{get_namespace, list_buckets, list_objects};
+use commands::work_request::{
+    cancel_wait, list_work_requests, wait_for_lifecycle_state, wait_for_work_request,
+};
+use state::AppState;
 use tauri::Manager;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -29,10 +42,20 @@ pub fn run() {
     }
 
     tauri::Builder::default()
+        .plugin(
+            tauri_plugin_log::Builder::new()
+                .level(log::LevelFilter::Info)
+                .build(),
+        )
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_opener::init())
+        .manage(AppState::default())
         .setup(|app| {
+            if let Ok(dir) = app.path().app_cache_dir() {
+                app.state::<AppState>().set_cache_dir(dir);
+            }
+            watch_active_config(app.handle());
             if let Some(window) = app.get_webview_window("main") {
                 let _ = window.show();
                 let _ = window.unminimize();
@@ -43,7 +66,14 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             // 設定コマンド
             get_default_config_path,
+            get_active_config_path,
+            set_active_config_path,
+            get_default_profile_name,
             load_oci_config,
+            diagnose_config,
+            list_config_backups,
+            diff_config_backup,
+            restore_config_backup,
             import_oci_cli_config,
             // プロファイルコマンド
             list_profiles,
@@ -51,15 +81,24 @@ pub fn run() {
             create_profile,
             update_profile,
             delete_profile,
+            export_profile_bundle,
+            import_profile_bundle,
             get_regions,
             // 認証コマンド
             validate_profile,
             test_connection,
+            set_session_passphrase,
+            get_session_status,
+            refresh_session,
+            sso_login,
+            generate_api_key,
             // コンピュートコマンド
             list_instances,
+            instance_action,
             // ストレージコマンド
             get_namespace,
             list_buckets,
+            list_objects,
             // ネットワークコマンド
             list_vcns,
             // データベースコマンド
@@ -67,6 +106,12 @@ pub fn run() {
             // IAM コマンド
             list_users,
             list_groups,
+            list_compartments,
+            // ワークリクエストコマンド
+            list_work_requests,
+            wait_for_work_request,
+            wait_for_lifecycle_state,
+            cancel_wait,
         ])
         .run(tauri::generate_context!())
         .expect("アプリケーションの起動に失敗しました");
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::oci::error::OciError;
+
 /// コンピュートインスタンス情報
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ComputeInstance {
@@ -20,3 +22,72 @@ pub struct ComputeInstance {
     #[serde(rename = "timeCreated", alias = "time_created")]
     pub time_created: Option<String>,
 }
+
+/// インスタンスの電源操作（InstanceAction API の action）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum InstanceAction {
+    /// 起動
+    Start,
+    /// 強制停止
+    Stop,
+    /// ACPI シャットダウン後に停止
+    Softstop,
+    /// 強制再起動
+    Reset,
+    /// ACPI シャットダウン後に再起動
+    Softreset,
+}
+
+impl InstanceAction {
+    /// API に渡す値
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Start => "START",
+            Self::Stop => "STOP",
+            Self::Softstop => "SOFTSTOP",
+            Self::Reset => "RESET",
+            Self::Softreset => "SOFTRESET",
+        }
+    }
+
+    /// 操作が完了したときのライフサイクル状態
+    pub fn target_state(self) -> &'static str {
+        match self {
+            Self::Start | Self::Reset | Self::Softreset => "RUNNING",
+            Self::Stop | Self::Softstop => "STOPPED",
+        }
+    }
+
+    /// 操作前のライフサイクル状態（再起動は操作前と完了時が同じ RUNNING）
+    pub fn origin_state(self) -> &'static str {
+        match self {
+            Self::Start => "STOPPED",
+            Self::Stop | Self::Softstop | Self::Reset | Self::Softreset => "RUNNING",
+        }
+    }
+}
+
+/// インスタンスごとの電源操作の結果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceActionResult {
+    pub instance_id: String,
+    /// 操作後（待機した場合は待機後）のライフサイクル状態
+    pub lifecycle_state: Option<String>,
+    /// 操作のワークリクエスト OCID（返された場合のみ）
+    pub work_request_id: Option<String>,
+    /// 失敗した場合のエラー
+    pub error: Option<OciError>,
+}
+
+/// 電源操作の進捗（instance-action-progress イベント）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceActionProgress {
+    pub instance_id: String,
+    pub action: InstanceAction,
+    pub lifecycle_state: String,
+    /// 目的の状態に達したか
+    pub done: bool,
+}
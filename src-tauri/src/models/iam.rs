@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::oci::error::OciError;
+
 /// IAM ユーザー情報
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IamUser {
@@ -33,3 +35,69 @@ pub struct IamGroup {
     #[serde(rename = "timeCreated", alias = "time_created")]
     pub time_created: Option<String>,
 }
+
+/// コンパートメント情報（ListCompartments の要素）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Compartment {
+    /// コンパートメント OCID
+    pub id: String,
+    /// コンパートメント名
+    pub name: String,
+    /// 説明
+    pub description: Option<String>,
+    /// 親コンパートメントの OCID
+    #[serde(rename = "compartmentId", alias = "compartment_id")]
+    pub compartment_id: String,
+    /// ライフサイクル状態
+    #[serde(rename = "lifecycleState", alias = "lifecycle_state")]
+    pub lifecycle_state: String,
+}
+
+/// コンパートメントツリーのノード
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompartmentNode {
+    /// コンパートメント OCID（ルートはテナンシ OCID）
+    pub id: String,
+    /// コンパートメント名（ルートは "root"）
+    pub name: String,
+    /// ルートからのパス（例: "root/prod/app"）
+    pub path: String,
+    /// 説明
+    pub description: Option<String>,
+    /// 子コンパートメント（名前順）
+    pub children: Vec<CompartmentNode>,
+}
+
+/// リージョン・コンパートメントのパスを付けた一覧の要素
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompartmentItem<T> {
+    #[serde(flatten)]
+    pub item: T,
+    /// リソースが属するリージョン
+    pub region: String,
+    /// リソースが属するコンパートメントの OCID
+    pub compartment_id: String,
+    /// リソースが属するコンパートメントのパス（例: "root/prod/app"）
+    pub compartment_path: String,
+}
+
+/// 一覧を取得できなかったリージョン・コンパートメント
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompartmentFailure {
+    pub region: String,
+    pub compartment_id: String,
+    pub compartment_path: String,
+    pub error: OciError,
+}
+
+/// 複数のリージョン・コンパートメントにまたがる一覧
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompartmentListing<T> {
+    pub items: Vec<CompartmentItem<T>>,
+    /// 権限不足（NotAuthorizedOrNotFound など）や障害で取得できなかったリージョン・コンパートメント
+    pub failures: Vec<CompartmentFailure>,
+}
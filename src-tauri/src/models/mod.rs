@@ -5,3 +5,4 @@ pub mod storage;
 pub mod network;
 pub mod database;
 pub mod iam;
+pub mod work_request;
@@ -1,7 +1,9 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 /// OCI プロファイル設定
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct OciProfile {
     /// プロファイル名（例: "DEFAULT", "PRODUCTION"）
     pub name: String,
@@ -15,6 +17,50 @@ pub struct OciProfile {
     pub fingerprint: String,
     /// 秘密鍵ファイルパス
     pub key_file: String,
+    /// 暗号化された秘密鍵のパスフレーズ（設定ファイルの pass_phrase。フロントエンドには送らない）
+    #[serde(default, skip_serializing)]
+    pub pass_phrase: Option<String>,
+    /// セッショントークンファイル（`oci session authenticate` で作成されたプロファイル）
+    #[serde(default)]
+    pub security_token_file: Option<String>,
+    /// プリンシパル認証（設定ファイルの auth_type。未指定時は API キーまたはセッショントークン認証）
+    #[serde(default)]
+    pub principal: Option<PrincipalType>,
+    /// 全サービス共通のエンドポイント上書き（例: "http://localhost:8080"）
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    /// サービス別のエンドポイント上書き（キーはサービス名。例: "iaas"）
+    #[serde(default)]
+    pub service_endpoints: BTreeMap<String, String>,
+}
+
+/// プリンシパル認証の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrincipalType {
+    /// インスタンス・プリンシパル（インスタンス・メタデータ・サービスの証明書）
+    InstancePrincipal,
+    /// リソース・プリンシパル（OCI_RESOURCE_PRINCIPAL_* 環境変数）
+    ResourcePrincipal,
+}
+
+impl PrincipalType {
+    /// 設定ファイルの auth_type の値
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::InstancePrincipal => "instance_principal",
+            Self::ResourcePrincipal => "resource_principal",
+        }
+    }
+
+    /// 設定ファイルの auth_type の値から変換
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "instance_principal" => Some(Self::InstancePrincipal),
+            "resource_principal" => Some(Self::ResourcePrincipal),
+            _ => None,
+        }
+    }
 }
 
 /// 接続テスト結果
@@ -26,6 +72,18 @@ pub struct ConnectionResult {
     pub message: String,
 }
 
+/// セッショントークンの状態
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionStatus {
+    /// 有効期限（RFC 3339。トークンから読み取れない場合は None）
+    pub expires_at: Option<String>,
+    /// 有効期限までの残り秒数（期限切れの場合は 0）
+    pub seconds_remaining: Option<i64>,
+    /// 期限切れかどうか
+    pub expired: bool,
+}
+
 /// バリデーション結果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidationResult {
@@ -33,4 +91,113 @@ pub struct ValidationResult {
     pub valid: bool,
     /// エラーメッセージ一覧
     pub errors: Vec<String>,
+    /// 警告メッセージ一覧（保存は可能）
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+/// 設定ファイルの行
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigLine {
+    /// 行番号（1 始まり）
+    pub line: usize,
+    /// 行の内容
+    pub text: String,
+}
+
+/// 設定ファイルのセクションの診断結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SectionDiagnostic {
+    /// セクション名
+    pub name: String,
+    /// ヘッダー行の行番号
+    pub start_line: usize,
+    /// セクションの最終行の行番号
+    pub end_line: usize,
+    /// プロファイルとして読み込めるかどうか
+    pub valid: bool,
+    /// 同名のセクションが先にあるかどうか（このセクションは無視される）
+    pub duplicate: bool,
+    /// 不足している必須キー（[DEFAULT] からの継承を考慮）
+    pub missing_keys: Vec<String>,
+    /// このアプリと OCI CLI が使わないキー
+    pub unknown_keys: Vec<String>,
+    /// 存在しないファイルを指すキーとそのパス（例: "key_file: /home/opc/.oci/key.pem"）
+    pub unresolved_files: Vec<String>,
+    /// 解析できない行
+    pub unparsable_lines: Vec<ConfigLine>,
+    /// 読み込めた値で作成したプロファイル（修復の初期値。不足しているキーは空）
+    pub draft: OciProfile,
+}
+
+/// 設定ファイルの診断結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigDiagnostics {
+    /// 各セクションの診断結果（ファイル内の順序）
+    pub sections: Vec<SectionDiagnostic>,
+    /// 最初のセクションより前にある解析できない行
+    pub orphan_lines: Vec<ConfigLine>,
+}
+
+/// 設定ファイルの変更で追加・削除・変更されたプロファイル
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProfileChanges {
+    /// 追加されたプロファイル
+    pub added: Vec<OciProfile>,
+    /// 削除されたプロファイル名
+    pub removed: Vec<String>,
+    /// 変更されたプロファイル（参照している鍵・トークンファイルの変更を含む）
+    pub changed: Vec<OciProfile>,
+}
+
+impl ProfileChanges {
+    /// 変更がないかどうか
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// 設定ファイルのバックアップ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigBackup {
+    /// バックアップ ID（バックアップディレクトリ内のファイル名）
+    pub id: String,
+    /// 作成日時（RFC 3339）
+    pub created_at: String,
+    /// ファイルサイズ（バイト）
+    pub size: u64,
+}
+
+/// 差分の行の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DiffLineKind {
+    /// 両方にある行
+    Same,
+    /// 変更後にのみある行
+    Added,
+    /// 変更前にのみある行
+    Removed,
+}
+
+/// 差分の 1 行
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    pub text: String,
+}
+
+/// バンドルからインポートしたプロファイル
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedProfile {
+    /// 設定ファイルに追加した名前
+    pub name: String,
+    /// バンドル内の名前（同名のプロファイルがある場合は `name` と異なる）
+    pub original_name: String,
+    /// バンドルにあったエンドポイントの上書きを取り込まなかったかどうか
+    pub dropped_endpoints: bool,
 }
@@ -14,3 +14,15 @@ pub struct ObjectStorageBucket {
     #[serde(rename = "timeCreated", alias = "time_created")]
     pub time_created: Option<String>,
 }
+
+/// オブジェクトストレージのオブジェクト情報
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectSummary {
+    /// オブジェクト名
+    pub name: String,
+    /// サイズ（バイト）
+    pub size: Option<u64>,
+    /// 作成日時
+    #[serde(rename = "timeCreated", alias = "time_created")]
+    pub time_created: Option<String>,
+}
@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+
+/// ワークリクエスト（Work Requests API）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkRequest {
+    /// ワークリクエスト OCID
+    pub id: String,
+    /// 操作の種類（例: LaunchInstance）
+    #[serde(rename = "operationType", alias = "operation_type")]
+    pub operation_type: String,
+    /// 状態（ACCEPTED / IN_PROGRESS / SUCCEEDED / FAILED / CANCELING / CANCELED）
+    pub status: String,
+    /// コンパートメント OCID
+    #[serde(rename = "compartmentId", alias = "compartment_id")]
+    pub compartment_id: Option<String>,
+    /// 進捗率（0〜100）
+    #[serde(rename = "percentComplete", alias = "percent_complete")]
+    pub percent_complete: Option<f32>,
+    /// 操作対象のリソース
+    #[serde(default)]
+    pub resources: Vec<WorkRequestResource>,
+    /// 受付日時
+    #[serde(rename = "timeAccepted", alias = "time_accepted")]
+    pub time_accepted: Option<String>,
+    /// 開始日時
+    #[serde(rename = "timeStarted", alias = "time_started")]
+    pub time_started: Option<String>,
+    /// 完了日時
+    #[serde(rename = "timeFinished", alias = "time_finished")]
+    pub time_finished: Option<String>,
+}
+
+impl WorkRequest {
+    /// 完了した（SUCCEEDED / FAILED / CANCELED）かどうか
+    pub fn is_finished(&self) -> bool {
+        matches!(self.status.as_str(), "SUCCEEDED" | "FAILED" | "CANCELED")
+    }
+}
+
+/// ワークリクエストの操作対象
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkRequestResource {
+    /// リソースの種類（例: instance）
+    #[serde(rename = "entityType", alias = "entity_type")]
+    pub entity_type: String,
+    /// 操作（CREATED / UPDATED / DELETED / IN_PROGRESS など）
+    #[serde(rename = "actionType", alias = "action_type")]
+    pub action_type: String,
+    /// リソース OCID
+    pub identifier: String,
+    /// リソースを取得する URI
+    #[serde(rename = "entityUri", alias = "entity_uri")]
+    pub entity_uri: Option<String>,
+}
+
+/// ワークリクエストのエラー
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkRequestError {
+    /// エラーコード
+    pub code: String,
+    /// メッセージ
+    pub message: String,
+    /// 発生日時
+    pub timestamp: Option<String>,
+}
+
+/// ワークリクエストのログ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkRequestLogEntry {
+    /// メッセージ
+    pub message: String,
+    /// 記録日時
+    pub timestamp: Option<String>,
+}
+
+/// ワークリクエストの進捗（work-request-progress イベント）
+///
+/// `errors` と `log_entries` には前回の通知以降に増えた分だけを入れる。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkRequestProgress {
+    pub work_request_id: String,
+    pub status: String,
+    pub percent_complete: Option<f32>,
+    pub errors: Vec<WorkRequestError>,
+    pub log_entries: Vec<WorkRequestLogEntry>,
+    /// 完了したか
+    pub done: bool,
+}
+
+/// リソースのライフサイクル状態の進捗（lifecycle-state-progress イベント）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LifecycleStateProgress {
+    pub resource_id: String,
+    pub lifecycle_state: String,
+    /// 目的の状態に達したか
+    pub done: bool,
+}
@@ -0,0 +1,262 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use reqwest::Method;
+use rsa::pkcs8::{EncodePrivateKey, EncodePublicKey, LineEnding};
+use rsa::RsaPrivateKey;
+use serde::Deserialize;
+
+use crate::models::profile::OciProfile;
+use crate::oci::client::{oci_request, OciClient, OciRequest};
+use crate::oci::config_parser;
+use crate::oci::error::OciError;
+use crate::oci::signature::public_key_fingerprint;
+
+/// 生成できる鍵の長さ
+pub const SUPPORTED_KEY_BITS: [usize; 2] = [2048, 4096];
+
+/// 保存した鍵ペアのパスとフィンガープリント
+#[derive(Debug, Clone)]
+pub struct SavedKeyPair {
+    pub private_key_file: PathBuf,
+    pub public_key_file: PathBuf,
+    /// 公開鍵の PEM
+    pub public_key_pem: String,
+    pub fingerprint: String,
+}
+
+/// UploadApiKey API のレスポンス（必要なフィールドのみ）
+#[derive(Debug, Deserialize)]
+struct ApiKey {
+    fingerprint: String,
+}
+
+/// RSA 鍵ペアを生成（2048 または 4096 ビット）
+pub async fn generate_key_pair(bits: usize) -> Result<RsaPrivateKey, OciError> {
+    if !SUPPORTED_KEY_BITS.contains(&bits) {
+        return Err(OciError::config(format!(
+            "鍵の長さは 2048 または 4096 ビットを指定してください。（指定値: {}）",
+            bits
+        )));
+    }
+
+    tokio::task::spawn_blocking(move || RsaPrivateKey::new(&mut rand::thread_rng(), bits))
+        .await
+        .map_err(|e| OciError::auth(e.to_string()))?
+        .map_err(|e| OciError::auth(format!("鍵ペアの生成に失敗しました: {}", e)))
+}
+
+/// 鍵ペアを `<dir>/<base_name>.pem` と `<dir>/<base_name>_public.pem` に保存する
+///
+/// `overwrite` が false の場合、既存のファイルは上書きしない。
+pub fn save_key_pair(
+    dir: &Path,
+    base_name: &str,
+    private_key: &RsaPrivateKey,
+    overwrite: bool,
+) -> Result<SavedKeyPair, OciError> {
+    let private_key_file = dir.join(format!("{}.pem", base_name));
+    let public_key_file = dir.join(format!("{}_public.pem", base_name));
+    if !overwrite {
+        if let Some(existing) = [&private_key_file, &public_key_file]
+            .into_iter()
+            .find(|path| path.exists())
+        {
+            return Err(OciError::config(format!(
+                "ファイル '{}' は既に存在します。",
+                existing.display()
+            )));
+        }
+    }
+
+    fs::create_dir_all(dir)
+        .map_err(|e| OciError::config(format!("ディレクトリの作成に失敗しました: {}", e)))?;
+
+    let public_key = private_key.to_public_key();
+    let private_pem = private_key
+        .to_pkcs8_pem(LineEnding::LF)
+        .map_err(|e| OciError::auth(format!("秘密鍵のエンコードに失敗しました: {}", e)))?;
+    let public_key_pem = public_key
+        .to_public_key_pem(LineEnding::LF)
+        .map_err(|e| OciError::auth(format!("公開鍵のエンコードに失敗しました: {}", e)))?;
+
+    write_private_file(&private_key_file, private_pem.as_bytes())?;
+    write_private_file(&public_key_file, public_key_pem.as_bytes())?;
+
+    Ok(SavedKeyPair {
+        private_key_file,
+        public_key_file,
+        public_key_pem,
+        fingerprint: public_key_fingerprint(&public_key)?,
+    })
+}
+
+/// 新しく作成したファイル（Unix系ではパーミッション 600）に書き込む
+///
+/// 既存のファイルは削除してから作り直すため、書き込み中も他のユーザーからは読めない。
+pub fn write_private_file(path: &Path, contents: &[u8]) -> Result<(), OciError> {
+    let write_error = |e: std::io::Error| {
+        OciError::config(format!(
+            "ファイル '{}' の書き込みに失敗しました: {}",
+            path.display(),
+            e
+        ))
+    };
+
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(write_error(e)),
+        _ => {}
+    }
+
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options.open(path).map_err(write_error)?;
+    file.write_all(contents)
+        .and_then(|_| file.sync_all())
+        .map_err(write_error)
+}
+
+/// Identity の UploadApiKey API で公開鍵をユーザーに登録し、登録されたフィンガープリントを返す
+pub async fn upload_api_key(
+    client: &OciClient,
+    profile: &OciProfile,
+    user_id: &str,
+    public_key_pem: &str,
+) -> Result<String, OciError> {
+    let request = OciRequest::new(
+        Method::POST,
+        "identity",
+        &format!("/20160918/users/{}/apiKeys", user_id),
+    )
+    .json(&serde_json::json!({ "key": public_key_pem }))?;
+
+    let response = oci_request(client, profile, request).await?;
+    let api_key: ApiKey =
+        serde_json::from_str(&response.body).map_err(|e| OciError::parse(e.to_string()))?;
+    Ok(api_key.fingerprint)
+}
+
+/// Identity の DeleteApiKey API でユーザーの API キーを削除する
+pub async fn delete_api_key(
+    client: &OciClient,
+    profile: &OciProfile,
+    user_id: &str,
+    fingerprint: &str,
+) -> Result<(), OciError> {
+    let request = OciRequest::new(
+        Method::DELETE,
+        "identity",
+        &format!("/20160918/users/{}/apiKeys/{}", user_id, fingerprint),
+    );
+    oci_request(client, profile, request).await?;
+    Ok(())
+}
+
+/// 鍵ペアを生成してユーザーに登録し、`<key_dir>/oci_api_key_<プロファイル名>.pem` を使うプロファイルを返す
+pub async fn create_api_key_profile(
+    client: &OciClient,
+    source: &OciProfile,
+    new_profile_name: &str,
+    bits: usize,
+    key_dir: &Path,
+) -> Result<OciProfile, OciError> {
+    // 名前は鍵ファイル名とセクション名に使うため、ファイルに触れる前に確認する
+    config_parser::check_profile_name(new_profile_name)?;
+    if source.user.is_empty() {
+        return Err(OciError::config(format!(
+            "プロファイル '{}' にユーザー OCID が設定されていません。",
+            source.name
+        )));
+    }
+
+    let private_key = generate_key_pair(bits).await?;
+    let saved = save_key_pair(
+        key_dir,
+        &format!("oci_api_key_{}", new_profile_name),
+        &private_key,
+        false,
+    )?;
+
+    let remove_files = || {
+        fs::remove_file(&saved.private_key_file).ok();
+        fs::remove_file(&saved.public_key_file).ok();
+    };
+    let uploaded = upload_api_key(client, source, &source.user, &saved.public_key_pem).await;
+    let fingerprint = match uploaded {
+        Ok(fingerprint) => fingerprint,
+        Err(e) => {
+            remove_files();
+            return Err(e);
+        }
+    };
+    if fingerprint != saved.fingerprint {
+        remove_files();
+        // 登録済みのキーも残さない（削除に失敗しても不一致のエラーを返す）
+        delete_api_key(client, source, &source.user, &fingerprint)
+            .await
+            .ok();
+        return Err(OciError::auth(format!(
+            "登録されたフィンガープリント（{}）が生成した鍵（{}）と一致しません。",
+            fingerprint, saved.fingerprint
+        )));
+    }
+
+    Ok(OciProfile {
+        name: new_profile_name.to_string(),
+        user: source.user.clone(),
+        tenancy: source.tenancy.clone(),
+        region: source.region.clone(),
+        fingerprint,
+        key_file: saved.private_key_file.to_string_lossy().to_string(),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_private_file_replaces_with_owner_only_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("key.pem");
+        fs::write(&path, "old").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        write_private_file(&path, b"new").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[tokio::test]
+    async fn test_create_api_key_profile_rejects_unsafe_names_before_writing() {
+        let dir = tempfile::tempdir().unwrap();
+        let key_dir = dir.path().join("oci");
+        fs::create_dir(&key_dir).unwrap();
+        let source = OciProfile {
+            name: "DEFAULT".to_string(),
+            user: "ocid1.user.oc1..test".to_string(),
+            ..Default::default()
+        };
+
+        for name in ["../escaped", "x]\n[DEFAULT"] {
+            let error = create_api_key_profile(&OciClient::new(), &source, name, 2048, &key_dir)
+                .await
+                .unwrap_err();
+            assert_eq!(error.kind(), "config");
+        }
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+        assert_eq!(fs::read_dir(&key_dir).unwrap().count(), 0);
+    }
+}
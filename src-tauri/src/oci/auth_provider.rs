@@ -0,0 +1,64 @@
+use async_trait::async_trait;
+
+use crate::models::profile::{OciProfile, PrincipalType};
+use crate::oci::client::OciClient;
+use crate::oci::error::OciError;
+use crate::oci::session::{current_security_token, session_key_id};
+use crate::oci::signature::{api_key_id, SigningCredentials};
+
+/// リクエスト署名に使う認証情報の提供元
+#[async_trait]
+pub trait AuthProvider: Send + Sync {
+    /// 署名に使う keyId と秘密鍵を返す（必要に応じてトークンを取得・更新する）
+    async fn signing_credentials(
+        &self,
+        client: &OciClient,
+        profile: &OciProfile,
+    ) -> Result<SigningCredentials, OciError>;
+}
+
+/// API キー認証（設定ファイルの user / fingerprint / key_file）
+pub struct ApiKeyAuth;
+
+#[async_trait]
+impl AuthProvider for ApiKeyAuth {
+    async fn signing_credentials(
+        &self,
+        client: &OciClient,
+        profile: &OciProfile,
+    ) -> Result<SigningCredentials, OciError> {
+        Ok(SigningCredentials {
+            key_id: api_key_id(profile),
+            private_key: client.keys.get(profile)?,
+        })
+    }
+}
+
+/// セッショントークン認証（設定ファイルの security_token_file）
+pub struct SessionTokenAuth;
+
+#[async_trait]
+impl AuthProvider for SessionTokenAuth {
+    async fn signing_credentials(
+        &self,
+        client: &OciClient,
+        profile: &OciProfile,
+    ) -> Result<SigningCredentials, OciError> {
+        let private_key = client.keys.get(profile)?;
+        let token = current_security_token(client, profile, &private_key).await?;
+        Ok(SigningCredentials {
+            key_id: session_key_id(&token),
+            private_key,
+        })
+    }
+}
+
+/// プロファイルの認証方式に応じた認証プロバイダーを選ぶ
+pub fn provider_for<'a>(client: &'a OciClient, profile: &OciProfile) -> &'a dyn AuthProvider {
+    match profile.principal {
+        Some(PrincipalType::InstancePrincipal) => &client.instance_principal,
+        Some(PrincipalType::ResourcePrincipal) => &client.resource_principal,
+        None if profile.security_token_file.is_some() => &SessionTokenAuth,
+        None => &ApiKeyAuth,
+    }
+}
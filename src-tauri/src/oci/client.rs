@@ -1,69 +1,482 @@
+use std::path::Path;
+use std::time::Duration;
+
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use reqwest::header::HeaderMap;
 use reqwest::Method;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 
 use crate::models::profile::OciProfile;
+use crate::oci::auth_provider::provider_for;
 use crate::oci::config_parser;
-use crate::oci::regions::region_to_endpoint;
-use crate::oci::signature::{sign_request, SigningRequest};
+use crate::oci::error::OciError;
+use crate::oci::key_cache::KeyCache;
+use crate::oci::principals::{InstancePrincipalAuth, ResourcePrincipalAuth};
+use crate::oci::regions::resolve_endpoint;
+use crate::oci::retry::{generate_retry_token, send_with_retry, RetryPolicy};
+use crate::oci::signature::{sign_request, SigningCredentials, SigningRequest};
+
+/// 次ページトークンの取得方法
+#[derive(Debug, Clone, Copy)]
+pub enum Pagination {
+    /// `opc-next-page` レスポンスヘッダーを `page` パラメータで送り返す（大半の OCI API）
+    NextPage,
+    /// レスポンスボディの `nextStartWith` を `start` パラメータで送り返す（Object Storage のオブジェクト一覧）
+    NextStartWith {
+        /// 要素配列が格納されているフィールド名
+        items_field: &'static str,
+    },
+}
+
+/// リクエストボディ
+#[derive(Debug, Clone)]
+pub enum RequestBody {
+    /// JSON ボディ（content-type: application/json）
+    Json(serde_json::Value),
+    /// 任意のバイト列ボディ
+    Raw { data: Vec<u8>, content_type: String },
+}
+
+/// OCI REST API へのリクエスト内容
+#[derive(Debug, Clone)]
+pub struct OciRequest {
+    pub method: Method,
+    /// サービス名（例: "iaas", "identity"）
+    pub service: String,
+    pub path: String,
+    pub query: Option<String>,
+    pub body: Option<RequestBody>,
+    /// 追加ヘッダー（if-match, opc-retry-token など）
+    pub headers: Vec<(String, String)>,
+}
+
+impl OciRequest {
+    pub fn new(method: Method, service: &str, path: &str) -> Self {
+        Self {
+            method,
+            service: service.to_string(),
+            path: path.to_string(),
+            query: None,
+            body: None,
+            headers: Vec::new(),
+        }
+    }
+
+    /// 任意のシリアライズ可能な値を JSON ボディとして設定
+    pub fn json<T: Serialize>(mut self, body: &T) -> Result<Self, OciError> {
+        let value = serde_json::to_value(body)
+            .map_err(|e| OciError::parse(format!("リクエストボディの生成に失敗しました: {}", e)))?;
+        self.body = Some(RequestBody::Json(value));
+        Ok(self)
+    }
+
+    /// バイト列をそのままボディとして設定
+    pub fn raw(mut self, data: Vec<u8>, content_type: &str) -> Self {
+        self.body = Some(RequestBody::Raw {
+            data,
+            content_type: content_type.to_string(),
+        });
+        self
+    }
+
+    /// 追加ヘッダーを設定
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+/// OCI REST API のレスポンス
+#[derive(Debug, Clone)]
+pub struct OciResponse {
+    pub status: u16,
+    pub headers: HeaderMap,
+    pub body: String,
+}
+
+impl OciResponse {
+    /// ヘッダー値を文字列で取得
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|v| v.to_str().ok())
+    }
+}
+
+/// アプリ全体で共有する OCI クライアント
+///
+/// コネクションプールを持つ HTTP クライアントと、解析済み秘密鍵のキャッシュを保持する。
+pub struct OciClient {
+    pub http: reqwest::Client,
+    pub keys: KeyCache,
+    pub retry_policy: RetryPolicy,
+    /// セッショントークン更新の排他制御
+    pub session_refresh: tokio::sync::Mutex<()>,
+    /// インスタンス・プリンシパル認証（フェデレーションで得たトークンを保持する）
+    pub instance_principal: InstancePrincipalAuth,
+    /// リソース・プリンシパル認証
+    pub resource_principal: ResourcePrincipalAuth,
+}
 
-/// プロファイル名からプロファイルを読み込む
-pub fn load_profile(profile_name: &str) -> Result<OciProfile, String> {
-    let cfg_path = config_parser::default_config_path();
-    let profiles = config_parser::parse_config(&cfg_path)?;
+impl OciClient {
+    pub fn new() -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .pool_idle_timeout(Duration::from_secs(90))
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            http,
+            keys: KeyCache::default(),
+            retry_policy: RetryPolicy::default(),
+            session_refresh: tokio::sync::Mutex::new(()),
+            instance_principal: InstancePrincipalAuth::from_env(),
+            resource_principal: ResourcePrincipalAuth::default(),
+        }
+    }
+}
+
+impl Default for OciClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 設定ファイルからプロファイルを読み込む
+pub fn load_profile(config_path: &Path, profile_name: &str) -> Result<OciProfile, OciError> {
+    let profiles = config_parser::parse_config(config_path).map_err(OciError::config)?;
     profiles
         .into_iter()
         .find(|p| p.name == profile_name)
-        .ok_or_else(|| format!("プロファイル '{}' が見つかりません。", profile_name))
+        .ok_or_else(|| {
+            OciError::config(format!(
+                "プロファイル '{}' が見つかりません。",
+                profile_name
+            ))
+        })
 }
 
 /// OCI REST API に対して GET リクエストを送信し、レスポンスボディを返す
 pub async fn oci_get_request(
+    client: &OciClient,
+    profile: &OciProfile,
+    service: &str,
+    path: &str,
+    query: Option<&str>,
+) -> Result<String, OciError> {
+    let (body, _) = send_get_request(client, profile, service, path, query).await?;
+    Ok(body)
+}
+
+/// 一覧系 API を `opc-next-page` に従って最後のページまで取得する
+///
+/// `max_items` を指定した場合は、その件数に達した時点で取得を打ち切る。
+pub async fn oci_list_request<T: DeserializeOwned>(
+    client: &OciClient,
     profile: &OciProfile,
     service: &str,
     path: &str,
     query: Option<&str>,
-) -> Result<String, String> {
-    let host = region_to_endpoint(&profile.region, service);
-    let full_path = match query {
-        Some(q) => format!("{}?{}", path, q),
-        None => path.to_string(),
+    max_items: Option<usize>,
+) -> Result<Vec<T>, OciError> {
+    oci_paginated_request(
+        client,
+        profile,
+        service,
+        path,
+        query,
+        Pagination::NextPage,
+        max_items,
+    )
+    .await
+}
+
+/// 一覧系 API を指定のページング方式で最後のページまで取得する
+pub async fn oci_paginated_request<T: DeserializeOwned>(
+    client: &OciClient,
+    profile: &OciProfile,
+    service: &str,
+    path: &str,
+    query: Option<&str>,
+    pagination: Pagination,
+    max_items: Option<usize>,
+) -> Result<Vec<T>, OciError> {
+    let mut items: Vec<T> = Vec::new();
+    let mut token: Option<String> = None;
+
+    loop {
+        let page_query = with_page_token(query, pagination, token.as_deref());
+        let (body, headers) =
+            send_get_request(client, profile, service, path, page_query.as_deref()).await?;
+
+        let (page_items, next) = match pagination {
+            Pagination::NextPage => {
+                let page_items: Vec<T> =
+                    serde_json::from_str(&body).map_err(|e| OciError::parse(e.to_string()))?;
+                let next = headers
+                    .get("opc-next-page")
+                    .and_then(|v| v.to_str().ok())
+                    .map(|v| v.to_string());
+                (page_items, next)
+            }
+            Pagination::NextStartWith { items_field } => {
+                let mut value: serde_json::Value =
+                    serde_json::from_str(&body).map_err(|e| OciError::parse(e.to_string()))?;
+                let next = value
+                    .get("nextStartWith")
+                    .and_then(|v| v.as_str())
+                    .map(|v| v.to_string());
+                let page_items: Vec<T> = serde_json::from_value(value[items_field].take())
+                    .map_err(|e| OciError::parse(e.to_string()))?;
+                (page_items, next)
+            }
+        };
+
+        items.extend(page_items);
+
+        if let Some(max) = max_items {
+            if items.len() >= max {
+                items.truncate(max);
+                break;
+            }
+        }
+
+        match next {
+            Some(next) if !next.is_empty() => token = Some(next),
+            _ => break,
+        }
+    }
+
+    Ok(items)
+}
+
+/// クエリ文字列にページトークンを付加
+fn with_page_token(
+    query: Option<&str>,
+    pagination: Pagination,
+    token: Option<&str>,
+) -> Option<String> {
+    let Some(token) = token else {
+        return query.map(|q| q.to_string());
     };
 
-    let signing_request = SigningRequest {
-        method: Method::GET,
-        path: full_path.clone(),
-        host: host.clone(),
-        body: None,
+    let param = match pagination {
+        Pagination::NextPage => "page",
+        Pagination::NextStartWith { .. } => "start",
     };
+    let token_param = format!("{}={}", param, utf8_percent_encode(token, NON_ALPHANUMERIC));
+
+    match query {
+        Some(q) if !q.is_empty() => Some(format!("{}&{}", q, token_param)),
+        _ => Some(token_param),
+    }
+}
 
-    let signed_headers = sign_request(profile, &signing_request)?;
+/// 署名付き GET リクエストを送信し、レスポンスボディとヘッダーを返す
+async fn send_get_request(
+    client: &OciClient,
+    profile: &OciProfile,
+    service: &str,
+    path: &str,
+    query: Option<&str>,
+) -> Result<(String, HeaderMap), OciError> {
+    let mut request = OciRequest::new(Method::GET, service, path);
+    request.query = query.map(|q| q.to_string());
+    let response = oci_request(client, profile, request).await?;
+    Ok((response.body, response.headers))
+}
 
-    let url = format!("https://{}{}", host, full_path);
-    let client = reqwest::Client::new();
-    let mut request_builder = client.get(&url);
+/// 任意メソッドの署名付きリクエストを送信し、ステータス・ヘッダー・ボディを返す
+///
+/// クライアントの再試行ポリシーでスロットリングや一時的な障害を再試行する。
+/// 2xx 以外のステータスはエラーとして扱う。
+pub async fn oci_request(
+    client: &OciClient,
+    profile: &OciProfile,
+    request: OciRequest,
+) -> Result<OciResponse, OciError> {
+    oci_request_with_policy(client, profile, request, &client.retry_policy).await
+}
 
-    for (name, value) in &signed_headers {
-        request_builder = request_builder.header(name, value);
+/// 再試行ポリシーを指定して署名付きリクエストを送信する
+pub async fn oci_request_with_policy(
+    client: &OciClient,
+    profile: &OciProfile,
+    request: OciRequest,
+    policy: &RetryPolicy,
+) -> Result<OciResponse, OciError> {
+    let credentials = provider_for(client, profile)
+        .signing_credentials(client, profile)
+        .await?;
+    send_signed_request(client, profile, request, &credentials, policy).await
+}
+
+/// 指定の認証情報で署名してリクエストを送信する
+///
+/// 認証プロバイダー自身がトークンを取得・更新する際にも使う（`profile` はエンドポイントの解決にのみ使う）。
+pub async fn send_signed_request(
+    client: &OciClient,
+    profile: &OciProfile,
+    mut request: OciRequest,
+    credentials: &SigningCredentials,
+    policy: &RetryPolicy,
+) -> Result<OciResponse, OciError> {
+    // POST は opc-retry-token を付けて再送を冪等にする
+    if request.method == Method::POST
+        && !request
+            .headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case("opc-retry-token"))
+    {
+        request
+            .headers
+            .push(("opc-retry-token".to_string(), generate_retry_token()));
     }
 
-    let response = request_builder
-        .timeout(std::time::Duration::from_secs(30))
-        .send()
-        .await
-        .map_err(|e| format!("API リクエストに失敗しました: {}", e))?;
+    let endpoint = resolve_endpoint(profile, &request.service).map_err(OciError::config)?;
+    let full_path = match &request.query {
+        Some(q) => format!("{}?{}", request.path, q),
+        None => request.path.clone(),
+    };
+
+    let (body, content_type) = match request.body {
+        Some(RequestBody::Json(value)) => (
+            Some(serde_json::to_vec(&value).map_err(|e| {
+                OciError::parse(format!("リクエストボディの生成に失敗しました: {}", e))
+            })?),
+            Some("application/json".to_string()),
+        ),
+        Some(RequestBody::Raw { data, content_type }) => (Some(data), Some(content_type)),
+        None => (None, None),
+    };
+
+    let signing_request = SigningRequest {
+        method: request.method.clone(),
+        path: endpoint.request_target(&full_path),
+        host: endpoint.host.clone(),
+        body,
+        content_type,
+    };
+
+    let url = endpoint.url(&full_path);
+
+    // 署名の日時ヘッダーを更新するため、試行ごとに署名し直す
+    let response = send_with_retry(policy, || {
+        let signed_headers = sign_request(credentials, &signing_request).map_err(OciError::auth)?;
+        let mut request_builder = client.http.request(request.method.clone(), &url);
+
+        for (name, value) in signed_headers.iter().chain(request.headers.iter()) {
+            request_builder = request_builder.header(name, value);
+        }
+
+        if let Some(body) = &signing_request.body {
+            request_builder = request_builder.body(body.clone());
+        }
+
+        Ok(request_builder)
+    })
+    .await?;
 
     let status = response.status();
+    let headers = response.headers().clone();
     let body = response
         .text()
         .await
         .unwrap_or_else(|_| "レスポンスの読み取りに失敗しました".to_string());
 
     if status.is_success() {
-        Ok(body)
+        Ok(OciResponse {
+            status: status.as_u16(),
+            headers,
+            body,
+        })
     } else {
-        Err(format!(
-            "API エラー（ステータス: {}）: {}",
+        let opc_request_id = headers
+            .get("opc-request-id")
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string());
+        Err(OciError::from_response(
             status.as_u16(),
-            body
+            &body,
+            opc_request_id,
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oci::test_server::{start_sequential_server, test_profile, MockResponse};
+
+    #[test]
+    fn test_with_page_token() {
+        assert_eq!(
+            with_page_token(
+                Some("compartmentId=ocid1.compartment.oc1..x"),
+                Pagination::NextPage,
+                None
+            ),
+            Some("compartmentId=ocid1.compartment.oc1..x".to_string())
+        );
+        assert_eq!(
+            with_page_token(
+                Some("compartmentId=c"),
+                Pagination::NextPage,
+                Some("AAA/b+c=")
+            ),
+            Some("compartmentId=c&page=AAA%2Fb%2Bc%3D".to_string())
+        );
+        assert_eq!(
+            with_page_token(
+                None,
+                Pagination::NextStartWith {
+                    items_field: "objects"
+                },
+                Some("a b")
+            ),
+            Some("start=a%20b".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_request_follows_next_page_until_max_items() {
+        let page = |ids: &[&str]| {
+            let items: Vec<_> = ids
+                .iter()
+                .map(|id| serde_json::json!({ "id": id }))
+                .collect();
+            serde_json::Value::from(items).to_string()
+        };
+        let (url, requests) = start_sequential_server(vec![
+            MockResponse::ok(page(&["a", "b"])).header("opc-next-page", "page2"),
+            MockResponse::ok(page(&["c", "d"])).header("opc-next-page", "page/3"),
+            MockResponse::ok(page(&["e", "f"])).header("opc-next-page", "page4"),
+            MockResponse::ok(page(&["g"])),
+        ])
+        .await;
+
+        let items: Vec<serde_json::Value> = oci_list_request(
+            &OciClient::new(),
+            &test_profile("iaas", &url),
+            "iaas",
+            "/20160918/instances",
+            Some("compartmentId=c"),
+            Some(5),
+        )
+        .await
+        .unwrap();
+
+        let ids: Vec<&str> = items
+            .iter()
+            .map(|item| item["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(ids, vec!["a", "b", "c", "d", "e"]);
+
+        // 上限に達したページで打ち切り、4 ページ目は取得しない
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[0].starts_with("GET /20160918/instances?compartmentId=c "));
+        assert!(requests[1].starts_with("GET /20160918/instances?compartmentId=c&page=page2 "));
+        assert!(requests[2].starts_with("GET /20160918/instances?compartmentId=c&page=page%2F3 "));
+    }
+}
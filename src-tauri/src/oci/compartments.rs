@@ -0,0 +1,254 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::models::iam::{Compartment, CompartmentNode};
+use crate::models::profile::OciProfile;
+use crate::oci::client::{self, OciClient};
+use crate::oci::error::OciError;
+
+/// ルートコンパートメント（テナンシ）の名前
+pub const ROOT_NAME: &str = "root";
+
+/// キャッシュしたツリーを使う期間
+const CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// プロファイルごとのコンパートメントツリーのキャッシュ
+///
+/// アクセスできるコンパートメントは呼び出し元のユーザー・プリンシパルによって変わるため、
+/// 同じテナンシでもプロファイルごとに分ける。
+#[derive(Default)]
+pub struct CompartmentCache {
+    entries: Mutex<HashMap<(String, String), (Instant, CompartmentNode)>>,
+}
+
+/// キャッシュのキー（プロファイル名とテナンシ）
+fn cache_key(profile: &OciProfile) -> (String, String) {
+    (profile.name.clone(), profile.tenancy.clone())
+}
+
+impl CompartmentCache {
+    fn get(&self, profile: &OciProfile) -> Option<CompartmentNode> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .get(&cache_key(profile))
+            .filter(|(fetched, _)| fetched.elapsed() < CACHE_TTL)
+            .map(|(_, tree)| tree.clone())
+    }
+
+    fn insert(&self, profile: &OciProfile, tree: CompartmentNode) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.insert(cache_key(profile), (Instant::now(), tree));
+    }
+
+    /// キャッシュ済みのツリーにあるコンパートメントのパス（期限切れのツリーも使う）
+    pub fn path_of(&self, profile: &OciProfile, compartment_id: &str) -> Option<String> {
+        fn find(node: &CompartmentNode, id: &str) -> Option<String> {
+            if node.id == id {
+                return Some(node.path.clone());
+            }
+            node.children.iter().find_map(|child| find(child, id))
+        }
+
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .get(&cache_key(profile))
+            .and_then(|(_, tree)| find(tree, compartment_id))
+    }
+
+    /// キャッシュをすべて破棄
+    pub fn clear(&self) {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+}
+
+/// テナンシ全体のコンパートメントツリーを取得（キャッシュが有効な間は API を呼ばない）
+pub async fn compartment_tree(
+    client: &OciClient,
+    cache: &CompartmentCache,
+    profile: &OciProfile,
+    refresh: bool,
+) -> Result<CompartmentNode, OciError> {
+    if profile.tenancy.is_empty() {
+        return Err(OciError::config(
+            "プロファイルにテナンシ OCID が設定されていません。",
+        ));
+    }
+    if !refresh {
+        if let Some(tree) = cache.get(profile) {
+            return Ok(tree);
+        }
+    }
+
+    // compartmentIdInSubtree はテナンシ（ルート）を指定した場合のみ使える
+    let query = format!(
+        "compartmentId={}&compartmentIdInSubtree=true&accessLevel=ACCESSIBLE&lifecycleState=ACTIVE",
+        profile.tenancy
+    );
+    let compartments: Vec<Compartment> = client::oci_list_request(
+        client,
+        profile,
+        "identity",
+        "/20160918/compartments",
+        Some(&query),
+        None,
+    )
+    .await?;
+
+    let tree = build_tree(&profile.tenancy, &compartments);
+    cache.insert(profile, tree.clone());
+    Ok(tree)
+}
+
+/// コンパートメント一覧からツリーを組み立てる
+///
+/// 親にアクセスできないコンパートメントはルート直下に置き、パスに "…" を挟む。
+pub fn build_tree(tenancy: &str, compartments: &[Compartment]) -> CompartmentNode {
+    let ids: HashSet<&str> = compartments.iter().map(|c| c.id.as_str()).collect();
+    let mut children_of: HashMap<&str, Vec<&Compartment>> = HashMap::new();
+    for compartment in compartments {
+        let known_parent = compartment.compartment_id == tenancy
+            || ids.contains(compartment.compartment_id.as_str());
+        let parent = if known_parent {
+            compartment.compartment_id.as_str()
+        } else {
+            tenancy
+        };
+        children_of.entry(parent).or_default().push(compartment);
+    }
+
+    fn build_children(
+        parent_id: &str,
+        parent_path: &str,
+        tenancy: &str,
+        children_of: &HashMap<&str, Vec<&Compartment>>,
+    ) -> Vec<CompartmentNode> {
+        let mut children: Vec<CompartmentNode> = children_of
+            .get(parent_id)
+            .into_iter()
+            .flatten()
+            .map(|c| {
+                let path = if parent_id == tenancy && c.compartment_id != tenancy {
+                    format!("{}/…/{}", parent_path, c.name)
+                } else {
+                    format!("{}/{}", parent_path, c.name)
+                };
+                CompartmentNode {
+                    id: c.id.clone(),
+                    name: c.name.clone(),
+                    children: build_children(&c.id, &path, tenancy, children_of),
+                    path,
+                    description: c.description.clone(),
+                }
+            })
+            .collect();
+        children.sort_by_key(|c| c.name.to_lowercase());
+        children
+    }
+
+    CompartmentNode {
+        id: tenancy.to_string(),
+        name: ROOT_NAME.to_string(),
+        path: ROOT_NAME.to_string(),
+        description: None,
+        children: build_children(tenancy, ROOT_NAME, tenancy, &children_of),
+    }
+}
+
+/// 名前に `query` を含むコンパートメントと、その祖先だけを残したツリー
+///
+/// `query` が "/" を含む場合はパスと照合する。大文字小文字は区別しない。一致するものがない場合は None。
+pub fn filter_tree(node: &CompartmentNode, query: &str) -> Option<CompartmentNode> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Some(node.clone());
+    }
+    filter_node(node, &query)
+}
+
+fn filter_node(node: &CompartmentNode, query: &str) -> Option<CompartmentNode> {
+    let children: Vec<CompartmentNode> = node
+        .children
+        .iter()
+        .filter_map(|child| filter_node(child, query))
+        .collect();
+    // "/" を含む検索語はパス（"prod/app" など）と照合する
+    let target = if query.contains('/') {
+        &node.path
+    } else {
+        &node.name
+    };
+    let matches = target.to_lowercase().contains(query);
+    (matches || !children.is_empty()).then(|| CompartmentNode {
+        children,
+        ..node.clone()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compartment(id: &str, name: &str, parent: &str) -> Compartment {
+        Compartment {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: None,
+            compartment_id: parent.to_string(),
+            lifecycle_state: "ACTIVE".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_cache_is_per_profile() {
+        let cache = CompartmentCache::default();
+        let profile = |name: &str| OciProfile {
+            name: name.to_string(),
+            tenancy: "ocid1.tenancy.oc1..t".to_string(),
+            ..Default::default()
+        };
+        let tree = build_tree("ocid1.tenancy.oc1..t", &[]);
+        cache.insert(&profile("admin"), tree);
+
+        assert!(cache.get(&profile("admin")).is_some());
+        assert!(cache.get(&profile("readonly")).is_none());
+        assert_eq!(
+            cache.path_of(&profile("admin"), "ocid1.tenancy.oc1..t"),
+            Some(ROOT_NAME.to_string())
+        );
+        assert_eq!(
+            cache.path_of(&profile("readonly"), "ocid1.tenancy.oc1..t"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_build_and_filter_tree() {
+        let compartments = vec![
+            compartment("app", "app", "prod"),
+            compartment("prod", "prod", "tenancy"),
+            compartment("dev", "Dev", "tenancy"),
+            compartment("hidden-child", "shared", "hidden"),
+        ];
+        let tree = build_tree("tenancy", &compartments);
+
+        let names: Vec<&str> = tree.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["Dev", "prod", "shared"]);
+        assert_eq!(tree.children[1].children[0].path, "root/prod/app");
+        assert_eq!(tree.children[2].path, "root/…/shared");
+
+        let filtered = filter_tree(&tree, "APP").unwrap();
+        assert_eq!(filtered.children.len(), 1);
+        assert_eq!(filtered.children[0].name, "prod");
+        assert_eq!(filtered.children[0].children[0].id, "app");
+
+        let filtered = filter_tree(&tree, "prod/app").unwrap();
+        assert_eq!(filtered.children[0].children[0].id, "app");
+
+        assert!(filter_tree(&tree, "missing").is_none());
+        assert_eq!(filter_tree(&tree, " "), Some(tree));
+    }
+}
@@ -0,0 +1,319 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::{NaiveDateTime, Utc};
+use tempfile::NamedTempFile;
+
+use crate::models::profile::{ConfigBackup, DiffLine, DiffLineKind};
+
+/// バックアップを保存するディレクトリ名（設定ファイルと同じディレクトリに作成）
+pub const BACKUP_DIR_NAME: &str = ".backups";
+
+/// 設定ファイルごとに残すバックアップの数
+pub const MAX_BACKUPS: usize = 20;
+
+/// バックアップ名に含める日時の形式（UTC。名前では末尾に Z を付ける）
+const TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%S%.3f";
+
+/// ファイルを一時ファイルに書き込んで fsync し、rename で置き換える
+///
+/// 書き込み途中で異常終了しても元のファイルが壊れない。パーミッションは 600（Unix系のみ）。
+/// シンボリックリンクの場合はリンクを残したままリンク先を置き換える。
+pub fn atomic_write(path: &Path, contents: &[u8]) -> Result<(), String> {
+    let is_symlink = fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_symlink());
+    let target = if is_symlink {
+        fs::canonicalize(path)
+            .map_err(|e| format!("シンボリックリンクのリンク先を解決できません: {}", e))?
+    } else {
+        path.to_path_buf()
+    };
+    let dir = target.parent().unwrap_or(Path::new("."));
+
+    // 一時ファイルは一意な名前・パーミッション 600 で作成される（破棄すると削除される）
+    let mut temp = NamedTempFile::new_in(dir)
+        .map_err(|e| format!("一時ファイルの作成に失敗しました: {}", e))?;
+    temp.write_all(contents)
+        .and_then(|_| temp.as_file().sync_all())
+        .map_err(|e| format!("設定ファイルの書き込みに失敗しました: {}", e))?;
+    temp.persist(&target)
+        .map_err(|e| format!("設定ファイルの置き換えに失敗しました: {}", e.error))?;
+
+    // rename をディスクに反映する（Unix系のみ。失敗しても書き込み自体は完了している）
+    #[cfg(unix)]
+    if let Ok(dir) = fs::File::open(dir) {
+        dir.sync_all().ok();
+    }
+
+    Ok(())
+}
+
+/// 600 で作成したファイルに書き込み、fsync する
+fn write_synced(path: &Path, contents: &[u8]) -> Result<(), String> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options
+        .open(path)
+        .map_err(|e| format!("一時ファイルの作成に失敗しました: {}", e))?;
+    file.write_all(contents)
+        .and_then(|_| file.sync_all())
+        .map_err(|e| format!("設定ファイルの書き込みに失敗しました: {}", e))?;
+
+    // 既存のファイルを開いた場合に備えてパーミッションを設定する
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))
+            .map_err(|e| format!("パーミッションの設定に失敗しました: {}", e))?;
+    }
+
+    Ok(())
+}
+
+/// 設定ファイルのバックアップディレクトリ
+pub fn backup_dir(config_path: &Path) -> PathBuf {
+    config_path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join(BACKUP_DIR_NAME)
+}
+
+/// バックアップ名の接頭辞（`<設定ファイル名>.`）
+fn backup_prefix(config_path: &Path) -> String {
+    let file_name = config_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "config".to_string());
+    format!("{}.", file_name)
+}
+
+/// 現在の設定ファイルをバックアップし、古いバックアップを `keep` 個まで削除する
+///
+/// 設定ファイルが存在しない場合は何もしない。
+pub fn backup_config(config_path: &Path, keep: usize) -> Result<Option<PathBuf>, String> {
+    if !config_path.exists() {
+        return Ok(None);
+    }
+    let contents = fs::read(config_path)
+        .map_err(|e| format!("設定ファイルの読み込みに失敗しました: {}", e))?;
+
+    let dir = backup_dir(config_path);
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("バックアップディレクトリの作成に失敗しました: {}", e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o700))
+            .map_err(|e| format!("パーミッションの設定に失敗しました: {}", e))?;
+    }
+
+    // 同じ時刻のバックアップがある場合は連番を付ける
+    let base = format!(
+        "{}{}Z",
+        backup_prefix(config_path),
+        Utc::now().format(TIMESTAMP_FORMAT)
+    );
+    let mut backup_path = dir.join(&base);
+    let mut counter = 1;
+    while backup_path.exists() {
+        backup_path = dir.join(format!("{}-{}", base, counter));
+        counter += 1;
+    }
+    write_synced(&backup_path, &contents)?;
+
+    for old in list_backups(config_path)?.into_iter().skip(keep) {
+        fs::remove_file(dir.join(old.id)).ok();
+    }
+    Ok(Some(backup_path))
+}
+
+/// バックアップ一覧（新しい順）
+pub fn list_backups(config_path: &Path) -> Result<Vec<ConfigBackup>, String> {
+    let dir = backup_dir(config_path);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let prefix = backup_prefix(config_path);
+    let entries = fs::read_dir(&dir)
+        .map_err(|e| format!("バックアップディレクトリの読み込みに失敗しました: {}", e))?;
+    let mut backups: Vec<(NaiveDateTime, ConfigBackup)> = entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let id = entry.file_name().to_string_lossy().to_string();
+            // 連番付きの名前（<日時>Z-1）も受け付ける
+            let (timestamp, _) = id.strip_prefix(&prefix)?.split_once('Z')?;
+            let created = NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT).ok()?;
+            let size = entry.metadata().ok()?.len();
+            Some((
+                created,
+                ConfigBackup {
+                    id,
+                    created_at: created.and_utc().to_rfc3339(),
+                    size,
+                },
+            ))
+        })
+        .collect();
+
+    backups.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| b.1.id.cmp(&a.1.id)));
+    Ok(backups.into_iter().map(|(_, backup)| backup).collect())
+}
+
+/// バックアップのパス（一覧にない ID は受け付けない）
+fn backup_path(config_path: &Path, id: &str) -> Result<PathBuf, String> {
+    if list_backups(config_path)?.iter().any(|b| b.id == id) {
+        Ok(backup_dir(config_path).join(id))
+    } else {
+        Err(format!("バックアップ '{}' が見つかりません。", id))
+    }
+}
+
+/// バックアップから現在の設定ファイルへの差分（行単位）
+pub fn diff_backup(config_path: &Path, id: &str) -> Result<Vec<DiffLine>, String> {
+    let backup = fs::read_to_string(backup_path(config_path, id)?)
+        .map_err(|e| format!("バックアップの読み込みに失敗しました: {}", e))?;
+    let current = if config_path.exists() {
+        fs::read_to_string(config_path)
+            .map_err(|e| format!("設定ファイルの読み込みに失敗しました: {}", e))?
+    } else {
+        String::new()
+    };
+    Ok(diff_lines(&backup, &current))
+}
+
+/// バックアップを復元する（復元前の設定ファイルもバックアップする）
+pub fn restore_backup(config_path: &Path, id: &str) -> Result<(), String> {
+    let contents = fs::read(backup_path(config_path, id)?)
+        .map_err(|e| format!("バックアップの読み込みに失敗しました: {}", e))?;
+    backup_config(config_path, MAX_BACKUPS)?;
+    atomic_write(config_path, &contents)
+}
+
+/// 2 つのテキストの行単位の差分（最長共通部分列）
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // lcs[i][j] = old[i..] と new[j..] の最長共通部分列の長さ
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let line = |kind, text: &str| DiffLine {
+        kind,
+        text: text.to_string(),
+    };
+    let (mut i, mut j) = (0, 0);
+    let mut diff = Vec::new();
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            diff.push(line(DiffLineKind::Same, old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            diff.push(line(DiffLineKind::Removed, old[i]));
+            i += 1;
+        } else {
+            diff.push(line(DiffLineKind::Added, new[j]));
+            j += 1;
+        }
+    }
+    diff.extend(old[i..].iter().map(|l| line(DiffLineKind::Removed, l)));
+    diff.extend(new[j..].iter().map(|l| line(DiffLineKind::Added, l)));
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_rotation_and_restore() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("config");
+        atomic_write(&config_path, b"[DEFAULT]\nregion=ap-tokyo-1\n").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&config_path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        for region in ["ap-osaka-1", "us-ashburn-1", "eu-frankfurt-1"] {
+            backup_config(&config_path, 2).unwrap();
+            atomic_write(
+                &config_path,
+                format!("[DEFAULT]\nregion={}\n", region).as_bytes(),
+            )
+            .unwrap();
+        }
+
+        let backups = list_backups(&config_path).unwrap();
+        assert_eq!(backups.len(), 2);
+        let newest = &backups[0];
+        let newest_contents =
+            fs::read_to_string(backup_dir(&config_path).join(&newest.id)).unwrap();
+        assert_eq!(newest_contents, "[DEFAULT]\nregion=us-ashburn-1\n");
+
+        restore_backup(&config_path, &newest.id).unwrap();
+        assert_eq!(
+            fs::read_to_string(&config_path).unwrap(),
+            "[DEFAULT]\nregion=us-ashburn-1\n"
+        );
+        // 復元前の内容もバックアップされる
+        assert_eq!(list_backups(&config_path).unwrap().len(), 3);
+
+        assert!(restore_backup(&config_path, "../config").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_atomic_write_keeps_symlink() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("dotfiles-config");
+        let link = dir.path().join("config");
+        fs::write(&target, "[DEFAULT]\n").unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        atomic_write(&link, b"[DEFAULT]\nregion=ap-osaka-1\n").unwrap();
+
+        assert!(fs::symlink_metadata(&link)
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert_eq!(
+            fs::read_to_string(&target).unwrap(),
+            "[DEFAULT]\nregion=ap-osaka-1\n"
+        );
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        let diff = diff_lines("a\nb\nc\n", "a\nc\nd\n");
+        let summary: Vec<(DiffLineKind, &str)> =
+            diff.iter().map(|l| (l.kind, l.text.as_str())).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (DiffLineKind::Same, "a"),
+                (DiffLineKind::Removed, "b"),
+                (DiffLineKind::Same, "c"),
+                (DiffLineKind::Added, "d"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,475 @@
+use std::collections::HashMap;
+
+/// 設定ファイルの 1 行
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Line {
+    /// key=value 行（`raw` は元の行そのもの）
+    Entry {
+        key: String,
+        value: String,
+        raw: String,
+    },
+    /// 空行・コメント行・解析できない行（元のまま保持する）
+    Other(String),
+}
+
+impl Line {
+    /// OCI CLI（Python の configparser）と同じ規則で 1 行を解析する
+    ///
+    /// - `#` または `;` で始まる行はコメント（値の途中の `#` / `;` はそのまま値の一部）
+    /// - キーと値の区切りは最初に現れる `=` または `:`（値には `=` を含められる）
+    /// - キーは大文字・小文字を区別しない（小文字に正規化して扱う）
+    fn parse(raw: &str) -> Self {
+        let trimmed = raw.trim();
+        if is_comment(trimmed) {
+            return Self::Other(raw.to_string());
+        }
+        match trimmed.find(['=', ':']) {
+            Some(pos) if pos > 0 => Self::Entry {
+                key: trimmed[..pos].trim().to_lowercase(),
+                value: trimmed[pos + 1..].trim().to_string(),
+                raw: raw.to_string(),
+            },
+            _ => Self::Other(raw.to_string()),
+        }
+    }
+
+    fn raw(&self) -> &str {
+        match self {
+            Self::Entry { raw, .. } => raw,
+            Self::Other(raw) => raw,
+        }
+    }
+
+    fn is_blank_or_comment(&self) -> bool {
+        match self {
+            Self::Entry { .. } => false,
+            Self::Other(raw) => is_comment(raw.trim()),
+        }
+    }
+}
+
+/// 空行またはコメント行かどうか
+fn is_comment(trimmed: &str) -> bool {
+    trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with(';')
+}
+
+/// `[NAME]` セクションとその行
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Section {
+    name: String,
+    header: String,
+    lines: Vec<Line>,
+}
+
+impl Section {
+    /// 末尾の空行・コメント（次のセクションに付属するもの）を除いた最後の位置
+    fn content_end(&self) -> usize {
+        self.lines
+            .iter()
+            .rposition(|line| !line.is_blank_or_comment())
+            .map(|i| i + 1)
+            .unwrap_or(0)
+    }
+}
+
+/// 診断用のセクションの概要（行番号は 1 始まり）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionOutline {
+    pub name: String,
+    /// ヘッダー行の行番号
+    pub start_line: usize,
+    /// 最後のキー（または解析できない行）の行番号（末尾の空行・コメントは含まない）
+    pub end_line: usize,
+    /// キーとその行番号（ファイル内の順序）
+    pub keys: Vec<(usize, String)>,
+    /// このセクション自身のキーと値（同じキーが複数ある場合は後のものを優先）
+    pub fields: HashMap<String, String>,
+    /// 解析できない行とその行番号
+    pub invalid_lines: Vec<(usize, String)>,
+}
+
+/// コメント・行の順序・未知のキーを保持したまま編集できる INI 形式の設定ファイル
+///
+/// 変更した行以外は元のテキストのまま書き戻す。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigDocument {
+    /// 最初のセクションより前の行
+    preamble: Vec<Line>,
+    sections: Vec<Section>,
+    newline: &'static str,
+    trailing_newline: bool,
+}
+
+impl ConfigDocument {
+    /// テキストを解析
+    pub fn parse(content: &str) -> Self {
+        let newline = if content.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+        let trailing_newline = content.is_empty() || content.ends_with('\n');
+        let body = content.strip_suffix('\n').unwrap_or(content);
+
+        let mut document = Self {
+            newline,
+            trailing_newline,
+            ..Self::default()
+        };
+        if content.is_empty() {
+            return document;
+        }
+
+        for raw in body.split('\n') {
+            let raw = raw.strip_suffix('\r').unwrap_or(raw);
+            let trimmed = raw.trim();
+
+            if trimmed.starts_with('[') && trimmed.ends_with(']') {
+                document.sections.push(Section {
+                    name: trimmed[1..trimmed.len() - 1].trim().to_string(),
+                    header: raw.to_string(),
+                    lines: Vec::new(),
+                });
+                continue;
+            }
+
+            let line = Line::parse(raw);
+            match document.sections.last_mut() {
+                Some(section) => section.lines.push(line),
+                None => document.preamble.push(line),
+            }
+        }
+
+        document
+    }
+
+    /// セクション名の一覧（ファイル内の順序）
+    pub fn section_names(&self) -> Vec<&str> {
+        self.sections.iter().map(|s| s.name.as_str()).collect()
+    }
+
+    /// セクションが存在するかどうか
+    pub fn has_section(&self, name: &str) -> bool {
+        self.section(name).is_some()
+    }
+
+    /// セクションを追加（既に存在する場合は何もしない）
+    pub fn add_section(&mut self, name: &str) {
+        self.ensure_section(name);
+    }
+
+    /// セクション内のキーと値（同じキーが複数ある場合は後のものを優先）
+    pub fn fields(&self, name: &str) -> Option<HashMap<String, String>> {
+        self.section(name).map(|section| {
+            section
+                .lines
+                .iter()
+                .filter_map(|line| match line {
+                    Line::Entry { key, value, .. } => Some((key.clone(), value.clone())),
+                    Line::Other(_) => None,
+                })
+                .collect()
+        })
+    }
+
+    /// 値を取得（`key` は小文字で指定する）
+    pub fn get(&self, name: &str, key: &str) -> Option<&str> {
+        self.section(name)?
+            .lines
+            .iter()
+            .rev()
+            .find_map(|line| match line {
+                Line::Entry { key: k, value, .. } if k == key => Some(value.as_str()),
+                _ => None,
+            })
+    }
+
+    /// 値を設定（既存の行は置き換え、なければセクションの最後のキーの後に追加する）
+    ///
+    /// セクションが存在しない場合はファイル末尾に作成する。改行を含む値は別の行として
+    /// 解釈されてしまうため拒否する。
+    pub fn set(&mut self, name: &str, key: &str, value: &str) -> Result<(), String> {
+        if [name, key, value].iter().any(|s| s.contains(['\n', '\r'])) {
+            return Err(format!("設定値に改行を含めることはできません: {}", key));
+        }
+        if self.get(name, key) == Some(value) {
+            return Ok(());
+        }
+
+        let index = self.ensure_section(name);
+        let section = &mut self.sections[index];
+        let new_line = Line::Entry {
+            key: key.to_string(),
+            value: value.to_string(),
+            raw: format!("{}={}", key, value),
+        };
+
+        let existing = section
+            .lines
+            .iter()
+            .rposition(|line| matches!(line, Line::Entry { key: k, .. } if k == key));
+        match existing {
+            Some(position) => section.lines[position] = new_line,
+            None => {
+                let end = section.content_end();
+                section.lines.insert(end, new_line);
+            }
+        }
+        Ok(())
+    }
+
+    /// キーを削除（同じキーの行はすべて削除する）
+    pub fn remove_key(&mut self, name: &str, key: &str) {
+        if let Some(section) = self.section_mut(name) {
+            section
+                .lines
+                .retain(|line| !matches!(line, Line::Entry { key: k, .. } if k == key));
+        }
+    }
+
+    /// セクション内のキーのうち、条件に一致するものを削除
+    pub fn remove_keys_where(&mut self, name: &str, predicate: impl Fn(&str) -> bool) {
+        if let Some(section) = self.section_mut(name) {
+            section
+                .lines
+                .retain(|line| !matches!(line, Line::Entry { key, .. } if predicate(key)));
+        }
+    }
+
+    /// セクションの名前を変更（ヘッダー行のみ書き換える）
+    pub fn rename_section(&mut self, old_name: &str, new_name: &str) -> bool {
+        match self.section_mut(old_name) {
+            Some(section) => {
+                section.name = new_name.to_string();
+                section.header = format!("[{}]", new_name);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// セクションを削除（そのセクションに属する行をすべて削除する）
+    ///
+    /// 直前のセクション末尾にあるコメントは削除したセクションの説明とみなし、併せて削除する。
+    pub fn remove_section(&mut self, name: &str) -> bool {
+        let Some(index) = self.sections.iter().position(|s| s.name == name) else {
+            return false;
+        };
+
+        let removed = self.sections.remove(index);
+        let previous_lines = match index {
+            0 => &mut self.preamble,
+            i => &mut self.sections[i - 1].lines,
+        };
+        while previous_lines
+            .last()
+            .is_some_and(|line| line.is_blank_or_comment() && !line.raw().trim().is_empty())
+        {
+            previous_lines.pop();
+        }
+        while previous_lines
+            .last()
+            .is_some_and(|line| line.raw().trim().is_empty())
+        {
+            previous_lines.pop();
+        }
+        // 削除したセクションの末尾の空行・コメントは次のセクションに付属するため残す
+        let trailing = removed.lines[removed.content_end()..].to_vec();
+        previous_lines.extend(trailing);
+        true
+    }
+
+    /// 最初のセクションより前にある解析できない行（キーを含む）とその行番号
+    pub fn orphan_lines(&self) -> Vec<(usize, String)> {
+        self.preamble
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| !line.is_blank_or_comment())
+            .map(|(i, line)| (i + 1, line.raw().to_string()))
+            .collect()
+    }
+
+    /// 各セクションの行範囲とキー（同名のセクションもそれぞれ返す）
+    pub fn outline(&self) -> Vec<SectionOutline> {
+        let mut line_number = self.preamble.len();
+        self.sections
+            .iter()
+            .map(|section| {
+                line_number += 1;
+                let start_line = line_number;
+                let mut outline = SectionOutline {
+                    name: section.name.clone(),
+                    start_line,
+                    end_line: start_line + section.content_end(),
+                    keys: Vec::new(),
+                    fields: HashMap::new(),
+                    invalid_lines: Vec::new(),
+                };
+                for line in &section.lines {
+                    line_number += 1;
+                    match line {
+                        Line::Entry { key, value, .. } => {
+                            outline.keys.push((line_number, key.clone()));
+                            outline.fields.insert(key.clone(), value.clone());
+                        }
+                        Line::Other(raw) if !line.is_blank_or_comment() => {
+                            outline.invalid_lines.push((line_number, raw.clone()))
+                        }
+                        Line::Other(_) => {}
+                    }
+                }
+                outline
+            })
+            .collect()
+    }
+
+    /// テキストに変換
+    pub fn render(&self) -> String {
+        let mut lines: Vec<&str> = self.preamble.iter().map(Line::raw).collect();
+        for section in &self.sections {
+            lines.push(&section.header);
+            lines.extend(section.lines.iter().map(Line::raw));
+        }
+
+        let mut content = lines.join(self.newline);
+        if self.trailing_newline && !lines.is_empty() {
+            content.push_str(self.newline);
+        }
+        content
+    }
+
+    fn section(&self, name: &str) -> Option<&Section> {
+        self.sections.iter().find(|s| s.name == name)
+    }
+
+    fn section_mut(&mut self, name: &str) -> Option<&mut Section> {
+        self.sections.iter_mut().find(|s| s.name == name)
+    }
+
+    /// セクションの位置を返す（なければ空行で区切って末尾に追加する）
+    fn ensure_section(&mut self, name: &str) -> usize {
+        if let Some(index) = self.sections.iter().position(|s| s.name == name) {
+            return index;
+        }
+
+        let previous_lines = match self.sections.last_mut() {
+            Some(section) => &mut section.lines,
+            None => &mut self.preamble,
+        };
+        let needs_separator = previous_lines
+            .last()
+            .is_some_and(|line| !line.raw().trim().is_empty());
+        if needs_separator {
+            previous_lines.push(Line::Other(String::new()));
+        }
+
+        self.sections.push(Section {
+            name: name.to_string(),
+            header: format!("[{}]", name),
+            lines: Vec::new(),
+        });
+        self.sections.len() - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "# OCI CLI config\n\
+[DEFAULT]\n\
+user=ocid1.user.oc1..aaaa\n\
+# 本番用の鍵\n\
+key_file = ~/.oci/key.pem\n\
+tenancy_override=ocid1.tenancy.oc1..other\n\
+\n\
+# 作りかけのプロファイル\n\
+[BROKEN]\n\
+region=us-ashburn-1\n\
+this line is not a key value pair\n\
+\n\
+[OTHER]\n\
+user=ocid1.user.oc1..bbbb\n";
+
+    #[test]
+    fn test_round_trip_is_lossless() {
+        assert_eq!(ConfigDocument::parse(SAMPLE).render(), SAMPLE);
+
+        let crlf = SAMPLE.replace('\n', "\r\n");
+        assert_eq!(ConfigDocument::parse(&crlf).render(), crlf);
+
+        let no_trailing_newline = SAMPLE.trim_end();
+        assert_eq!(
+            ConfigDocument::parse(no_trailing_newline).render(),
+            no_trailing_newline
+        );
+    }
+
+    #[test]
+    fn test_set_edits_only_touched_lines() {
+        let mut document = ConfigDocument::parse(SAMPLE);
+        document
+            .set("DEFAULT", "user", "ocid1.user.oc1..cccc")
+            .unwrap();
+        document.set("DEFAULT", "region", "ap-tokyo-1").unwrap();
+        document
+            .set("DEFAULT", "key_file", "~/.oci/key.pem")
+            .unwrap();
+
+        let expected = SAMPLE
+            .replace("user=ocid1.user.oc1..aaaa", "user=ocid1.user.oc1..cccc")
+            .replace(
+                "tenancy_override=ocid1.tenancy.oc1..other\n",
+                "tenancy_override=ocid1.tenancy.oc1..other\nregion=ap-tokyo-1\n",
+            );
+        assert_eq!(document.render(), expected);
+    }
+
+    #[test]
+    fn test_set_rejects_line_breaks() {
+        let mut document = ConfigDocument::parse(SAMPLE);
+        assert!(document
+            .set("DEFAULT", "region", "ap-tokyo-1\nkey_file=/tmp/evil.pem")
+            .is_err());
+        assert!(document.set("DEFAULT", "region", "ap-tokyo-1\r").is_err());
+        assert_eq!(document.render(), SAMPLE);
+    }
+
+    #[test]
+    fn test_add_rename_and_remove_sections() {
+        let mut document = ConfigDocument::parse(SAMPLE);
+        document.set("NEW", "region", "ap-osaka-1").unwrap();
+        assert!(document
+            .render()
+            .ends_with("user=ocid1.user.oc1..bbbb\n\n[NEW]\nregion=ap-osaka-1\n"));
+
+        assert!(document.rename_section("OTHER", "RENAMED"));
+        assert_eq!(
+            document.get("RENAMED", "user"),
+            Some("ocid1.user.oc1..bbbb")
+        );
+
+        assert!(document.remove_section("BROKEN"));
+        assert_eq!(document.section_names(), vec!["DEFAULT", "RENAMED", "NEW"]);
+        let rendered = document.render();
+        assert!(!rendered.contains("作りかけ"));
+        assert!(rendered.contains("tenancy_override=ocid1.tenancy.oc1..other\n\n[RENAMED]\n"));
+    }
+
+    #[test]
+    fn test_outline_reports_line_numbers() {
+        let document = ConfigDocument::parse(&format!("stray=1\n{}", SAMPLE));
+        assert_eq!(document.orphan_lines(), vec![(1, "stray=1".to_string())]);
+
+        let outline = document.outline();
+        assert_eq!(outline.len(), 3);
+        assert_eq!((outline[0].start_line, outline[0].end_line), (3, 7));
+        assert_eq!(outline[0].keys[1], (6, "key_file".to_string()));
+        assert_eq!((outline[1].start_line, outline[1].end_line), (10, 12));
+        assert_eq!(
+            outline[1].invalid_lines,
+            vec![(12, "this line is not a key value pair".to_string())]
+        );
+    }
+}
@@ -1,15 +1,84 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::models::profile::OciProfile;
+use crate::models::profile::{
+    ConfigDiagnostics, ConfigLine, OciProfile, PrincipalType, SectionDiagnostic,
+};
+use crate::oci::config_backup;
+use crate::oci::config_document::ConfigDocument;
+use crate::oci::principals;
+
+/// サービス別エンドポイント上書きキーの接頭辞
+const SERVICE_ENDPOINT_PREFIX: &str = "endpoint_";
+
+/// 全プロファイルに値を継承させるセクション（OCI CLI と同じ）
+pub const DEFAULT_SECTION: &str = "DEFAULT";
+
+/// このアプリまたは OCI CLI が使うキー（endpoint_<service> を除く）
+const KNOWN_KEYS: [&str; 10] = [
+    "user",
+    "fingerprint",
+    "key_file",
+    "tenancy",
+    "region",
+    "pass_phrase",
+    "security_token_file",
+    "delegation_token_file",
+    "auth_type",
+    "endpoint",
+];
 
 /// OCI 設定ファイルのデフォルトパスを取得
+///
+/// OCI CLI と同様に `OCI_CLI_CONFIG_FILE`、SDK と同様に `OCI_CONFIG_FILE` 環境変数を優先し、
+/// どちらもなければ `~/.oci/config` を返す。
 pub fn default_config_path() -> PathBuf {
-    dirs::home_dir()
-        .unwrap_or_else(|| PathBuf::from("~"))
-        .join(".oci")
-        .join("config")
+    config_path_from_env(|name| std::env::var(name).ok())
+}
+
+/// 環境変数から設定ファイルのパスを決定
+pub fn config_path_from_env(lookup: impl Fn(&str) -> Option<String>) -> PathBuf {
+    ["OCI_CLI_CONFIG_FILE", "OCI_CONFIG_FILE"]
+        .into_iter()
+        .filter_map(&lookup)
+        .find(|value| !value.trim().is_empty())
+        .map(|value| PathBuf::from(expand_tilde(value.trim())))
+        .unwrap_or_else(|| {
+            dirs::home_dir()
+                .unwrap_or_else(|| PathBuf::from("~"))
+                .join(".oci")
+                .join("config")
+        })
+}
+
+/// 起動時に選択するプロファイル名（`OCI_CLI_PROFILE` 環境変数）
+pub fn default_profile_name() -> Option<String> {
+    std::env::var("OCI_CLI_PROFILE")
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// プロファイル名が、設定ファイルのセクション名・ファイル名として安全か確認する
+///
+/// パス区切り・`..`・`[` `]`・制御文字を含む名前は、鍵ファイルの保存先が設定ディレクトリの外に
+/// 出たり、セクションのヘッダーが壊れたりするため拒否する。
+pub fn check_profile_name(name: &str) -> Result<(), String> {
+    let valid = !name.trim().is_empty()
+        && name != "."
+        && !name.contains("..")
+        && !name
+            .chars()
+            .any(|c| matches!(c, '/' | '\\' | '[' | ']') || c.is_control());
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "プロファイル名に使用できない文字が含まれています: {:?}",
+            name
+        ))
+    }
 }
 
 /// チルダ(~)をホームディレクトリに展開
@@ -27,112 +96,382 @@ fn resolve_key_file_path(key_file: &str, config_dir: &Path) -> String {
     // チルダ展開
     let expanded = expand_tilde(key_file);
     let path = Path::new(&expanded);
-    
+
     // 既に絶対パスの場合はそのまま返す
     if path.is_absolute() {
         return expanded;
     }
-    
+
     // 相対パスの場合は設定ファイルディレクトリからの絶対パスに変換
     config_dir.join(path).to_string_lossy().to_string()
 }
 
 /// OCI 設定ファイル（INI 形式）を解析してプロファイル一覧を返す
+///
+/// OCI CLI と同様に、[DEFAULT] のキーは他のすべてのプロファイルに継承される。
+/// 継承後も必須項目が不足しているセクションはプロファイルとして扱わない（ファイルには残る）。
 pub fn parse_config(path: &Path) -> Result<Vec<OciProfile>, String> {
-    let content = fs::read_to_string(path)
-        .map_err(|e| format!("設定ファイルの読み込みに失敗しました: {}", e))?;
+    let document = read_document(path)?;
 
     // 設定ファイルのディレクトリを取得（相対パス解決用）
     let config_dir = path.parent().unwrap_or(Path::new("/"));
 
-    let mut profiles: Vec<OciProfile> = Vec::new();
-    let mut current_section: Option<String> = None;
-    let mut current_fields: HashMap<String, String> = HashMap::new();
+    // 同名のセクションが複数ある場合は最初のものだけを使う
+    let mut seen = HashSet::new();
+    Ok(document
+        .section_names()
+        .into_iter()
+        .filter(|name| seen.insert(*name))
+        .filter_map(|name| {
+            let fields = section_fields(&document, name)?;
+            build_profile(name, &fields, config_dir)
+        })
+        .collect())
+}
+
+/// 設定ファイルの各セクションを検査し、読み込めない理由を行番号付きで返す
+pub fn diagnose_config(path: &Path) -> Result<ConfigDiagnostics, String> {
+    let document = read_document(path)?;
+    let config_dir = path.parent().unwrap_or(Path::new("/"));
+    let to_lines = |lines: Vec<(usize, String)>| {
+        lines
+            .into_iter()
+            .map(|(line, text)| ConfigLine { line, text })
+            .collect::<Vec<_>>()
+    };
 
-    for line in content.lines() {
-        let trimmed = line.trim();
+    let mut seen = HashSet::new();
+    let sections = document
+        .outline()
+        .into_iter()
+        .map(|outline| {
+            let duplicate = !seen.insert(outline.name.clone());
+            // 重複したセクションも先頭のセクションではなく、そのセクション自身の値で診断する
+            let mut fields = match outline.name.as_str() {
+                DEFAULT_SECTION => HashMap::new(),
+                _ => document.fields(DEFAULT_SECTION).unwrap_or_default(),
+            };
+            fields.extend(outline.fields.clone());
 
-        // 空行・コメント行をスキップ
-        if trimmed.is_empty() || trimmed.starts_with('#') {
-            continue;
-        }
+            let missing_keys: Vec<String> = required_keys(&fields)
+                .iter()
+                .filter(|key| fields.get(**key).is_none_or(|v| v.trim().is_empty()))
+                .map(|key| key.to_string())
+                .collect();
 
-        // セクションヘッダー [PROFILE_NAME]
-        if trimmed.starts_with('[') && trimmed.ends_with(']') {
-            // 前のセクションを保存
-            if let Some(section_name) = current_section.take() {
-                if let Some(profile) = build_profile(&section_name, &current_fields, config_dir) {
-                    profiles.push(profile);
+            let mut unknown_keys = Vec::new();
+            for (_, key) in &outline.keys {
+                let known =
+                    KNOWN_KEYS.contains(&key.as_str()) || key.starts_with(SERVICE_ENDPOINT_PREFIX);
+                if !known && !unknown_keys.contains(key) {
+                    unknown_keys.push(key.clone());
                 }
             }
-            current_section = Some(trimmed[1..trimmed.len() - 1].trim().to_string());
-            current_fields.clear();
-            continue;
-        }
 
-        // key=value 解析
-        if let Some(eq_pos) = trimmed.find('=') {
-            let key = trimmed[..eq_pos].trim().to_string();
-            let value = trimmed[eq_pos + 1..].trim().to_string();
-            current_fields.insert(key, value);
-        }
+            let unresolved_files = ["key_file", "security_token_file"]
+                .into_iter()
+                .filter_map(|key| {
+                    let value = fields.get(key).filter(|v| !v.trim().is_empty())?;
+                    let resolved = resolve_key_file_path(value, config_dir);
+                    (!Path::new(&resolved).exists()).then(|| format!("{}: {}", key, resolved))
+                })
+                .collect();
+
+            // 不足しているキーを空にしてプロファイルを組み立て、修復の初期値にする
+            let mut draft_fields = fields.clone();
+            for key in &missing_keys {
+                draft_fields.insert(key.clone(), String::new());
+            }
+            let mut draft =
+                build_profile(&outline.name, &draft_fields, config_dir).unwrap_or_default();
+            if missing_keys.iter().any(|key| key == "key_file") {
+                draft.key_file = String::new();
+            }
+
+            SectionDiagnostic {
+                valid: !duplicate && build_profile(&outline.name, &fields, config_dir).is_some(),
+                name: outline.name,
+                start_line: outline.start_line,
+                end_line: outline.end_line,
+                duplicate,
+                missing_keys,
+                unknown_keys,
+                unresolved_files,
+                unparsable_lines: to_lines(outline.invalid_lines),
+                draft,
+            }
+        })
+        .collect();
+
+    Ok(ConfigDiagnostics {
+        sections,
+        orphan_lines: to_lines(document.orphan_lines()),
+    })
+}
+
+/// プロファイルとして読み込むために必要なキー（build_profile と同じ条件）
+fn required_keys(fields: &HashMap<String, String>) -> &'static [&'static str] {
+    let principal = fields
+        .get("auth_type")
+        .and_then(|value| PrincipalType::parse(value));
+    let session = fields
+        .get("security_token_file")
+        .is_some_and(|value| !value.trim().is_empty());
+
+    if principal == Some(PrincipalType::ResourcePrincipal)
+        && principals::resource_principal_region().is_some()
+    {
+        &[]
+    } else if principal.is_some() {
+        &["region"]
+    } else if session {
+        &["tenancy", "region", "key_file"]
+    } else {
+        &["user", "fingerprint", "tenancy", "region", "key_file"]
     }
+}
 
-    // 最後のセクションを保存
-    if let Some(section_name) = current_section {
-        if let Some(profile) = build_profile(&section_name, &current_fields, config_dir) {
-            profiles.push(profile);
-        }
+/// [DEFAULT] から継承した値を含むセクションのキーと値
+pub fn section_fields(document: &ConfigDocument, name: &str) -> Option<HashMap<String, String>> {
+    let own = document.fields(name)?;
+    if name == DEFAULT_SECTION {
+        return Some(own);
     }
 
-    Ok(profiles)
+    let mut fields = document.fields(DEFAULT_SECTION).unwrap_or_default();
+    fields.extend(own);
+    Some(fields)
+}
+
+/// 設定ファイルを読み込む
+pub fn read_document(path: &Path) -> Result<ConfigDocument, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("設定ファイルの読み込みに失敗しました: {}", e))?;
+    Ok(ConfigDocument::parse(&content))
 }
 
 /// フィールドマップからプロファイルを構築
-fn build_profile(name: &str, fields: &HashMap<String, String>, config_dir: &Path) -> Option<OciProfile> {
+fn build_profile(
+    name: &str,
+    fields: &HashMap<String, String>,
+    config_dir: &Path,
+) -> Option<OciProfile> {
+    // エンドポイント上書き（endpoint_<service>=URL）
+    let service_endpoints = fields
+        .iter()
+        .filter_map(|(key, value)| {
+            key.strip_prefix(SERVICE_ENDPOINT_PREFIX)
+                .map(|service| (service.to_string(), value.clone()))
+        })
+        .collect();
+
+    // セッショントークン認証のプロファイルは user / fingerprint を持たない
+    let security_token_file = fields
+        .get("security_token_file")
+        .filter(|value| !value.trim().is_empty())
+        .map(|value| resolve_key_file_path(value, config_dir));
+    let optional_for_session = |key: &str| match fields.get(key) {
+        Some(value) => Some(value.clone()),
+        None if security_token_file.is_some() => Some(String::new()),
+        None => None,
+    };
+
+    // プリンシパル認証のプロファイルはリージョン以外の認証情報を持たない
+    let principal = fields
+        .get("auth_type")
+        .and_then(|value| PrincipalType::parse(value));
+    if principal.is_some() {
+        // リソース・プリンシパルのリージョンは実行環境の環境変数でも指定できる
+        let region = fields
+            .get("region")
+            .filter(|region| !region.trim().is_empty())
+            .cloned()
+            .or_else(|| {
+                (principal == Some(PrincipalType::ResourcePrincipal))
+                    .then(principals::resource_principal_region)
+                    .flatten()
+            });
+        return Some(OciProfile {
+            name: name.to_string(),
+            tenancy: fields.get("tenancy").cloned().unwrap_or_default(),
+            region: region?,
+            principal,
+            endpoint: fields.get("endpoint").cloned(),
+            service_endpoints,
+            ..Default::default()
+        });
+    }
+
     Some(OciProfile {
         name: name.to_string(),
-        user: fields.get("user")?.clone(),
+        user: optional_for_session("user")?,
         tenancy: fields.get("tenancy")?.clone(),
         region: fields.get("region")?.clone(),
-        fingerprint: fields.get("fingerprint")?.clone(),
+        fingerprint: optional_for_session("fingerprint")?,
         key_file: resolve_key_file_path(fields.get("key_file")?, config_dir),
+        pass_phrase: fields.get("pass_phrase").cloned(),
+        security_token_file,
+        principal: None,
+        endpoint: fields.get("endpoint").cloned(),
+        service_endpoints,
     })
 }
 
-/// プロファイル一覧を OCI 設定ファイル形式で書き出す
-pub fn write_config(path: &Path, profiles: &[OciProfile]) -> Result<(), String> {
+/// 設定ファイルを書き込む
+pub fn write_document(path: &Path, document: &ConfigDocument) -> Result<(), String> {
     // 親ディレクトリを作成
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)
             .map_err(|e| format!("ディレクトリの作成に失敗しました: {}", e))?;
     }
 
-    let mut content = String::new();
-    for (i, profile) in profiles.iter().enumerate() {
-        if i > 0 {
-            content.push('\n');
+    // 書き込み前の内容をバックアップし、一時ファイル経由で置き換える（パーミッションは 600）
+    config_backup::backup_config(path, config_backup::MAX_BACKUPS)?;
+    config_backup::atomic_write(path, document.render().as_bytes())
+}
+
+/// 設定ファイルを読み込む（存在しない場合は空のドキュメント）
+fn read_document_or_empty(path: &Path) -> Result<ConfigDocument, String> {
+    if path.exists() {
+        read_document(path)
+    } else {
+        Ok(ConfigDocument::parse(""))
+    }
+}
+
+/// プロファイルを設定ファイルに書き出す
+///
+/// 渡されたプロファイルのセクションのみを書き換え、それ以外のセクション・コメント・
+/// 未知のキーはそのまま残す。
+pub fn write_config(path: &Path, profiles: &[OciProfile]) -> Result<(), String> {
+    let mut document = read_document_or_empty(path)?;
+    let config_dir = path.parent().unwrap_or(Path::new("/"));
+    for profile in profiles {
+        apply_profile(&mut document, profile, config_dir)?;
+    }
+    write_document(path, &document)
+}
+
+/// プロファイルを追加・更新する
+///
+/// `previous_name` を指定すると、そのセクションを `profile.name` に改名してから更新する。
+pub fn upsert_profile(
+    path: &Path,
+    previous_name: Option<&str>,
+    profile: &OciProfile,
+) -> Result<(), String> {
+    let mut document = read_document_or_empty(path)?;
+
+    if let Some(previous_name) = previous_name.filter(|n| *n != profile.name) {
+        if document.has_section(&profile.name) {
+            return Err(format!(
+                "プロファイル '{}' は既に存在します。",
+                profile.name
+            ));
+        }
+        if !document.rename_section(previous_name, &profile.name) {
+            return Err(format!(
+                "プロファイル '{}' が見つかりません。",
+                previous_name
+            ));
         }
-        content.push_str(&format!("[{}]\n", profile.name));
-        content.push_str(&format!("user={}\n", profile.user));
-        content.push_str(&format!("fingerprint={}\n", profile.fingerprint));
-        content.push_str(&format!("tenancy={}\n", profile.tenancy));
-        content.push_str(&format!("region={}\n", profile.region));
-        content.push_str(&format!("key_file={}\n", profile.key_file));
     }
 
-    fs::write(path, content)
-        .map_err(|e| format!("設定ファイルの書き込みに失敗しました: {}", e))?;
+    let config_dir = path.parent().unwrap_or(Path::new("/"));
+    apply_profile(&mut document, profile, config_dir)?;
+    write_document(path, &document)
+}
+
+/// プロファイルのセクションを削除する
+pub fn remove_profile(path: &Path, name: &str) -> Result<(), String> {
+    let mut document = read_document(path)?;
+    if !document.remove_section(name) {
+        return Err(format!("プロファイル '{}' が見つかりません。", name));
+    }
+    write_document(path, &document)
+}
 
-    // パーミッションを 600 に設定（Unix系のみ）
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        let permissions = fs::Permissions::from_mode(0o600);
-        fs::set_permissions(path, permissions)
-            .map_err(|e| format!("パーミッションの設定に失敗しました: {}", e))?;
+/// プロファイルの内容をセクションに反映する
+///
+/// このアプリが扱うキーのみを変更し、それ以外のキーには触れない。パスは元の値と
+/// 同じファイルを指す場合、元の表記（`~` や相対パス）のまま残す。
+fn apply_profile(
+    document: &mut ConfigDocument,
+    profile: &OciProfile,
+    config_dir: &Path,
+) -> Result<(), String> {
+    let name = profile.name.as_str();
+    check_profile_name(name)?;
+    document.add_section(name);
+
+    // 現在の値（DEFAULT から継承した値を含む）と同じ場合は書き換えない
+    let write = |document: &mut ConfigDocument, key: &str, value: Option<&str>, is_path: bool| {
+        let value = value.filter(|v| !v.is_empty());
+        let current = document.get(name, key).or_else(|| {
+            (name != DEFAULT_SECTION)
+                .then(|| document.get(DEFAULT_SECTION, key))
+                .flatten()
+        });
+        let unchanged = match (current, value) {
+            (Some(current), Some(value)) if is_path => {
+                resolve_key_file_path(current, config_dir) == value
+            }
+            (current, value) => current == value,
+        };
+        if unchanged {
+            return Ok(());
+        }
+        match value {
+            Some(value) => document.set(name, key, value),
+            None => {
+                document.remove_key(name, key);
+                Ok(())
+            }
+        }
+    };
+
+    if let Some(principal) = profile.principal {
+        write(document, "auth_type", Some(principal.as_str()), false)?;
+        write(document, "tenancy", Some(&profile.tenancy), false)?;
+        write(document, "region", Some(&profile.region), false)?;
+        for key in [
+            "user",
+            "fingerprint",
+            "key_file",
+            "pass_phrase",
+            "security_token_file",
+        ] {
+            document.remove_key(name, key);
+        }
+    } else {
+        document.remove_key(name, "auth_type");
+        write(document, "user", Some(&profile.user), false)?;
+        write(document, "fingerprint", Some(&profile.fingerprint), false)?;
+        write(document, "tenancy", Some(&profile.tenancy), false)?;
+        write(document, "region", Some(&profile.region), false)?;
+        write(document, "key_file", Some(&profile.key_file), true)?;
+        write(
+            document,
+            "security_token_file",
+            profile.security_token_file.as_deref(),
+            true,
+        )?;
+        // パスフレーズはフロントエンドに送らないため、未指定の場合は既存の値を残す
+        if let Some(pass_phrase) = profile.pass_phrase.as_deref() {
+            write(document, "pass_phrase", Some(pass_phrase), false)?;
+        }
     }
 
+    // エンドポイント上書き
+    write(document, "endpoint", profile.endpoint.as_deref(), false)?;
+    document.remove_keys_where(name, |key| {
+        key.strip_prefix(SERVICE_ENDPOINT_PREFIX)
+            .is_some_and(|service| !profile.service_endpoints.contains_key(service))
+    });
+    for (service, endpoint) in &profile.service_endpoints {
+        let key = format!("{}{}", SERVICE_ENDPOINT_PREFIX, service);
+        write(document, &key, Some(endpoint), false)?;
+    }
     Ok(())
 }
 
@@ -142,6 +481,12 @@ mod tests {
     use std::io::Write;
     use tempfile::NamedTempFile;
 
+    const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/config");
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(FIXTURES).join(name)
+    }
+
     #[test]
     fn test_parse_config() {
         let mut file = NamedTempFile::new().unwrap();
@@ -182,6 +527,7 @@ key_file=/home/test/.oci/prod_key.pem
             region: "ap-tokyo-1".to_string(),
             fingerprint: "aa:bb:cc:dd".to_string(),
             key_file: "/home/test/.oci/key.pem".to_string(),
+            ..Default::default()
         }];
 
         write_config(file.path(), &profiles).unwrap();
@@ -190,4 +536,285 @@ key_file=/home/test/.oci/prod_key.pem
         assert_eq!(parsed[0].name, "DEFAULT");
         assert_eq!(parsed[0].user, "ocid1.user.oc1..test");
     }
+
+    #[test]
+    fn test_upsert_creates_new_file_and_rejects_line_breaks() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config");
+        let mut profile = OciProfile {
+            name: "NEW".to_string(),
+            user: "ocid1.user.oc1..test".to_string(),
+            tenancy: "ocid1.tenancy.oc1..test".to_string(),
+            region: "ap-tokyo-1".to_string(),
+            fingerprint: "aa:bb".to_string(),
+            key_file: "/home/test/.oci/key.pem".to_string(),
+            ..Default::default()
+        };
+
+        upsert_profile(&path, None, &profile).unwrap();
+        let original = fs::read_to_string(&path).unwrap();
+        assert!(original.starts_with("[NEW]\nuser=ocid1.user.oc1..test\n"));
+
+        profile.region = "ap-osaka-1\nkey_file=/tmp/evil.pem".to_string();
+        assert!(upsert_profile(&path, Some("NEW"), &profile).is_err());
+        profile.region = "ap-osaka-1".to_string();
+        profile.name = "x]\n[DEFAULT".to_string();
+        assert!(upsert_profile(&path, None, &profile).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), original);
+    }
+
+    #[test]
+    fn test_parse_session_token_profile() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(
+            file,
+            r#"[SESSION]
+fingerprint=aa:bb:cc:dd
+key_file=~/.oci/sessions/SESSION/oci_api_key.pem
+tenancy=ocid1.tenancy.oc1..aaaatest
+region=ap-osaka-1
+security_token_file=sessions/SESSION/token
+"#
+        )
+        .unwrap();
+
+        let profiles = parse_config(file.path()).unwrap();
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[0].user, "");
+        let token_file = profiles[0].security_token_file.as_deref().unwrap();
+        assert!(Path::new(token_file).is_absolute());
+        assert!(token_file.ends_with("sessions/SESSION/token"));
+    }
+
+    #[test]
+    fn test_update_preserves_other_content() {
+        let original = r#"# 共有の設定ファイル
+[DEFAULT]
+user=ocid1.user.oc1..aaaatest
+fingerprint=aa:bb:cc:dd
+tenancy=ocid1.tenancy.oc1..aaaatest
+region=ap-tokyo-1
+# 鍵は ~/.oci に置く
+key_file=~/.oci/key.pem
+pass_phrase=secret
+tenancy_override=ocid1.tenancy.oc1..other
+
+[INCOMPLETE]
+region=us-ashburn-1
+
+[PRODUCTION]
+user=ocid1.user.oc1..bbbbtest
+fingerprint=11:22:33:44
+tenancy=ocid1.tenancy.oc1..bbbbtest
+region=us-ashburn-1
+key_file=keys/prod.pem
+custom_tool_setting=1
+"#;
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "{}", original).unwrap();
+
+        let mut profile = parse_config(file.path())
+            .unwrap()
+            .into_iter()
+            .find(|p| p.name == "DEFAULT")
+            .unwrap();
+        // フロントエンドから戻ってきたプロファイルにはパスフレーズが含まれない
+        assert!(serde_json::to_value(&profile)
+            .unwrap()
+            .get("pass_phrase")
+            .is_none());
+        profile.pass_phrase = None;
+        profile.region = "ap-osaka-1".to_string();
+        upsert_profile(file.path(), Some("DEFAULT"), &profile).unwrap();
+
+        let updated = fs::read_to_string(file.path()).unwrap();
+        assert_eq!(
+            updated,
+            original.replace("region=ap-tokyo-1", "region=ap-osaka-1")
+        );
+
+        remove_profile(file.path(), "PRODUCTION").unwrap();
+        let removed = fs::read_to_string(file.path()).unwrap();
+        assert!(removed.contains("[INCOMPLETE]\nregion=us-ashburn-1\n"));
+        assert!(!removed.contains("custom_tool_setting"));
+    }
+
+    #[test]
+    fn test_check_profile_name() {
+        assert!(check_profile_name("prod_tokyo-1").is_ok());
+        for name in [
+            "",
+            "../../.ssh",
+            "a/b",
+            "a\\b",
+            "x]\n[DEFAULT",
+            "a\tb",
+            "..",
+        ] {
+            assert!(check_profile_name(name).is_err(), "{:?}", name);
+        }
+    }
+
+    #[test]
+    fn test_rename_profile() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(
+            file,
+            "[OLD]\nregion=ap-tokyo-1\nauth_type=instance_principal\ncustom=1\n\n[OTHER]\nregion=x\n"
+        )
+        .unwrap();
+
+        let mut profile = parse_config(file.path()).unwrap().remove(0);
+        profile.name = "OTHER".to_string();
+        assert!(upsert_profile(file.path(), Some("OLD"), &profile).is_err());
+
+        profile.name = "NEW".to_string();
+        upsert_profile(file.path(), Some("OLD"), &profile).unwrap();
+        assert_eq!(
+            fs::read_to_string(file.path()).unwrap(),
+            "[NEW]\nregion=ap-tokyo-1\nauth_type=instance_principal\ncustom=1\n\n[OTHER]\nregion=x\n"
+        );
+    }
+
+    #[test]
+    fn test_config_path_from_env() {
+        let path = config_path_from_env(|name| match name {
+            "OCI_CLI_CONFIG_FILE" => Some("/etc/oci/cli_config".to_string()),
+            "OCI_CONFIG_FILE" => Some("/etc/oci/sdk_config".to_string()),
+            _ => None,
+        });
+        assert_eq!(path, PathBuf::from("/etc/oci/cli_config"));
+
+        let path = config_path_from_env(|name| match name {
+            "OCI_CLI_CONFIG_FILE" => Some(" ".to_string()),
+            "OCI_CONFIG_FILE" => Some("/etc/oci/sdk_config".to_string()),
+            _ => None,
+        });
+        assert_eq!(path, PathBuf::from("/etc/oci/sdk_config"));
+
+        let path = config_path_from_env(|_| None);
+        assert!(path.ends_with(".oci/config"));
+    }
+
+    #[test]
+    fn test_parse_cli_generated_config() {
+        let profiles = parse_config(&fixture("cli_setup")).unwrap();
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[0].name, "DEFAULT");
+        assert_eq!(
+            profiles[0].fingerprint,
+            "2f:35:22:36:9c:ba:71:75:55:0b:cf:86:68:dc:84:3c"
+        );
+        assert!(Path::new(&profiles[0].key_file).is_absolute());
+    }
+
+    #[test]
+    fn test_default_section_is_inherited() {
+        let profiles = parse_config(&fixture("inheritance")).unwrap();
+        let names: Vec<&str> = profiles.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["DEFAULT", "DEV", "PROD", "SESSION"]);
+
+        let default = &profiles[0];
+        let dev = &profiles[1];
+        assert_eq!(dev.region, "ap-osaka-1");
+        assert_eq!(dev.user, default.user);
+        assert_eq!(dev.key_file, default.key_file);
+
+        let prod = &profiles[2];
+        assert_eq!(prod.region, "us-ashburn-1");
+        assert!(prod.key_file.ends_with("prod_api_key.pem"));
+        assert_eq!(
+            prod.fingerprint,
+            "11:22:33:44:55:66:77:88:99:aa:bb:cc:dd:ee:ff:00"
+        );
+
+        let session = &profiles[3];
+        assert_eq!(session.user, default.user);
+        assert_eq!(
+            session.security_token_file.as_deref(),
+            Some("/home/opc/.oci/sessions/SESSION/token")
+        );
+    }
+
+    #[test]
+    fn test_parse_comments_delimiters_and_key_case() {
+        let profiles = parse_config(&fixture("mixed_syntax")).unwrap();
+        // DEFAULT は必須項目が不足しているため、継承元としてのみ使われる
+        assert_eq!(profiles.len(), 1);
+
+        let profile = &profiles[0];
+        assert_eq!(profile.name, "ENCRYPTED");
+        assert_eq!(profile.region, "ap-tokyo-1");
+        assert!(profile.user.starts_with("ocid1.user.oc1.."));
+        assert_eq!(
+            profile.fingerprint,
+            "2f:35:22:36:9c:ba:71:75:55:0b:cf:86:68:dc:84:3c"
+        );
+        assert_eq!(profile.pass_phrase.as_deref(), Some("p@ss=word;#1"));
+        assert_eq!(
+            profile.endpoint.as_deref(),
+            Some("http://localhost:8080/?a=b")
+        );
+    }
+
+    #[test]
+    fn test_update_does_not_copy_inherited_values() {
+        let original = fs::read_to_string(fixture("inheritance")).unwrap();
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "{}", original).unwrap();
+
+        let mut dev = parse_config(file.path()).unwrap().remove(1);
+        dev.region = "ap-singapore-1".to_string();
+        upsert_profile(file.path(), Some("DEV"), &dev).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(file.path()).unwrap(),
+            original.replace("region=ap-osaka-1", "region=ap-singapore-1")
+        );
+    }
+
+    #[test]
+    fn test_diagnose_config_reports_broken_sections() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(
+            file,
+            "region=ap-tokyo-1\n\n[DEFAULT]\ntenancy=ocid1.tenancy.oc1..aaaatest\nregion=ap-tokyo-1\n\n[DEV]\nuser=ocid1.user.oc1..aaaatest\nfingerprint=aa:bb\nkey_file=/nonexistent/key.pem\nlog_level=debug\nnot a key value pair\n\n[DEV]\nregion=ap-osaka-1\n"
+        )
+        .unwrap();
+
+        let diagnostics = diagnose_config(file.path()).unwrap();
+        assert_eq!(diagnostics.orphan_lines.len(), 1);
+        assert_eq!(diagnostics.orphan_lines[0].line, 1);
+
+        let sections = &diagnostics.sections;
+        assert_eq!(sections.len(), 3);
+        // DEFAULT は継承元としては使えるが、単独ではプロファイルにならない
+        assert!(!sections[0].valid);
+        assert_eq!(
+            sections[0].missing_keys,
+            vec!["user", "fingerprint", "key_file"]
+        );
+
+        let dev = &sections[1];
+        assert!(dev.valid);
+        assert_eq!((dev.start_line, dev.end_line), (7, 12));
+        assert_eq!(dev.unknown_keys, vec!["log_level"]);
+        assert_eq!(dev.unresolved_files, vec!["key_file: /nonexistent/key.pem"]);
+        assert_eq!(dev.unparsable_lines[0].line, 12);
+        assert_eq!(dev.draft.region, "ap-tokyo-1");
+
+        let duplicate = &sections[2];
+        assert!(duplicate.duplicate);
+        assert!(!duplicate.valid);
+        assert_eq!(duplicate.start_line, 14);
+        assert_eq!(
+            duplicate.missing_keys,
+            vec!["user", "fingerprint", "key_file"]
+        );
+        assert!(duplicate.unresolved_files.is_empty());
+        assert_eq!(duplicate.draft.region, "ap-osaka-1");
+
+        // 重複したセクションはプロファイル一覧に二重に現れない
+        assert_eq!(parse_config(file.path()).unwrap().len(), 1);
+    }
 }
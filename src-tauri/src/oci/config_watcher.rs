@@ -0,0 +1,272 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex, Weak};
+use std::thread;
+use std::time::Duration;
+
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::models::profile::{OciProfile, ProfileChanges};
+use crate::oci::config_parser;
+
+/// 連続した変更通知をまとめる時間（エディタの保存は複数のイベントになる）
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// 設定ファイルと、プロファイルが参照する鍵・トークンファイルの監視
+///
+/// ファイルはエディタや OCI CLI によって置き換えられることがあるため、ファイルを含む
+/// ディレクトリを監視する。シンボリックリンクの場合はリンク先のディレクトリも監視する。
+/// drop すると監視を終了する。
+pub struct ConfigWatcher {
+    _watcher: Arc<Mutex<RecommendedWatcher>>,
+}
+
+impl ConfigWatcher {
+    /// 監視を開始し、プロファイルが変化するたびに `on_change` を呼び出す
+    pub fn start<F>(config_path: &Path, on_change: F) -> Result<Self, String>
+    where
+        F: Fn(ProfileChanges) + Send + 'static,
+    {
+        let (tx, rx) = channel();
+        let watcher = notify::recommended_watcher(move |event| {
+            tx.send(event).ok();
+        })
+        .map_err(|e| format!("設定ファイルの監視を開始できません: {}", e))?;
+        let watcher = Arc::new(Mutex::new(watcher));
+
+        let mut state = WatchState {
+            config_path: config_path.to_path_buf(),
+            profiles: read_profiles(config_path),
+            watched_dirs: HashSet::new(),
+            watcher: Arc::downgrade(&watcher),
+        };
+        state.update_watched_dirs();
+
+        thread::Builder::new()
+            .name("oci-config-watcher".to_string())
+            .spawn(move || state.run(rx, on_change))
+            .map_err(|e| format!("設定ファイルの監視を開始できません: {}", e))?;
+
+        Ok(Self { _watcher: watcher })
+    }
+}
+
+/// 監視スレッドの状態
+struct WatchState {
+    config_path: PathBuf,
+    /// 直前に読み込んだプロファイル
+    profiles: Vec<OciProfile>,
+    watched_dirs: HashSet<PathBuf>,
+    /// ConfigWatcher が drop されると無効になり、イベントの送信元も閉じる
+    watcher: Weak<Mutex<RecommendedWatcher>>,
+}
+
+impl WatchState {
+    fn run<F>(&mut self, rx: Receiver<notify::Result<Event>>, on_change: F)
+    where
+        F: Fn(ProfileChanges),
+    {
+        // 送信元（ウォッチャー）が drop されると recv がエラーになり終了する
+        while let Ok(event) = rx.recv() {
+            let mut paths = event_paths(event);
+            loop {
+                match rx.recv_timeout(DEBOUNCE) {
+                    Ok(event) => paths.extend(event_paths(event)),
+                    Err(RecvTimeoutError::Timeout) => break,
+                    Err(RecvTimeoutError::Disconnected) => return,
+                }
+            }
+
+            let changes = self.handle(&paths);
+            if !changes.is_empty() {
+                on_change(changes);
+            }
+        }
+    }
+
+    /// 変更されたパスから、追加・削除・変更されたプロファイルを求める
+    fn handle(&mut self, paths: &HashSet<PathBuf>) -> ProfileChanges {
+        let paths: HashSet<PathBuf> = paths.iter().map(|path| normalize(path)).collect();
+        let touched = |file: &Path| watch_paths(file).iter().any(|path| paths.contains(path));
+
+        let mut changes = ProfileChanges::default();
+        if touched(&self.config_path) {
+            let profiles = read_profiles(&self.config_path);
+            changes = diff_profiles(&self.profiles, &profiles);
+            self.profiles = profiles;
+            self.update_watched_dirs();
+        }
+
+        // 鍵・トークンファイルが変わったプロファイル
+        for profile in &self.profiles {
+            let already_reported = changes.added.iter().any(|p| p.name == profile.name)
+                || changes.changed.iter().any(|p| p.name == profile.name);
+            if !already_reported && referenced_files(profile).any(touched) {
+                changes.changed.push(profile.clone());
+            }
+        }
+        changes
+    }
+
+    /// 設定ファイルと参照ファイルのディレクトリを監視対象にする
+    fn update_watched_dirs(&mut self) {
+        let Some(watcher) = self.watcher.upgrade() else {
+            return;
+        };
+        let mut watcher = watcher.lock().unwrap_or_else(|e| e.into_inner());
+
+        let dirs: HashSet<PathBuf> = std::iter::once(self.config_path.as_path())
+            .chain(self.profiles.iter().flat_map(referenced_files))
+            .flat_map(watch_paths)
+            .filter_map(|file| file.parent().map(Path::to_path_buf))
+            .filter(|dir| dir.is_dir())
+            .collect();
+
+        for dir in self.watched_dirs.difference(&dirs) {
+            watcher.unwatch(dir).ok();
+        }
+        for dir in dirs.difference(&self.watched_dirs) {
+            if let Err(e) = watcher.watch(dir, RecursiveMode::NonRecursive) {
+                log::warn!("{} を監視できません: {}", dir.display(), e);
+            }
+        }
+        self.watched_dirs = dirs;
+    }
+}
+
+/// 設定ファイルを読み込む（存在しない・読み込めない場合は空）
+fn read_profiles(config_path: &Path) -> Vec<OciProfile> {
+    config_parser::parse_config(config_path).unwrap_or_default()
+}
+
+/// プロファイルが参照する鍵・トークンファイル
+fn referenced_files(profile: &OciProfile) -> impl Iterator<Item = &Path> {
+    std::iter::once(profile.key_file.as_str())
+        .chain(profile.security_token_file.as_deref())
+        .filter(|file| !file.is_empty())
+        .map(Path::new)
+}
+
+/// ディレクトリ部分を正規化したパス（ファイル自体は削除済みでもよい）
+///
+/// 監視するディレクトリとイベントのパスを同じ表記で比較するため、`..` やシンボリック
+/// リンクを含むディレクトリを解決する。ファイル名のシンボリックリンクは解決しない。
+fn normalize(path: &Path) -> PathBuf {
+    match (path.parent(), path.file_name()) {
+        (Some(dir), Some(name)) => dir
+            .canonicalize()
+            .map(|dir| dir.join(name))
+            .unwrap_or_else(|_| path.to_path_buf()),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// ファイルの変更として扱うパス（ファイル自体と、シンボリックリンクの場合はリンク先）
+fn watch_paths(file: &Path) -> Vec<PathBuf> {
+    let mut paths = vec![normalize(file)];
+    if let Ok(target) = file.canonicalize() {
+        if !paths.contains(&target) {
+            paths.push(target);
+        }
+    }
+    paths
+}
+
+fn event_paths(event: notify::Result<Event>) -> HashSet<PathBuf> {
+    event
+        .map(|e| e.paths.into_iter().collect())
+        .unwrap_or_default()
+}
+
+/// 2 つのプロファイル一覧の差分
+pub fn diff_profiles(old: &[OciProfile], new: &[OciProfile]) -> ProfileChanges {
+    let find = |profiles: &'_ [OciProfile], name: &str| -> Option<OciProfile> {
+        profiles.iter().find(|p| p.name == name).cloned()
+    };
+
+    ProfileChanges {
+        added: new
+            .iter()
+            .filter(|p| find(old, &p.name).is_none())
+            .cloned()
+            .collect(),
+        removed: old
+            .iter()
+            .filter(|p| find(new, &p.name).is_none())
+            .map(|p| p.name.clone())
+            .collect(),
+        changed: new
+            .iter()
+            .filter(|p| find(old, &p.name).is_some_and(|previous| previous != **p))
+            .cloned()
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const PROFILE: &str = "[DEFAULT]\nuser=ocid1.user.oc1..aaaa\nfingerprint=aa:bb\ntenancy=ocid1.tenancy.oc1..aaaa\nregion=ap-tokyo-1\nkey_file=key.pem\n";
+
+    #[test]
+    fn test_watcher_reports_config_and_key_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("config");
+        fs::write(&config_path, PROFILE).unwrap();
+        fs::write(dir.path().join("key.pem"), "old").unwrap();
+
+        let (tx, rx) = channel();
+        let _watcher = ConfigWatcher::start(&config_path, move |changes| {
+            tx.send(changes).ok();
+        })
+        .unwrap();
+
+        fs::write(
+            &config_path,
+            format!(
+                "{}\n[DEV]\nregion=ap-osaka-1\n",
+                PROFILE.replace("ap-tokyo-1", "us-ashburn-1")
+            ),
+        )
+        .unwrap();
+        let changes = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert_eq!(changes.added[0].name, "DEV");
+        assert_eq!(changes.changed[0].region, "us-ashburn-1");
+        assert!(changes.removed.is_empty());
+
+        fs::write(dir.path().join("key.pem"), "new").unwrap();
+        let changes = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        let mut names: Vec<&str> = changes.changed.iter().map(|p| p.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["DEFAULT", "DEV"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_watcher_follows_symlinked_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let dotfiles = dir.path().join("dotfiles");
+        let oci_dir = dir.path().join("oci");
+        fs::create_dir(&dotfiles).unwrap();
+        fs::create_dir(&oci_dir).unwrap();
+        let target = dotfiles.join("config");
+        fs::write(&target, PROFILE).unwrap();
+        let config_path = oci_dir.join("config");
+        std::os::unix::fs::symlink(&target, &config_path).unwrap();
+
+        let (tx, rx) = channel();
+        // ".." を含むパスで指定しても、正規化したパスでイベントと照合する
+        let _watcher = ConfigWatcher::start(&oci_dir.join("../oci/config"), move |changes| {
+            tx.send(changes).ok();
+        })
+        .unwrap();
+
+        // リンク先のファイルだけを書き換える
+        fs::write(&target, PROFILE.replace("ap-tokyo-1", "us-ashburn-1")).unwrap();
+        let changes = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert_eq!(changes.changed[0].region, "us-ashburn-1");
+    }
+}
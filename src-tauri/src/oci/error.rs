@@ -0,0 +1,194 @@
+use serde::{Deserialize, Serialize, Serializer};
+
+/// OCI 操作で発生するエラー
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum OciError {
+    /// 通信エラー（接続失敗・タイムアウトなど）
+    #[error("API リクエストに失敗しました: {message}")]
+    Transport { message: String },
+    /// 認証情報エラー（秘密鍵の読み込み・署名の失敗など）
+    #[error("{message}")]
+    Auth { message: String },
+    /// 暗号化された秘密鍵のパスフレーズが未指定
+    #[error("秘密鍵 '{key_file}' のパスフレーズを入力してください。")]
+    PassphraseRequired { key_file: String },
+    /// 設定エラー（設定ファイル・プロファイルの不備など）
+    #[error("{message}")]
+    Config { message: String },
+    /// レスポンスの解析エラー
+    #[error("レスポンスの解析に失敗しました: {message}")]
+    Parse { message: String },
+    /// 待機の失敗（タイムアウト・想定外の状態への遷移）
+    #[error("{message}")]
+    Wait { message: String },
+    /// OCI サービスが返したエラー
+    #[error("API エラー（ステータス: {status}, コード: {code}）: {message}")]
+    Service {
+        status: u16,
+        code: String,
+        message: String,
+        opc_request_id: Option<String>,
+    },
+}
+
+/// 設定ファイル・プロファイルの処理が返す文字列のエラーは設定エラーとして扱う
+impl From<String> for OciError {
+    fn from(message: String) -> Self {
+        Self::config(message)
+    }
+}
+
+/// OCI のエラーレスポンスボディ
+#[derive(Debug, Deserialize)]
+struct ServiceErrorBody {
+    code: Option<String>,
+    message: Option<String>,
+}
+
+impl OciError {
+    pub fn transport(message: impl Into<String>) -> Self {
+        Self::Transport {
+            message: message.into(),
+        }
+    }
+
+    pub fn auth(message: impl Into<String>) -> Self {
+        Self::Auth {
+            message: message.into(),
+        }
+    }
+
+    pub fn config(message: impl Into<String>) -> Self {
+        Self::Config {
+            message: message.into(),
+        }
+    }
+
+    pub fn parse(message: impl Into<String>) -> Self {
+        Self::Parse {
+            message: message.into(),
+        }
+    }
+
+    pub fn wait(message: impl Into<String>) -> Self {
+        Self::Wait {
+            message: message.into(),
+        }
+    }
+
+    /// エラーレスポンスのステータス・ボディ・opc-request-id からサービスエラーを生成
+    pub fn from_response(status: u16, body: &str, opc_request_id: Option<String>) -> Self {
+        let parsed = serde_json::from_str::<ServiceErrorBody>(body).ok();
+        let code = parsed
+            .as_ref()
+            .and_then(|b| b.code.clone())
+            .unwrap_or_else(|| "Unknown".to_string());
+        let message = parsed
+            .and_then(|b| b.message)
+            .unwrap_or_else(|| body.trim().to_string());
+
+        Self::Service {
+            status,
+            code,
+            message,
+            opc_request_id,
+        }
+    }
+
+    /// エラー種別（フロントエンドでの分岐用）
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Transport { .. } => "transport",
+            Self::Auth { .. } => "auth",
+            Self::PassphraseRequired { .. } => "passphraseRequired",
+            Self::Config { .. } => "config",
+            Self::Parse { .. } => "parse",
+            Self::Wait { .. } => "wait",
+            Self::Service { .. } => "service",
+        }
+    }
+
+    /// HTTP ステータス（サービスエラーのみ）
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Self::Service { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// opc-request-id（サービスエラーのみ）
+    pub fn opc_request_id(&self) -> Option<&str> {
+        match self {
+            Self::Service { opc_request_id, .. } => opc_request_id.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// フロントエンドに渡すエラー表現
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SerializedError<'a> {
+    kind: &'static str,
+    message: String,
+    status: Option<u16>,
+    code: Option<&'a str>,
+    opc_request_id: Option<&'a str>,
+}
+
+impl Serialize for OciError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let code = match self {
+            Self::Service { code, .. } => Some(code.as_str()),
+            _ => None,
+        };
+
+        SerializedError {
+            kind: self.kind(),
+            message: self.to_string(),
+            status: self.status(),
+            code,
+            opc_request_id: self.opc_request_id(),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_response_parses_oci_error_body() {
+        let err = OciError::from_response(
+            404,
+            r#"{"code":"NotAuthorizedOrNotFound","message":"Authorization failed or requested resource not found."}"#,
+            Some("ABC123".to_string()),
+        );
+        let json = serde_json::to_value(&err).unwrap();
+        assert_eq!(json["kind"], "service");
+        assert_eq!(json["status"], 404);
+        assert_eq!(json["code"], "NotAuthorizedOrNotFound");
+        assert_eq!(json["opcRequestId"], "ABC123");
+    }
+
+    #[test]
+    fn test_string_errors_serialize_as_config_errors() {
+        let err = OciError::from("設定ファイルの読み込みに失敗しました".to_string());
+        let json = serde_json::to_value(&err).unwrap();
+        assert_eq!(json["kind"], "config");
+        assert_eq!(json["message"], "設定ファイルの読み込みに失敗しました");
+    }
+
+    #[test]
+    fn test_from_response_keeps_non_json_body() {
+        let err = OciError::from_response(502, "Bad Gateway", None);
+        match err {
+            OciError::Service { code, message, .. } => {
+                assert_eq!(code, "Unknown");
+                assert_eq!(message, "Bad Gateway");
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+}
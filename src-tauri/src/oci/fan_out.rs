@@ -0,0 +1,293 @@
+use std::future::Future;
+
+use futures_util::stream::{self, StreamExt};
+
+use crate::models::iam::{
+    CompartmentFailure, CompartmentItem, CompartmentListing, CompartmentNode,
+};
+use crate::models::profile::OciProfile;
+use crate::oci::client::OciClient;
+use crate::oci::compartments::{self, CompartmentCache};
+use crate::oci::error::OciError;
+
+/// 同時に一覧を取得するコンパートメントの数
+pub const DEFAULT_CONCURRENCY: usize = 8;
+
+/// 一覧を取得するコンパートメント
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompartmentTarget {
+    pub id: String,
+    pub path: String,
+}
+
+/// ツリーから `compartment_id` のコンパートメント（`include_subtree` の場合は子孫も）を探す
+///
+/// 親から順に並べる。ツリーにない場合は None。
+pub fn targets_in_tree(
+    tree: &CompartmentNode,
+    compartment_id: &str,
+    include_subtree: bool,
+) -> Option<Vec<CompartmentTarget>> {
+    fn find<'a>(node: &'a CompartmentNode, id: &str) -> Option<&'a CompartmentNode> {
+        if node.id == id {
+            return Some(node);
+        }
+        node.children.iter().find_map(|child| find(child, id))
+    }
+
+    fn collect(node: &CompartmentNode, targets: &mut Vec<CompartmentTarget>) {
+        targets.push(CompartmentTarget {
+            id: node.id.clone(),
+            path: node.path.clone(),
+        });
+        for child in &node.children {
+            collect(child, targets);
+        }
+    }
+
+    let node = find(tree, compartment_id)?;
+    let mut targets = Vec::new();
+    if include_subtree {
+        collect(node, &mut targets);
+    } else {
+        targets.push(CompartmentTarget {
+            id: node.id.clone(),
+            path: node.path.clone(),
+        });
+    }
+    Some(targets)
+}
+
+/// 一覧を取得するリージョンとコンパートメントの組
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListTarget {
+    pub region: String,
+    pub compartment: CompartmentTarget,
+}
+
+/// リージョン・コンパートメントごとに `list` を最大 `concurrency` 件ずつ並行して呼び出し、結果をまとめる
+///
+/// `list` にはリージョンを書き換えたプロファイルとコンパートメント OCID を渡す。
+/// 失敗した組は `failures` に記録し、他の結果は返す。
+/// 結果は `targets` の順に並べ、`max_items` を指定した場合は合計件数で打ち切る。
+pub async fn fan_out<T, F, Fut>(
+    profile: &OciProfile,
+    targets: Vec<ListTarget>,
+    concurrency: usize,
+    max_items: Option<usize>,
+    list: F,
+) -> CompartmentListing<T>
+where
+    F: Fn(OciProfile, String) -> Fut,
+    Fut: Future<Output = Result<Vec<T>, OciError>>,
+{
+    let results: Vec<(ListTarget, Result<Vec<T>, OciError>)> = stream::iter(targets)
+        .map(|target| {
+            let regional = OciProfile {
+                region: target.region.clone(),
+                ..profile.clone()
+            };
+            let request = list(regional, target.compartment.id.clone());
+            async move { (target, request.await) }
+        })
+        .buffered(concurrency.max(1))
+        .collect()
+        .await;
+
+    let mut listing = CompartmentListing {
+        items: Vec::new(),
+        failures: Vec::new(),
+    };
+    for (target, result) in results {
+        match result {
+            Ok(items) => listing
+                .items
+                .extend(items.into_iter().map(|item| CompartmentItem {
+                    item,
+                    region: target.region.clone(),
+                    compartment_id: target.compartment.id.clone(),
+                    compartment_path: target.compartment.path.clone(),
+                })),
+            Err(error) => listing.failures.push(CompartmentFailure {
+                region: target.region,
+                compartment_id: target.compartment.id,
+                compartment_path: target.compartment.path,
+                error,
+            }),
+        }
+    }
+    if let Some(max) = max_items {
+        listing.items.truncate(max);
+    }
+    listing
+}
+
+/// 一覧を取得する範囲
+#[derive(Debug, Clone, Default)]
+pub struct ListScope {
+    /// 起点のコンパートメント OCID
+    pub compartment_id: String,
+    /// 子孫のコンパートメントも含めるか
+    pub include_subtree: bool,
+    /// 対象のリージョン（空の場合はプロファイルのリージョン）
+    pub regions: Vec<String>,
+}
+
+/// 指定範囲のリージョン・コンパートメントのリソース一覧を取得
+///
+/// `list` は [`fan_out`] と同じ。対象が 1 つだけの場合はエラーをそのまま返す。
+/// 単一のコンパートメントのパスはキャッシュ済みのツリーから求める
+/// （コンパートメント一覧の権限がなくても使えるよう、ツリーは取得しない）。
+pub async fn list_in_compartments<T, F, Fut>(
+    client: &OciClient,
+    cache: &CompartmentCache,
+    profile: &OciProfile,
+    scope: &ListScope,
+    max_items: Option<usize>,
+    list: F,
+) -> Result<CompartmentListing<T>, OciError>
+where
+    F: Fn(OciProfile, String) -> Fut,
+    Fut: Future<Output = Result<Vec<T>, OciError>>,
+{
+    let compartment_id = scope.compartment_id.as_str();
+    let compartments = if scope.include_subtree {
+        let tree = compartments::compartment_tree(client, cache, profile, false).await?;
+        targets_in_tree(&tree, compartment_id, true).ok_or_else(|| {
+            OciError::config(format!(
+                "コンパートメント '{}' が見つかりません。",
+                compartment_id
+            ))
+        })?
+    } else {
+        vec![CompartmentTarget {
+            id: compartment_id.to_string(),
+            path: cache
+                .path_of(profile, compartment_id)
+                .unwrap_or_else(|| compartment_id.to_string()),
+        }]
+    };
+
+    let regions = if scope.regions.is_empty() {
+        vec![profile.region.clone()]
+    } else {
+        scope.regions.clone()
+    };
+    let targets: Vec<ListTarget> = regions
+        .iter()
+        .flat_map(|region| {
+            compartments.iter().map(|compartment| ListTarget {
+                region: region.clone(),
+                compartment: compartment.clone(),
+            })
+        })
+        .collect();
+
+    let single = targets.len() == 1;
+    let mut listing = fan_out(profile, targets, DEFAULT_CONCURRENCY, max_items, list).await;
+    if single {
+        if let Some(failure) = listing.failures.pop() {
+            return Err(failure.error);
+        }
+    }
+    Ok(listing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    fn node(id: &str, path: &str, children: Vec<CompartmentNode>) -> CompartmentNode {
+        CompartmentNode {
+            id: id.to_string(),
+            name: path.rsplit('/').next().unwrap_or_default().to_string(),
+            path: path.to_string(),
+            description: None,
+            children,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_collects_results_and_failures() {
+        let tree = node(
+            "root",
+            "root",
+            vec![
+                node(
+                    "prod",
+                    "root/prod",
+                    vec![node("app", "root/prod/app", vec![])],
+                ),
+                node("dev", "root/dev", vec![]),
+            ],
+        );
+        let targets = targets_in_tree(&tree, "prod", true).unwrap();
+        assert_eq!(
+            targets.iter().map(|t| t.id.as_str()).collect::<Vec<_>>(),
+            vec!["prod", "app"]
+        );
+        assert_eq!(targets_in_tree(&tree, "dev", false).unwrap().len(), 1);
+        assert!(targets_in_tree(&tree, "missing", true).is_none());
+
+        let running = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let targets: Vec<ListTarget> = ["ap-tokyo-1", "ap-osaka-1"]
+            .iter()
+            .flat_map(|region| {
+                targets_in_tree(&tree, "root", true)
+                    .unwrap()
+                    .into_iter()
+                    .map(|compartment| ListTarget {
+                        region: region.to_string(),
+                        compartment,
+                    })
+            })
+            .collect();
+        let profile = OciProfile {
+            region: "us-ashburn-1".to_string(),
+            ..Default::default()
+        };
+        let listing = fan_out(&profile, targets, 2, None, |profile, id| {
+            let (running, peak) = (&running, &peak);
+            async move {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+                if id == "dev" && profile.region == "ap-osaka-1" {
+                    Err(OciError::from_response(
+                        404,
+                        r#"{"code":"NotAuthorizedOrNotFound","message":"not authorized"}"#,
+                        None,
+                    ))
+                } else {
+                    Ok(vec![format!("{}-{}", profile.region, id)])
+                }
+            }
+        })
+        .await;
+
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+        let items: Vec<(&str, &str, &str)> = listing
+            .items
+            .iter()
+            .map(|i| {
+                (
+                    i.item.as_str(),
+                    i.region.as_str(),
+                    i.compartment_path.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(items.len(), 7);
+        assert_eq!(items[0], ("ap-tokyo-1-root", "ap-tokyo-1", "root"));
+        assert_eq!(items[2], ("ap-tokyo-1-app", "ap-tokyo-1", "root/prod/app"));
+        assert_eq!(items[3], ("ap-tokyo-1-dev", "ap-tokyo-1", "root/dev"));
+        assert_eq!(items[4], ("ap-osaka-1-root", "ap-osaka-1", "root"));
+        assert_eq!(listing.failures.len(), 1);
+        assert_eq!(listing.failures[0].region, "ap-osaka-1");
+        assert_eq!(listing.failures[0].compartment_path, "root/dev");
+    }
+}
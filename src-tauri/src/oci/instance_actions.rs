@@ -0,0 +1,218 @@
+use reqwest::Method;
+
+use crate::models::compute::{ComputeInstance, InstanceAction};
+use crate::models::profile::OciProfile;
+use crate::oci::client::{self, OciClient, OciRequest};
+use crate::oci::error::OciError;
+use crate::oci::waiter::Poller;
+
+/// InstanceAction API を呼び出し、操作後のインスタンスとワークリクエスト OCID を返す
+pub async fn instance_action(
+    client: &OciClient,
+    profile: &OciProfile,
+    instance_id: &str,
+    action: InstanceAction,
+) -> Result<(ComputeInstance, Option<String>), OciError> {
+    let mut request = OciRequest::new(
+        Method::POST,
+        "iaas",
+        &format!("/20160918/instances/{}", instance_id),
+    );
+    request.query = Some(format!("action={}", action.as_str()));
+
+    let response = client::oci_request(client, profile, request).await?;
+    let instance: ComputeInstance =
+        serde_json::from_str(&response.body).map_err(|e| OciError::parse(e.to_string()))?;
+    let work_request_id = response.header("opc-work-request-id").map(str::to_string);
+    Ok((instance, work_request_id))
+}
+
+/// インスタンスを取得
+pub async fn get_instance(
+    client: &OciClient,
+    profile: &OciProfile,
+    instance_id: &str,
+) -> Result<ComputeInstance, OciError> {
+    let path = format!("/20160918/instances/{}", instance_id);
+    let body = client::oci_get_request(client, profile, "iaas", &path, None).await?;
+    serde_json::from_str(&body).map_err(|e| OciError::parse(e.to_string()))
+}
+
+/// 電源操作が完了する（`action` の完了時の状態になる）まで `poller` の間隔で状態を確認する
+///
+/// `initial_state` は操作直後の状態。再起動は操作前と完了時が同じ RUNNING のため、
+/// 一度 RUNNING 以外になってから RUNNING に戻った時点で完了とする。
+/// 状態を取得するたびに `on_progress` を（完了したかどうかとともに）呼び出す。
+/// 操作前の状態に戻った（起動・停止に失敗した）場合、終了（TERMINATING / TERMINATED）した場合、
+/// タイムアウト・中止した場合はエラー。
+pub async fn wait_for_state<F>(
+    client: &OciClient,
+    profile: &OciProfile,
+    instance_id: &str,
+    action: InstanceAction,
+    initial_state: &str,
+    mut poller: Poller<'_>,
+    on_progress: F,
+) -> Result<ComputeInstance, OciError>
+where
+    F: Fn(&ComputeInstance, bool),
+{
+    let (origin, target) = (action.origin_state(), action.target_state());
+    let is_transition = |state: &str| state != origin && state != target;
+    let mut left_origin = is_transition(initial_state);
+    loop {
+        poller.tick().await?;
+        let instance = get_instance(client, profile, instance_id).await?;
+        let state = instance.lifecycle_state.as_str();
+        left_origin |= is_transition(state);
+
+        let done = state == target && (origin != target || left_origin);
+        on_progress(&instance, done);
+        if done {
+            return Ok(instance);
+        }
+        if matches!(state, "TERMINATING" | "TERMINATED") {
+            return Err(OciError::wait(format!(
+                "インスタンス '{}' は終了しています（状態: {}）。",
+                instance.display_name, state
+            )));
+        }
+        if state == origin && origin != target && left_origin {
+            return Err(OciError::wait(format!(
+                "インスタンス '{}' は {} に戻りました。操作に失敗した可能性があります。",
+                instance.display_name, state
+            )));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oci::test_server::{start_sequential_server, test_profile, MockResponse};
+    use crate::oci::waiter::{profile_for_resource, CancelToken, WaitPolicy};
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    const INSTANCE_ID: &str = "ocid1.instance.oc1.ap-osaka-1.test";
+
+    fn instance(state: &str) -> String {
+        format!(
+            r#"{{"id":"{}","displayName":"web-1","shape":"VM.Standard.E4.Flex","lifecycleState":"{}","availabilityDomain":"AD-1"}}"#,
+            INSTANCE_ID, state
+        )
+    }
+
+    fn fast_policy() -> WaitPolicy {
+        WaitPolicy {
+            initial_interval: Duration::from_millis(10),
+            ..WaitPolicy::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stop_and_wait_until_stopped() {
+        let (url, requests) = start_sequential_server(vec![
+            MockResponse::ok(instance("STOPPING"))
+                .header("opc-work-request-id", "ocid1.workrequest.oc1..stop"),
+            MockResponse::ok(instance("STOPPING")),
+            MockResponse::ok(instance("STOPPED")),
+        ])
+        .await;
+
+        let profile = profile_for_resource(&test_profile("iaas", &url), INSTANCE_ID);
+        assert_eq!(profile.region, "ap-osaka-1");
+
+        let client = OciClient::new();
+        let (stopping, work_request_id) =
+            instance_action(&client, &profile, INSTANCE_ID, InstanceAction::Stop)
+                .await
+                .unwrap();
+        assert_eq!(stopping.lifecycle_state, "STOPPING");
+        assert_eq!(
+            work_request_id.as_deref(),
+            Some("ocid1.workrequest.oc1..stop")
+        );
+
+        let seen = Mutex::new(Vec::new());
+        let cancel = CancelToken::default();
+        let stopped = wait_for_state(
+            &client,
+            &profile,
+            INSTANCE_ID,
+            InstanceAction::Stop,
+            &stopping.lifecycle_state,
+            Poller::new(fast_policy(), &cancel),
+            |instance, _| seen.lock().unwrap().push(instance.lifecycle_state.clone()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(stopped.lifecycle_state, "STOPPED");
+        assert_eq!(*seen.lock().unwrap(), vec!["STOPPING", "STOPPED"]);
+
+        let requests = requests.lock().unwrap();
+        assert!(requests[0].starts_with(&format!(
+            "POST /20160918/instances/{}?action=STOP ",
+            INSTANCE_ID
+        )));
+        assert!(requests[1].starts_with("GET /20160918/instances/"));
+    }
+
+    #[tokio::test]
+    async fn test_reset_waits_for_restart() {
+        let (url, _) = start_sequential_server(vec![
+            MockResponse::ok(instance("RUNNING")),
+            MockResponse::ok(instance("STOPPING")),
+            MockResponse::ok(instance("RUNNING")),
+        ])
+        .await;
+        let profile = test_profile("iaas", &url);
+        let cancel = CancelToken::default();
+        let seen = Mutex::new(Vec::new());
+
+        let running = wait_for_state(
+            &OciClient::new(),
+            &profile,
+            INSTANCE_ID,
+            InstanceAction::Reset,
+            "RUNNING",
+            Poller::new(fast_policy(), &cancel),
+            |instance, done| {
+                seen.lock()
+                    .unwrap()
+                    .push((instance.lifecycle_state.clone(), done))
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(running.lifecycle_state, "RUNNING");
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![
+                ("RUNNING".to_string(), false),
+                ("STOPPING".to_string(), false),
+                ("RUNNING".to_string(), true),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stop_fails_when_instance_returns_to_running() {
+        let (url, _) = start_sequential_server(vec![MockResponse::ok(instance("RUNNING"))]).await;
+        let profile = test_profile("iaas", &url);
+        let cancel = CancelToken::default();
+
+        let error = wait_for_state(
+            &OciClient::new(),
+            &profile,
+            INSTANCE_ID,
+            InstanceAction::Stop,
+            "STOPPING",
+            Poller::new(fast_policy(), &cancel),
+            |_, _| {},
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(error.kind(), "wait");
+    }
+}
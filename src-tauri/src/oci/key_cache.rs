@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::fs;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use rsa::RsaPrivateKey;
+
+use crate::models::profile::OciProfile;
+use crate::oci::error::OciError;
+use crate::oci::signature::load_private_key;
+
+/// キャッシュ済みの秘密鍵と、読み込み時点の鍵ファイルの状態
+struct CachedKey {
+    key_file: String,
+    modified: Option<SystemTime>,
+    len: u64,
+    key: Arc<RsaPrivateKey>,
+}
+
+/// プロファイルごとの解析済み秘密鍵キャッシュ
+///
+/// 鍵ファイルのパス・更新日時・サイズのいずれかが変わった場合は読み込み直す。
+/// 対話的に入力されたパスフレーズは鍵ファイルごとにメモリ上にのみ保持する。
+#[derive(Default)]
+pub struct KeyCache {
+    entries: Mutex<HashMap<String, CachedKey>>,
+    session_passphrases: Mutex<HashMap<String, String>>,
+}
+
+impl KeyCache {
+    /// プロファイルの秘密鍵を取得（未キャッシュまたは変更時は読み込み直す）
+    pub fn get(&self, profile: &OciProfile) -> Result<Arc<RsaPrivateKey>, OciError> {
+        let metadata = fs::metadata(&profile.key_file).map_err(|e| {
+            OciError::auth(format!("秘密鍵ファイルの読み込みに失敗しました: {}", e))
+        })?;
+        let modified = metadata.modified().ok();
+        let len = metadata.len();
+
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(cached) = entries.get(&profile.name) {
+            if cached.key_file == profile.key_file
+                && cached.modified == modified
+                && cached.len == len
+            {
+                return Ok(cached.key.clone());
+            }
+        }
+
+        let key = Arc::new(self.load_key(profile)?);
+        entries.insert(
+            profile.name.clone(),
+            CachedKey {
+                key_file: profile.key_file.clone(),
+                modified,
+                len,
+                key: key.clone(),
+            },
+        );
+        Ok(key)
+    }
+
+    /// セッション中のみ有効なパスフレーズを登録（鍵を復号できることを確認してから保持する）
+    pub fn set_session_passphrase(
+        &self,
+        profile: &OciProfile,
+        passphrase: &str,
+    ) -> Result<(), OciError> {
+        load_private_key(&profile.key_file, Some(passphrase))?;
+        self.session_passphrases
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(profile.key_file.clone(), passphrase.to_string());
+        Ok(())
+    }
+
+    /// 秘密鍵を読み込む
+    ///
+    /// セッション中に入力されたパスフレーズ（復号を確認済み）を優先し、復号できなければ
+    /// 設定ファイルの pass_phrase を使う。設定ファイルの値が古くても入力し直した値で読み込める。
+    fn load_key(&self, profile: &OciProfile) -> Result<RsaPrivateKey, OciError> {
+        let session_passphrase = self
+            .session_passphrases
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&profile.key_file)
+            .cloned();
+        if let Some(passphrase) = session_passphrase {
+            if let Ok(key) = load_private_key(&profile.key_file, Some(&passphrase)) {
+                return Ok(key);
+            }
+        }
+        load_private_key(&profile.key_file, profile.pass_phrase.as_deref())
+    }
+
+    /// すべてのキャッシュを破棄（設定ファイル変更時）
+    pub fn clear(&self) {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENCRYPTED_KEY: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/keys/pkcs8_encrypted.pem"
+    );
+
+    #[test]
+    fn test_session_passphrase_is_used_when_config_passphrase_is_wrong() {
+        let profile = OciProfile {
+            name: "ENCRYPTED".to_string(),
+            key_file: ENCRYPTED_KEY.to_string(),
+            pass_phrase: Some("outdated".to_string()),
+            ..Default::default()
+        };
+        let cache = KeyCache::default();
+        assert_eq!(cache.get(&profile).unwrap_err().kind(), "auth");
+
+        cache.set_session_passphrase(&profile, "secret").unwrap();
+        assert!(cache.get(&profile).is_ok());
+    }
+}
@@ -1,5 +1,25 @@
 // OCI 関連モジュール
+pub mod api_keys;
+pub mod auth_provider;
 pub mod client;
+pub mod compartments;
+pub mod config_backup;
+pub mod config_document;
 pub mod config_parser;
+pub mod config_watcher;
+pub mod error;
+pub mod fan_out;
+pub mod instance_actions;
+pub mod key_cache;
+pub mod principals;
+pub mod profile_bundle;
+pub mod region_catalog;
 pub mod regions;
+pub mod retry;
+pub mod session;
 pub mod signature;
+pub mod sso;
+#[cfg(test)]
+pub mod test_server;
+pub mod waiter;
+pub mod work_requests;
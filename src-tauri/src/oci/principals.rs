@@ -0,0 +1,509 @@
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use reqwest::Method;
+use rsa::pkcs8::EncodePublicKey;
+use rsa::RsaPrivateKey;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use x509_cert::der::DecodePem;
+use x509_cert::Certificate;
+
+use crate::models::profile::OciProfile;
+use crate::oci::auth_provider::AuthProvider;
+use crate::oci::client::{send_signed_request, OciClient, OciRequest};
+use crate::oci::error::OciError;
+use crate::oci::retry::{send_with_retry, RetryPolicy};
+use crate::oci::session::{expires_soon, session_key_id, token_expiry};
+use crate::oci::signature::{parse_private_key, SigningCredentials};
+
+/// インスタンス・メタデータ・サービスの既定のベース URL
+const DEFAULT_METADATA_BASE_URL: &str = "http://169.254.169.254/opc/v2";
+
+/// メタデータ・サービスのベース URL を上書きする環境変数（OCI SDK と共通）
+const METADATA_BASE_URL_ENV: &str = "OCI_METADATA_BASE_URL";
+
+/// メタデータ・サービスへのリクエストのタイムアウト（リンクローカルのため短くてよい）
+const METADATA_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// メタデータ・サービスの再試行ポリシー
+///
+/// OCI の外ではメタデータ・サービスに接続できないため、既定のポリシーで待たずにすぐ失敗させる。
+const METADATA_RETRY_POLICY: RetryPolicy = RetryPolicy {
+    max_attempts: 3,
+    base_delay: Duration::from_millis(500),
+    max_delay: Duration::from_secs(2),
+};
+
+/// リソース・プリンシパルのリージョンを指定する環境変数
+const RESOURCE_PRINCIPAL_REGION_ENV: &str = "OCI_RESOURCE_PRINCIPAL_REGION";
+
+/// フェデレーション API のパス
+const FEDERATION_PATH: &str = "/v1/x509";
+
+/// フェデレーションで登録するセッション鍵の長さ
+const SESSION_KEY_BITS: usize = 2048;
+
+/// 有効期限を読み取れないトークンを使い続ける時間
+const UNKNOWN_EXPIRY_LIFETIME: Duration = Duration::from_secs(10 * 60);
+
+/// フェデレーションまたはリソース・プリンシパルで得たトークンとセッション鍵
+#[derive(Clone)]
+struct PrincipalSession {
+    token: String,
+    private_key: Arc<RsaPrivateKey>,
+    fetched_at: Instant,
+}
+
+impl PrincipalSession {
+    fn new(token: String, private_key: RsaPrivateKey) -> Self {
+        Self {
+            token,
+            private_key: Arc::new(private_key),
+            fetched_at: Instant::now(),
+        }
+    }
+
+    /// 取得し直す必要があるかどうか（有効期限を読み取れない場合は一定時間で取得し直す）
+    fn needs_refresh(&self) -> bool {
+        match token_expiry(&self.token) {
+            Some(_) => expires_soon(&self.token),
+            None => self.fetched_at.elapsed() >= UNKNOWN_EXPIRY_LIFETIME,
+        }
+    }
+
+    fn credentials(&self) -> SigningCredentials {
+        SigningCredentials {
+            key_id: session_key_id(&self.token),
+            private_key: self.private_key.clone(),
+        }
+    }
+}
+
+/// フェデレーション API のレスポンス
+#[derive(Debug, Deserialize)]
+struct FederationResponse {
+    token: String,
+}
+
+/// インスタンス・プリンシパル認証
+///
+/// メタデータ・サービスからインスタンスの証明書と秘密鍵を取得し、フェデレーション API で
+/// セッション鍵に対するセキュリティトークンと交換する。トークンの有効期限が近づいたら取得し直す。
+pub struct InstancePrincipalAuth {
+    metadata_base_url: String,
+    session_key_bits: usize,
+    session: tokio::sync::Mutex<Option<PrincipalSession>>,
+}
+
+impl InstancePrincipalAuth {
+    /// メタデータ・サービスのベース URL（例: "http://169.254.169.254/opc/v2"）を指定して作成
+    pub fn new(metadata_base_url: impl Into<String>) -> Self {
+        Self {
+            metadata_base_url: metadata_base_url.into().trim_end_matches('/').to_string(),
+            session_key_bits: SESSION_KEY_BITS,
+            session: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    /// 環境変数 OCI_METADATA_BASE_URL（未設定時は既定の URL）から作成
+    pub fn from_env() -> Self {
+        Self::new(
+            std::env::var(METADATA_BASE_URL_ENV)
+                .unwrap_or_else(|_| DEFAULT_METADATA_BASE_URL.to_string()),
+        )
+    }
+
+    /// メタデータ・サービスから取得する
+    async fn fetch_metadata(&self, client: &OciClient, path: &str) -> Result<String, OciError> {
+        let url = format!("{}{}", self.metadata_base_url, path);
+        let response = send_with_retry(&METADATA_RETRY_POLICY, || {
+            Ok(client
+                .http
+                .get(&url)
+                .timeout(METADATA_TIMEOUT)
+                .header("Authorization", "Bearer Oracle"))
+        })
+        .await?;
+
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| OciError::transport(e.to_string()))?;
+        if !status.is_success() {
+            return Err(OciError::auth(format!(
+                "インスタンス・メタデータ（{}）の取得に失敗しました（ステータス: {}）。OCI のインスタンス上で実行しているか確認してください。",
+                path,
+                status.as_u16()
+            )));
+        }
+        Ok(body)
+    }
+
+    /// 証明書をフェデレーション API でセキュリティトークンと交換する
+    async fn federate(
+        &self,
+        client: &OciClient,
+        profile: &OciProfile,
+    ) -> Result<PrincipalSession, OciError> {
+        let leaf_pem = self.fetch_metadata(client, "/identity/cert.pem").await?;
+        let leaf_key_pem = self.fetch_metadata(client, "/identity/key.pem").await?;
+        let intermediate_pem = self
+            .fetch_metadata(client, "/identity/intermediate.pem")
+            .await?;
+
+        let leaf_der = pem_body_der(&leaf_pem)?;
+        let tenancy = certificate_tenancy(&leaf_pem)?;
+        let leaf_key = parse_private_key(&leaf_key_pem, None, "インスタンス証明書の秘密鍵")?;
+
+        let bits = self.session_key_bits;
+        let session_key =
+            tokio::task::spawn_blocking(move || RsaPrivateKey::new(&mut rand::thread_rng(), bits))
+                .await
+                .map_err(|e| OciError::auth(e.to_string()))?
+                .map_err(|e| OciError::auth(format!("鍵ペアの生成に失敗しました: {}", e)))?;
+        let session_public_der = session_key
+            .to_public_key()
+            .to_public_key_der()
+            .map_err(|e| OciError::auth(format!("公開鍵のエンコードに失敗しました: {}", e)))?;
+
+        let request =
+            OciRequest::new(Method::POST, "auth", FEDERATION_PATH).json(&serde_json::json!({
+                "certificate": BASE64.encode(&leaf_der),
+                "publicKey": BASE64.encode(session_public_der.as_bytes()),
+                "intermediateCertificates": [BASE64.encode(pem_body_der(&intermediate_pem)?)],
+                "purpose": "DEFAULT",
+                "fingerprintAlgorithm": "SHA256",
+            }))?;
+        let credentials = SigningCredentials {
+            key_id: format!(
+                "{}/fed-x509-sha256/{}",
+                tenancy,
+                sha256_fingerprint(&leaf_der)
+            ),
+            private_key: Arc::new(leaf_key),
+        };
+
+        let response =
+            send_signed_request(client, profile, request, &credentials, &client.retry_policy)
+                .await?;
+        let federation: FederationResponse =
+            serde_json::from_str(&response.body).map_err(|e| OciError::parse(e.to_string()))?;
+
+        Ok(PrincipalSession::new(federation.token, session_key))
+    }
+}
+
+#[async_trait]
+impl AuthProvider for InstancePrincipalAuth {
+    async fn signing_credentials(
+        &self,
+        client: &OciClient,
+        profile: &OciProfile,
+    ) -> Result<SigningCredentials, OciError> {
+        let mut session = self.session.lock().await;
+        if let Some(current) = session.as_ref().filter(|s| !s.needs_refresh()) {
+            return Ok(current.credentials());
+        }
+
+        let federated = self.federate(client, profile).await?;
+        let credentials = federated.credentials();
+        *session = Some(federated);
+        Ok(credentials)
+    }
+}
+
+/// 環境変数の参照方法（テストで差し替えるため）
+type EnvLookup = Box<dyn Fn(&str) -> Option<String> + Send + Sync>;
+
+/// リソース・プリンシパル認証（OCI_RESOURCE_PRINCIPAL_* 環境変数、バージョン 2.2）
+///
+/// トークン（RPST）と秘密鍵は値そのもの、または絶対パスで指定される。パスの場合は
+/// 有効期限が近づくたびに読み込み直す（プラットフォーム側で更新されるため）。
+pub struct ResourcePrincipalAuth {
+    lookup: EnvLookup,
+    session: tokio::sync::Mutex<Option<PrincipalSession>>,
+}
+
+impl Default for ResourcePrincipalAuth {
+    fn default() -> Self {
+        Self::with_lookup(|name| std::env::var(name).ok())
+    }
+}
+
+impl ResourcePrincipalAuth {
+    /// 環境変数の参照方法を指定して作成
+    pub fn with_lookup(lookup: impl Fn(&str) -> Option<String> + Send + Sync + 'static) -> Self {
+        Self {
+            lookup: Box::new(lookup),
+            session: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    /// 環境変数の値（絶対パスの場合はファイルの内容）を取得
+    fn read_value(&self, name: &str) -> Result<Option<String>, OciError> {
+        let Some(value) = (self.lookup)(name).filter(|v| !v.trim().is_empty()) else {
+            return Ok(None);
+        };
+        if !Path::new(&value).is_absolute() {
+            return Ok(Some(value));
+        }
+        fs::read_to_string(&value)
+            .map(|content| Some(content.trim().to_string()))
+            .map_err(|e| {
+                OciError::auth(format!(
+                    "{} のファイル '{}' の読み込みに失敗しました: {}",
+                    name, value, e
+                ))
+            })
+    }
+
+    fn load(&self) -> Result<PrincipalSession, OciError> {
+        let missing =
+            |name: &str| OciError::config(format!("環境変数 {} が設定されていません。", name));
+
+        let version = (self.lookup)("OCI_RESOURCE_PRINCIPAL_VERSION")
+            .ok_or_else(|| missing("OCI_RESOURCE_PRINCIPAL_VERSION"))?;
+        if version.trim() != "2.2" {
+            return Err(OciError::config(format!(
+                "リソース・プリンシパルのバージョン '{}' には対応していません。（対応: 2.2）",
+                version
+            )));
+        }
+
+        let token = self
+            .read_value("OCI_RESOURCE_PRINCIPAL_RPST")?
+            .ok_or_else(|| missing("OCI_RESOURCE_PRINCIPAL_RPST"))?;
+        let pem = self
+            .read_value("OCI_RESOURCE_PRINCIPAL_PRIVATE_PEM")?
+            .ok_or_else(|| missing("OCI_RESOURCE_PRINCIPAL_PRIVATE_PEM"))?;
+        let passphrase = self.read_value("OCI_RESOURCE_PRINCIPAL_PRIVATE_PEM_PASSPHRASE")?;
+        let private_key = parse_private_key(
+            &pem,
+            passphrase.as_deref(),
+            "OCI_RESOURCE_PRINCIPAL_PRIVATE_PEM",
+        )?;
+
+        Ok(PrincipalSession::new(token, private_key))
+    }
+}
+
+#[async_trait]
+impl AuthProvider for ResourcePrincipalAuth {
+    async fn signing_credentials(
+        &self,
+        _client: &OciClient,
+        _profile: &OciProfile,
+    ) -> Result<SigningCredentials, OciError> {
+        let mut session = self.session.lock().await;
+        if let Some(current) = session.as_ref().filter(|s| !s.needs_refresh()) {
+            return Ok(current.credentials());
+        }
+
+        let loaded = self.load()?;
+        let credentials = loaded.credentials();
+        *session = Some(loaded);
+        Ok(credentials)
+    }
+}
+
+/// 環境変数 OCI_RESOURCE_PRINCIPAL_REGION で指定されたリソース・プリンシパルのリージョン
+pub fn resource_principal_region() -> Option<String> {
+    resource_principal_region_from(|name| std::env::var(name).ok())
+}
+
+/// 環境変数の参照方法を指定してリソース・プリンシパルのリージョンを取得
+fn resource_principal_region_from(lookup: impl Fn(&str) -> Option<String>) -> Option<String> {
+    lookup(RESOURCE_PRINCIPAL_REGION_ENV)
+        .map(|region| region.trim().to_string())
+        .filter(|region| !region.is_empty())
+}
+
+/// PEM の本文を DER にデコード
+fn pem_body_der(pem: &str) -> Result<Vec<u8>, OciError> {
+    let body: String = pem
+        .lines()
+        .map(str::trim)
+        .skip_while(|l| !l.starts_with("-----BEGIN"))
+        .skip(1)
+        .take_while(|l| !l.starts_with("-----END"))
+        .collect();
+    BASE64
+        .decode(body)
+        .map_err(|e| OciError::parse(format!("証明書の解析に失敗しました: {}", e)))
+}
+
+/// 証明書のサブジェクト（OU=opc-tenant:<OCID>）からテナンシー OCID を取得
+fn certificate_tenancy(pem: &str) -> Result<String, OciError> {
+    let certificate = Certificate::from_pem(pem.as_bytes())
+        .map_err(|e| OciError::parse(format!("証明書の解析に失敗しました: {}", e)))?;
+    let subject = certificate.tbs_certificate.subject.to_string();
+
+    ["opc-tenant:", "opc-identity:"]
+        .iter()
+        .find_map(|prefix| {
+            subject.split([',', '+']).find_map(|part| {
+                part.trim()
+                    .split_once('=')
+                    .and_then(|(_, value)| value.strip_prefix(prefix))
+                    .map(|ocid| ocid.to_string())
+            })
+        })
+        .ok_or_else(|| OciError::parse("証明書にテナンシー OCID が含まれていません。"))
+}
+
+/// 証明書（DER）の SHA-256 フィンガープリント（コロン区切りの大文字 16 進数）
+fn sha256_fingerprint(der: &[u8]) -> String {
+    Sha256::digest(der)
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oci::test_server::{start_routed_server, start_sequential_server, MockResponse};
+    use std::collections::HashMap;
+
+    const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+    fn fixture(name: &str) -> String {
+        fs::read_to_string(format!("{}/{}", FIXTURES, name)).unwrap()
+    }
+
+    #[test]
+    fn test_certificate_tenancy_reads_subject() {
+        assert_eq!(
+            certificate_tenancy(&fixture("instance_principal/cert.pem")).unwrap(),
+            "ocid1.tenancy.oc1..test"
+        );
+    }
+
+    #[test]
+    fn test_session_without_expiry_is_refreshed_after_lifetime() {
+        let pem = fixture("keys/plain_pkcs1.pem");
+        let key = parse_private_key(&pem, None, "test").unwrap();
+        let mut session = PrincipalSession::new("not-a-jwt".to_string(), key);
+        assert!(!session.needs_refresh());
+
+        session.fetched_at = Instant::now() - UNKNOWN_EXPIRY_LIFETIME;
+        assert!(session.needs_refresh());
+    }
+
+    #[tokio::test]
+    async fn test_instance_principal_federates_with_metadata_certificate() {
+        let routes = HashMap::from([
+            (
+                "/opc/v2/identity/cert.pem",
+                fixture("instance_principal/cert.pem"),
+            ),
+            (
+                "/opc/v2/identity/key.pem",
+                fixture("instance_principal/key.pem"),
+            ),
+            (
+                "/opc/v2/identity/intermediate.pem",
+                fixture("instance_principal/intermediate.pem"),
+            ),
+            ("/v1/x509", r#"{"token":"federated-token"}"#.to_string()),
+        ]);
+        // メタデータ・サービスとフェデレーション API を兼ねる
+        let (url, requests) = start_routed_server(routes).await;
+
+        let mut auth = InstancePrincipalAuth::new(format!("{}/opc/v2", url));
+        // テストを速くするため短い鍵を使う
+        auth.session_key_bits = 1024;
+        let mut profile = OciProfile {
+            region: "ap-tokyo-1".to_string(),
+            ..Default::default()
+        };
+        profile.service_endpoints.insert("auth".to_string(), url);
+
+        let client = OciClient::new();
+        let credentials = auth.signing_credentials(&client, &profile).await.unwrap();
+        assert_eq!(credentials.key_id, "ST$federated-token");
+
+        // 2 回目はキャッシュしたトークンを使う
+        auth.signing_credentials(&client, &profile).await.unwrap();
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 4);
+        assert!(requests[..3]
+            .iter()
+            .all(|r| r.to_lowercase().contains("authorization: bearer oracle")));
+        let federation = &requests[3];
+        assert!(federation.starts_with("POST /v1/x509 "));
+        assert!(federation
+            .contains("keyId=\"ocid1.tenancy.oc1..test/fed-x509-sha256/4C:F3:2B:36:1A:8E:3E:A0"));
+        assert!(federation.contains("\"purpose\":\"DEFAULT\""));
+    }
+
+    #[tokio::test]
+    async fn test_resource_principal_reads_env_values_and_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let rpst_file = dir.path().join("rpst");
+        fs::write(&rpst_file, "resource-token\n").unwrap();
+
+        let vars = HashMap::from([
+            ("OCI_RESOURCE_PRINCIPAL_VERSION", "2.2".to_string()),
+            (
+                "OCI_RESOURCE_PRINCIPAL_RPST",
+                rpst_file.to_string_lossy().to_string(),
+            ),
+            (
+                "OCI_RESOURCE_PRINCIPAL_PRIVATE_PEM",
+                fixture("keys/plain_pkcs1.pem"),
+            ),
+        ]);
+        let auth = ResourcePrincipalAuth::with_lookup(move |name| vars.get(name).cloned());
+
+        let credentials = auth
+            .signing_credentials(&OciClient::new(), &OciProfile::default())
+            .await
+            .unwrap();
+        assert_eq!(credentials.key_id, "ST$resource-token");
+
+        let missing = ResourcePrincipalAuth::with_lookup(|_| None);
+        assert!(matches!(
+            missing
+                .signing_credentials(&OciClient::new(), &OciProfile::default())
+                .await,
+            Err(OciError::Config { .. })
+        ));
+    }
+
+    #[test]
+    fn test_resource_principal_region_from_env() {
+        let region = resource_principal_region_from(|name| {
+            (name == "OCI_RESOURCE_PRINCIPAL_REGION").then(|| " ap-osaka-1\n".to_string())
+        });
+        assert_eq!(region.as_deref(), Some("ap-osaka-1"));
+        assert_eq!(
+            resource_principal_region_from(|_| Some(" ".to_string())),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_metadata_uses_short_retry_policy() {
+        let (url, requests) = start_sequential_server(vec![MockResponse::status(503); 5]).await;
+        let auth = InstancePrincipalAuth::new(format!("{}/opc/v2", url));
+
+        let started = Instant::now();
+        let error = auth
+            .fetch_metadata(&OciClient::new(), "/identity/cert.pem")
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), "auth");
+        assert_eq!(requests.lock().unwrap().len(), 3);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
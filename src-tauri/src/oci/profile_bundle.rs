@@ -0,0 +1,537 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use crate::models::profile::{ImportedProfile, OciProfile};
+use crate::oci::api_keys::write_private_file;
+use crate::oci::config_parser;
+
+/// バンドルの形式名（暗号化の追加認証データにも使う）
+const BUNDLE_FORMAT: &str = "oci-desktop-profile-bundle";
+
+/// バンドルの形式のバージョン
+const BUNDLE_VERSION: u32 = 1;
+
+/// パスフレーズの最小文字数
+pub const MIN_PASSPHRASE_LEN: usize = 8;
+
+/// インポートした鍵・トークンを保存するディレクトリ（設定ファイルと同じディレクトリに作成）
+const IMPORT_DIR_NAME: &str = "imported";
+
+/// パスフレーズから鍵を導出する Argon2id のパラメータ
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KdfParams {
+    /// メモリコスト（KiB）
+    pub memory_kib: u32,
+    /// 反復回数
+    pub iterations: u32,
+    /// 並列度
+    pub parallelism: u32,
+}
+
+/// インポート時に受け付ける鍵導出パラメータの上限（細工したバンドルでメモリや CPU を使い切らないように）
+const MAX_KDF: KdfParams = KdfParams {
+    memory_kib: 256 * 1024,
+    iterations: 10,
+    parallelism: 4,
+};
+
+impl Default for KdfParams {
+    /// OWASP の推奨値（Argon2id, 64 MiB, 3 回）
+    fn default() -> Self {
+        Self {
+            memory_kib: 64 * 1024,
+            iterations: 3,
+            parallelism: 1,
+        }
+    }
+}
+
+/// バンドルファイルの内容（ペイロードは XChaCha20-Poly1305 で暗号化）
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BundleEnvelope {
+    format: String,
+    version: u32,
+    kdf: KdfParams,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// 暗号化される内容
+#[derive(Debug, Serialize, Deserialize)]
+struct BundlePayload {
+    profiles: Vec<BundledProfile>,
+}
+
+/// プロファイルと、その鍵・トークンファイル
+#[derive(Debug, Serialize, Deserialize)]
+struct BundledProfile {
+    profile: OciProfile,
+    /// 秘密鍵のパスフレーズ（`OciProfile` はパスフレーズをシリアライズしないため別に持つ）
+    #[serde(default)]
+    pass_phrase: Option<String>,
+    key: Option<BundledFile>,
+    security_token: Option<BundledFile>,
+}
+
+/// バンドルに含めるファイル
+#[derive(Debug, Serialize, Deserialize)]
+struct BundledFile {
+    file_name: String,
+    /// ファイルの内容（Base64）
+    contents: String,
+}
+
+impl BundledFile {
+    fn read(path: &str) -> Result<Self, String> {
+        let path = Path::new(path);
+        let contents = fs::read(path).map_err(|e| {
+            format!(
+                "ファイル '{}' の読み込みに失敗しました: {}",
+                path.display(),
+                e
+            )
+        })?;
+        Ok(Self {
+            file_name: path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "file".to_string()),
+            contents: BASE64.encode(contents),
+        })
+    }
+
+    /// `dir` に書き込み、書き込んだパスを返す
+    fn write(&self, dir: &Path, default_name: &str) -> Result<PathBuf, String> {
+        // バンドル内のファイル名はパスとして解釈しない
+        let file_name = Path::new(&self.file_name)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .filter(|n| !n.starts_with('.'))
+            .unwrap_or_else(|| default_name.to_string());
+        let contents = BASE64
+            .decode(&self.contents)
+            .map_err(|e| format!("バンドルの内容が不正です: {}", e))?;
+
+        let path = dir.join(file_name);
+        write_private_file(&path, &contents).map_err(|e| e.to_string())?;
+        Ok(path)
+    }
+}
+
+/// 設定ファイルのプロファイルを、鍵・トークンファイルとともに暗号化したバンドルに書き出す
+pub fn export_bundle(
+    config_path: &Path,
+    names: &[String],
+    passphrase: &str,
+    output: &Path,
+    kdf: KdfParams,
+) -> Result<(), String> {
+    if names.is_empty() {
+        return Err("エクスポートするプロファイルを選択してください。".to_string());
+    }
+    if passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+        return Err(format!(
+            "パスフレーズは {} 文字以上にしてください。",
+            MIN_PASSPHRASE_LEN
+        ));
+    }
+
+    let profiles = config_parser::parse_config(config_path)?;
+    let bundled = names
+        .iter()
+        .map(|name| {
+            let profile = profiles
+                .iter()
+                .find(|p| &p.name == name)
+                .cloned()
+                .ok_or_else(|| format!("プロファイル '{}' が見つかりません。", name))?;
+            let key = (!profile.key_file.is_empty())
+                .then(|| BundledFile::read(&profile.key_file))
+                .transpose()?;
+            let security_token = profile
+                .security_token_file
+                .as_deref()
+                .map(BundledFile::read)
+                .transpose()?;
+            Ok(BundledProfile {
+                pass_phrase: profile.pass_phrase.clone(),
+                profile,
+                key,
+                security_token,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let payload =
+        serde_json::to_vec(&BundlePayload { profiles: bundled }).map_err(|e| e.to_string())?;
+    let envelope = seal(&payload, passphrase, kdf)?;
+    let contents = serde_json::to_vec_pretty(&envelope).map_err(|e| e.to_string())?;
+    write_private_file(output, &contents).map_err(|e| e.to_string())
+}
+
+/// バンドルを復号し、プロファイルを設定ファイルに追加する
+///
+/// 鍵・トークンは `<設定ディレクトリ>/imported/<プロファイル名>/` に保存し、パスを書き換える。
+/// 同名のプロファイルがある場合は `<名前>_2` のように名前を変えて追加する。
+/// エンドポイントの上書きは署名付きリクエストの送信先を変えられるため、`allow_endpoints` を
+/// 指定した場合のみ取り込む。
+pub fn import_bundle(
+    config_path: &Path,
+    bundle_path: &Path,
+    passphrase: &str,
+    allow_endpoints: bool,
+) -> Result<Vec<ImportedProfile>, String> {
+    let contents =
+        fs::read(bundle_path).map_err(|e| format!("バンドルの読み込みに失敗しました: {}", e))?;
+    let envelope: BundleEnvelope = serde_json::from_slice(&contents)
+        .map_err(|_| "プロファイルバンドルの形式ではありません。".to_string())?;
+    let payload: BundlePayload =
+        serde_json::from_slice(&open(&envelope, passphrase)?).map_err(|e| e.to_string())?;
+
+    let mut taken: HashSet<String> = if config_path.exists() {
+        config_parser::read_document(config_path)?
+            .section_names()
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    } else {
+        HashSet::new()
+    };
+    let import_dir = config_path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join(IMPORT_DIR_NAME);
+
+    let mut imported = Vec::new();
+    let mut profiles = Vec::new();
+    for bundled in payload.profiles {
+        let original_name = bundled.profile.name.clone();
+        config_parser::check_profile_name(&original_name)?;
+        let name = unique_name(&original_name, &taken);
+        taken.insert(name.clone());
+
+        let mut profile = bundled.profile;
+        profile.name = name.clone();
+        profile.pass_phrase = bundled.pass_phrase.or(profile.pass_phrase);
+        check_profile_values(&profile)?;
+        let has_endpoints = profile.endpoint.is_some() || !profile.service_endpoints.is_empty();
+        let dropped_endpoints = has_endpoints && !allow_endpoints;
+        if dropped_endpoints {
+            profile.endpoint = None;
+            profile.service_endpoints.clear();
+        }
+        let dir = import_dir.join(&name);
+        if !dir.starts_with(&import_dir) {
+            return Err(format!(
+                "プロファイル名 '{}' は使用できません。",
+                original_name
+            ));
+        }
+        if let Some(key) = &bundled.key {
+            fs::create_dir_all(&dir)
+                .map_err(|e| format!("ディレクトリの作成に失敗しました: {}", e))?;
+            profile.key_file = key
+                .write(&dir, "oci_api_key.pem")?
+                .to_string_lossy()
+                .to_string();
+        }
+        if let Some(token) = &bundled.security_token {
+            fs::create_dir_all(&dir)
+                .map_err(|e| format!("ディレクトリの作成に失敗しました: {}", e))?;
+            profile.security_token_file =
+                Some(token.write(&dir, "token")?.to_string_lossy().to_string());
+        }
+
+        imported.push(ImportedProfile {
+            name,
+            original_name,
+            dropped_endpoints,
+        });
+        profiles.push(profile);
+    }
+
+    config_parser::write_config(config_path, &profiles)?;
+    Ok(imported)
+}
+
+/// バンドル内のプロファイルの値に制御文字が含まれていないか確認する
+///
+/// 改行を含む値は設定ファイルに別の行を差し込めてしまうため、バンドル全体を拒否する。
+fn check_profile_values(profile: &OciProfile) -> Result<(), String> {
+    let values = [
+        &profile.user,
+        &profile.tenancy,
+        &profile.region,
+        &profile.fingerprint,
+        &profile.key_file,
+    ]
+    .into_iter()
+    .chain(&profile.pass_phrase)
+    .chain(&profile.security_token_file)
+    .chain(&profile.endpoint)
+    .chain(profile.service_endpoints.keys())
+    .chain(profile.service_endpoints.values());
+    for value in values {
+        if value.chars().any(char::is_control) {
+            return Err(format!(
+                "バンドルのプロファイル '{}' に使用できない値が含まれています: {:?}",
+                profile.name, value
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// 既存の名前と重ならない名前（`<名前>_2`, `<名前>_3`, ...）
+fn unique_name(name: &str, taken: &HashSet<String>) -> String {
+    if !taken.contains(name) {
+        return name.to_string();
+    }
+    (2..)
+        .map(|n| format!("{}_{}", name, n))
+        .find(|candidate| !taken.contains(candidate))
+        .unwrap_or_default()
+}
+
+/// パスフレーズから 256 ビットの鍵を導出
+fn derive_key(passphrase: &str, salt: &[u8], kdf: KdfParams) -> Result<[u8; 32], String> {
+    let params = Params::new(kdf.memory_kib, kdf.iterations, kdf.parallelism, Some(32))
+        .map_err(|e| format!("鍵導出のパラメータが不正です: {}", e))?;
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("鍵の導出に失敗しました: {}", e))?;
+    Ok(key)
+}
+
+/// ペイロードを暗号化
+fn seal(payload: &[u8], passphrase: &str, kdf: KdfParams) -> Result<BundleEnvelope, String> {
+    let mut salt = [0u8; 16];
+    let mut nonce = [0u8; 24];
+    rand::thread_rng().fill_bytes(&mut salt);
+    rand::thread_rng().fill_bytes(&mut nonce);
+
+    let key = derive_key(passphrase, &salt, kdf)?;
+    let ciphertext = XChaCha20Poly1305::new(&key.into())
+        .encrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: payload,
+                aad: BUNDLE_FORMAT.as_bytes(),
+            },
+        )
+        .map_err(|_| "バンドルの暗号化に失敗しました。".to_string())?;
+
+    Ok(BundleEnvelope {
+        format: BUNDLE_FORMAT.to_string(),
+        version: BUNDLE_VERSION,
+        kdf,
+        salt: BASE64.encode(salt),
+        nonce: BASE64.encode(nonce),
+        ciphertext: BASE64.encode(ciphertext),
+    })
+}
+
+/// ペイロードを復号（パスフレーズの誤りや改ざんはエラーになる）
+fn open(envelope: &BundleEnvelope, passphrase: &str) -> Result<Vec<u8>, String> {
+    if envelope.format != BUNDLE_FORMAT {
+        return Err("プロファイルバンドルの形式ではありません。".to_string());
+    }
+    if envelope.version != BUNDLE_VERSION {
+        return Err(format!(
+            "対応していないバンドルのバージョンです。（バージョン: {}）",
+            envelope.version
+        ));
+    }
+
+    let decode = |value: &str| {
+        BASE64
+            .decode(value)
+            .map_err(|e| format!("バンドルの内容が不正です: {}", e))
+    };
+    let kdf = envelope.kdf;
+    if kdf.memory_kib > MAX_KDF.memory_kib
+        || kdf.iterations > MAX_KDF.iterations
+        || kdf.parallelism > MAX_KDF.parallelism
+    {
+        return Err("バンドルの鍵導出パラメータが上限を超えています。".to_string());
+    }
+
+    let salt = decode(&envelope.salt)?;
+    let nonce = decode(&envelope.nonce)?;
+    let ciphertext = decode(&envelope.ciphertext)?;
+    if nonce.len() != 24 {
+        return Err("バンドルの内容が不正です。".to_string());
+    }
+
+    let key = derive_key(passphrase, &salt, kdf)?;
+    XChaCha20Poly1305::new(&key.into())
+        .decrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: &ciphertext,
+                aad: BUNDLE_FORMAT.as_bytes(),
+            },
+        )
+        .map_err(|_| "パスフレーズが正しくないか、バンドルが破損しています。".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// テストでは鍵導出を軽くする
+    const TEST_KDF: KdfParams = KdfParams {
+        memory_kib: 1024,
+        iterations: 1,
+        parallelism: 1,
+    };
+
+    #[test]
+    fn test_export_and_import_bundle() {
+        let source = tempfile::tempdir().unwrap();
+        let source_config = source.path().join("config");
+        fs::write(source.path().join("key.pem"), "PRIVATE KEY").unwrap();
+        fs::write(
+            &source_config,
+            "[DEFAULT]\nuser=ocid1.user.oc1..aaaa\nfingerprint=aa:bb\ntenancy=ocid1.tenancy.oc1..aaaa\nregion=ap-tokyo-1\nkey_file=key.pem\npass_phrase=secret\n",
+        )
+        .unwrap();
+
+        let bundle = source.path().join("profiles.ocibundle");
+        export_bundle(
+            &source_config,
+            &["DEFAULT".to_string()],
+            "correct horse",
+            &bundle,
+            TEST_KDF,
+        )
+        .unwrap();
+        let raw = fs::read_to_string(&bundle).unwrap();
+        assert!(!raw.contains("PRIVATE KEY"));
+        assert!(!raw.contains("ocid1.user"));
+
+        let target = tempfile::tempdir().unwrap();
+        let target_config = target.path().join("config");
+        fs::write(
+            &target_config,
+            "# 既存の設定\n[DEFAULT]\nregion=us-ashburn-1\n",
+        )
+        .unwrap();
+
+        assert!(import_bundle(&target_config, &bundle, "wrong passphrase", false).is_err());
+
+        let imported = import_bundle(&target_config, &bundle, "correct horse", false).unwrap();
+        assert_eq!(imported.len(), 1);
+        assert_eq!(imported[0].original_name, "DEFAULT");
+        assert_eq!(imported[0].name, "DEFAULT_2");
+
+        let profiles = config_parser::parse_config(&target_config).unwrap();
+        let profile = profiles.iter().find(|p| p.name == "DEFAULT_2").unwrap();
+        assert_eq!(profile.region, "ap-tokyo-1");
+        assert_eq!(profile.pass_phrase.as_deref(), Some("secret"));
+        let key_file = Path::new(&profile.key_file);
+        assert!(key_file.starts_with(target.path().join("imported").join("DEFAULT_2")));
+        assert_eq!(fs::read_to_string(key_file).unwrap(), "PRIVATE KEY");
+        assert!(fs::read_to_string(&target_config)
+            .unwrap()
+            .starts_with("# 既存の設定\n[DEFAULT]\nregion=us-ashburn-1\n"));
+    }
+
+    #[test]
+    fn test_import_rejects_oversized_kdf() {
+        let dir = tempfile::tempdir().unwrap();
+        let bundle = dir.path().join("profiles.ocibundle");
+        let mut envelope = seal(b"{\"profiles\":[]}", "correct horse", TEST_KDF).unwrap();
+        envelope.kdf.memory_kib = 4 * 1024 * 1024;
+        fs::write(&bundle, serde_json::to_vec(&envelope).unwrap()).unwrap();
+
+        let error =
+            import_bundle(&dir.path().join("config"), &bundle, "correct horse", false).unwrap_err();
+        assert!(error.contains("上限"));
+
+        envelope.kdf = KdfParams {
+            iterations: 1_000_000_000,
+            ..TEST_KDF
+        };
+        fs::write(&bundle, serde_json::to_vec(&envelope).unwrap()).unwrap();
+        assert!(
+            import_bundle(&dir.path().join("config"), &bundle, "correct horse", false).is_err()
+        );
+    }
+
+    /// 鍵ファイルを含まないプロファイルだけのバンドルを書き出す
+    fn write_bundle(path: &Path, profiles: Vec<OciProfile>) {
+        let payload = BundlePayload {
+            profiles: profiles
+                .into_iter()
+                .map(|profile| BundledProfile {
+                    profile,
+                    pass_phrase: None,
+                    key: None,
+                    security_token: None,
+                })
+                .collect(),
+        };
+        let envelope = seal(
+            &serde_json::to_vec(&payload).unwrap(),
+            "correct horse",
+            TEST_KDF,
+        )
+        .unwrap();
+        fs::write(path, serde_json::to_vec(&envelope).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_import_drops_endpoints_and_rejects_control_characters() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("config");
+        let bundle = dir.path().join("profiles.ocibundle");
+        let mut profile = OciProfile {
+            name: "REDIRECTED".to_string(),
+            user: "ocid1.user.oc1..aaaa".to_string(),
+            tenancy: "ocid1.tenancy.oc1..aaaa".to_string(),
+            region: "ap-tokyo-1".to_string(),
+            fingerprint: "aa:bb".to_string(),
+            key_file: "/keys/key.pem".to_string(),
+            endpoint: Some("https://attacker.example".to_string()),
+            ..Default::default()
+        };
+        profile
+            .service_endpoints
+            .insert("iaas".to_string(), "https://attacker.example".to_string());
+        write_bundle(&bundle, vec![profile.clone()]);
+
+        let imported = import_bundle(&config, &bundle, "correct horse", false).unwrap();
+        assert!(imported[0].dropped_endpoints);
+        let saved = config_parser::parse_config(&config).unwrap();
+        assert_eq!(saved[0].endpoint, None);
+        assert!(saved[0].service_endpoints.is_empty());
+
+        let imported = import_bundle(&config, &bundle, "correct horse", true).unwrap();
+        assert!(!imported[0].dropped_endpoints);
+        let saved = config_parser::parse_config(&config).unwrap();
+        assert_eq!(
+            saved[1].endpoint.as_deref(),
+            Some("https://attacker.example")
+        );
+
+        profile.region = "ap-tokyo-1\nkey_file=/tmp/evil.pem".to_string();
+        write_bundle(&bundle, vec![profile]);
+        let before = fs::read_to_string(&config).unwrap();
+        assert!(import_bundle(&config, &bundle, "correct horse", true).is_err());
+        assert_eq!(fs::read_to_string(&config).unwrap(), before);
+    }
+}
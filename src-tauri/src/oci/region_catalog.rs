@@ -0,0 +1,361 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::models::profile::OciProfile;
+use crate::oci::client::{self, OciClient};
+use crate::oci::error::OciError;
+use crate::oci::regions::{get_available_regions, normalize_region, OciRegion};
+
+/// キャッシュを有効とみなす期間
+const CACHE_TTL_HOURS: i64 = 24;
+
+/// キャッシュディレクトリ内のリージョン情報の保存先
+const CACHE_DIR_NAME: &str = "regions";
+
+/// ListRegions の要素
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegionInfo {
+    /// リージョンキー（例: "NRT"）
+    pub key: String,
+    /// リージョンコード（例: "ap-tokyo-1"）
+    pub name: String,
+}
+
+/// ListRegionSubscriptions の要素
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegionSubscription {
+    pub region_key: String,
+    pub region_name: String,
+    /// "READY" または "IN_PROGRESS"
+    pub status: String,
+    pub is_home_region: bool,
+}
+
+/// テナンシごとにディスクに保存するリージョン情報
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegionCatalogCache {
+    /// 取得日時（RFC 3339）
+    pub fetched_at: String,
+    pub regions: Vec<RegionInfo>,
+    pub subscriptions: Vec<RegionSubscription>,
+}
+
+impl RegionCatalogCache {
+    fn is_fresh(&self) -> bool {
+        DateTime::parse_from_rfc3339(&self.fetched_at)
+            .is_ok_and(|fetched| Utc::now() - fetched.to_utc() < Duration::hours(CACHE_TTL_HOURS))
+    }
+}
+
+/// テナンシのキャッシュファイルのパス
+fn cache_path(cache_dir: &Path, tenancy: &str) -> PathBuf {
+    // OCID は英数字・ドット・アンダースコアのみだが、念のためファイル名に使えない文字を置き換える
+    let name: String = tenancy
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let name = if name.is_empty() {
+        "default".to_string()
+    } else {
+        name
+    };
+    cache_dir
+        .join(CACHE_DIR_NAME)
+        .join(format!("{}.json", name))
+}
+
+/// キャッシュを読み込む（存在しない・壊れている場合は None）
+pub fn load_cache(cache_dir: &Path, tenancy: &str) -> Option<RegionCatalogCache> {
+    let contents = fs::read(cache_path(cache_dir, tenancy)).ok()?;
+    serde_json::from_slice(&contents).ok()
+}
+
+/// キャッシュを保存する
+pub fn save_cache(
+    cache_dir: &Path,
+    tenancy: &str,
+    cache: &RegionCatalogCache,
+) -> Result<(), String> {
+    let path = cache_path(cache_dir, tenancy);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("キャッシュディレクトリの作成に失敗しました: {}", e))?;
+    }
+    let contents = serde_json::to_vec_pretty(cache).map_err(|e| e.to_string())?;
+    fs::write(&path, contents)
+        .map_err(|e| format!("リージョン情報のキャッシュの保存に失敗しました: {}", e))
+}
+
+/// Identity API からリージョン一覧とテナンシのサブスクリプションを取得
+async fn fetch_catalog(
+    client: &OciClient,
+    profile: &OciProfile,
+) -> Result<RegionCatalogCache, OciError> {
+    let body =
+        client::oci_get_request(client, profile, "identity", "/20160918/regions", None).await?;
+    let regions: Vec<RegionInfo> =
+        serde_json::from_str(&body).map_err(|e| OciError::parse(e.to_string()))?;
+
+    // インスタンスプリンシパルなどテナンシが設定にない場合はサブスクリプションを取得しない
+    let subscriptions = if profile.tenancy.is_empty() {
+        Vec::new()
+    } else {
+        let path = format!(
+            "/20160918/tenancies/{}/regionSubscriptions",
+            profile.tenancy
+        );
+        let body = client::oci_get_request(client, profile, "identity", &path, None).await?;
+        serde_json::from_str(&body).map_err(|e| OciError::parse(e.to_string()))?
+    };
+
+    Ok(RegionCatalogCache {
+        fetched_at: Utc::now().to_rfc3339(),
+        regions,
+        subscriptions,
+    })
+}
+
+/// プロファイルのテナンシで利用できるリージョン一覧
+///
+/// ディスクのキャッシュが有効な間は API を呼ばない（`refresh` で再取得）。取得に失敗した場合は
+/// エラーを返す（組み込みの一覧は `get_available_regions` で取得できる）。
+pub async fn region_catalog(
+    client: &OciClient,
+    profile: &OciProfile,
+    cache_dir: &Path,
+    refresh: bool,
+) -> Result<Vec<OciRegion>, OciError> {
+    if let Some(cache) =
+        load_cache(cache_dir, &profile.tenancy).filter(|c| !refresh && c.is_fresh())
+    {
+        return Ok(merge_regions(&cache.regions, &cache.subscriptions));
+    }
+
+    let cache = fetch_catalog(client, profile).await?;
+    // 保存できなくても取得した一覧は使える（次回また取得する）
+    if let Err(e) = save_cache(cache_dir, &profile.tenancy, &cache) {
+        log::warn!("{}", e);
+    }
+    Ok(merge_regions(&cache.regions, &cache.subscriptions))
+}
+
+/// 一覧を取得するリージョンの指定
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum RegionScope {
+    /// プロファイルのリージョン
+    #[default]
+    Profile,
+    /// 指定したリージョン（リージョンキーも可）
+    Regions { regions: Vec<String> },
+    /// テナンシがサブスクライブしているすべてのリージョン
+    Subscribed,
+}
+
+/// リージョンの指定を、重複のないリージョンコードの一覧にする
+pub async fn resolve_regions(
+    client: &OciClient,
+    profile: &OciProfile,
+    cache_dir: &Path,
+    scope: &RegionScope,
+) -> Result<Vec<String>, OciError> {
+    let regions: Vec<String> = match scope {
+        RegionScope::Profile => vec![profile.region.clone()],
+        RegionScope::Regions { regions } => regions
+            .iter()
+            .map(|r| normalize_region(r.trim()))
+            .filter(|r| !r.is_empty())
+            .collect(),
+        RegionScope::Subscribed => region_catalog(client, profile, cache_dir, false)
+            .await?
+            .into_iter()
+            .filter(|r| r.subscribed == Some(true))
+            .map(|r| r.code)
+            .collect(),
+    };
+
+    let mut unique: Vec<String> = Vec::new();
+    for region in regions {
+        if !unique.contains(&region) {
+            unique.push(region);
+        }
+    }
+    if unique.is_empty() {
+        return Err(OciError::config(match scope {
+            RegionScope::Subscribed => "サブスクライブしているリージョンを取得できません。",
+            _ => "リージョンを指定してください。",
+        }));
+    }
+    Ok(unique)
+}
+
+/// 組み込みの一覧と API の結果をまとめる
+///
+/// ホームリージョン、サブスクライブ済みのリージョン、それ以外の順に並べる。
+/// サブスクリプションが取得できていない場合は `subscribed` を None のままにする。
+pub fn merge_regions(
+    regions: &[RegionInfo],
+    subscriptions: &[RegionSubscription],
+) -> Vec<OciRegion> {
+    let mut merged = get_available_regions();
+    for region in regions {
+        match merged.iter_mut().find(|r| r.code == region.name) {
+            Some(existing) => existing.key = region.key.clone(),
+            None => merged.push(OciRegion {
+                code: region.name.clone(),
+                key: region.key.clone(),
+                display_name: region.name.clone(),
+                subscribed: None,
+                is_home_region: false,
+            }),
+        }
+    }
+    // ListRegions にないリージョン（別レルムなど）のサブスクリプションも表示する
+    for subscription in subscriptions {
+        if !merged.iter().any(|r| r.code == subscription.region_name) {
+            merged.push(OciRegion {
+                code: subscription.region_name.clone(),
+                key: subscription.region_key.clone(),
+                display_name: subscription.region_name.clone(),
+                subscribed: None,
+                is_home_region: false,
+            });
+        }
+    }
+
+    if !subscriptions.is_empty() {
+        for region in &mut merged {
+            let subscription = subscriptions.iter().find(|s| s.region_name == region.code);
+            region.subscribed = Some(subscription.is_some_and(|s| s.status == "READY"));
+            region.is_home_region = subscription.is_some_and(|s| s.is_home_region);
+        }
+    }
+
+    // 同じ順位の中では組み込みの一覧の順序を保つ
+    merged.sort_by_key(|r| (!r.is_home_region, r.subscribed != Some(true)));
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oci::test_server::{start_sequential_server, test_profile, MockResponse};
+
+    fn subscription(name: &str, key: &str, home: bool) -> RegionSubscription {
+        RegionSubscription {
+            region_key: key.to_string(),
+            region_name: name.to_string(),
+            status: "READY".to_string(),
+            is_home_region: home,
+        }
+    }
+
+    #[test]
+    fn test_merge_regions_orders_subscribed_first() {
+        let regions = vec![
+            RegionInfo {
+                key: "NRT".to_string(),
+                name: "ap-tokyo-1".to_string(),
+            },
+            RegionInfo {
+                key: "XYZ".to_string(),
+                name: "ap-newregion-1".to_string(),
+            },
+        ];
+        let subscriptions = vec![
+            subscription("us-ashburn-1", "IAD", false),
+            subscription("ap-osaka-1", "KIX", true),
+        ];
+
+        let merged = merge_regions(&regions, &subscriptions);
+        let codes: Vec<&str> = merged.iter().take(2).map(|r| r.code.as_str()).collect();
+        assert_eq!(codes, vec!["ap-osaka-1", "us-ashburn-1"]);
+        assert!(merged[0].is_home_region);
+        assert_eq!(merged[2].code, "ap-tokyo-1");
+        assert_eq!(merged[2].subscribed, Some(false));
+
+        let new_region = merged.iter().find(|r| r.code == "ap-newregion-1").unwrap();
+        assert_eq!(new_region.key, "XYZ");
+        assert_eq!(new_region.display_name, "ap-newregion-1");
+
+        // サブスクリプションが不明な場合は組み込みの順序のまま
+        let merged = merge_regions(&regions, &[]);
+        assert_eq!(merged[0].code, "ap-tokyo-1");
+        assert_eq!(merged[0].subscribed, None);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_regions_normalizes_keys() {
+        let client = OciClient::default();
+        let profile = OciProfile {
+            region: "ap-tokyo-1".to_string(),
+            ..Default::default()
+        };
+        let dir = tempfile::tempdir().unwrap();
+
+        let scope = RegionScope::Regions {
+            regions: vec![
+                "kix".to_string(),
+                "ap-osaka-1".to_string(),
+                "us-ashburn-1".to_string(),
+            ],
+        };
+        let regions = resolve_regions(&client, &profile, dir.path(), &scope)
+            .await
+            .unwrap();
+        assert_eq!(regions, vec!["ap-osaka-1", "us-ashburn-1"]);
+
+        let regions = resolve_regions(&client, &profile, dir.path(), &RegionScope::Profile)
+            .await
+            .unwrap();
+        assert_eq!(regions, vec!["ap-tokyo-1"]);
+
+        let empty = RegionScope::Regions { regions: vec![] };
+        assert!(resolve_regions(&client, &profile, dir.path(), &empty)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_region_catalog_reports_fetch_failure() {
+        let (url, _) = start_sequential_server(vec![MockResponse::status(401)]).await;
+        let profile = test_profile("identity", &url);
+        let dir = tempfile::tempdir().unwrap();
+
+        let error = region_catalog(&OciClient::new(), &profile, dir.path(), true)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, OciError::Service { status: 401, .. }));
+        assert!(load_cache(dir.path(), &profile.tenancy).is_none());
+    }
+
+    #[test]
+    fn test_cache_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let tenancy = "ocid1.tenancy.oc1..aaaa";
+        assert!(load_cache(dir.path(), tenancy).is_none());
+
+        let cache = RegionCatalogCache {
+            fetched_at: Utc::now().to_rfc3339(),
+            regions: Vec::new(),
+            subscriptions: vec![subscription("ap-tokyo-1", "NRT", true)],
+        };
+        save_cache(dir.path(), tenancy, &cache).unwrap();
+
+        let loaded = load_cache(dir.path(), tenancy).unwrap();
+        assert!(loaded.is_fresh());
+        assert_eq!(loaded.subscriptions, cache.subscriptions);
+        assert!(load_cache(dir.path(), "ocid1.tenancy.oc1..bbbb").is_none());
+    }
+}
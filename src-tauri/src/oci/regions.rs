@@ -1,56 +1,379 @@
+use std::sync::OnceLock;
+
 use serde::{Deserialize, Serialize};
 
+use crate::models::profile::OciProfile;
+
 /// OCI リージョン情報
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct OciRegion {
     /// リージョンコード（例: "ap-tokyo-1"）
     pub code: String,
-    /// 表示名（日本語）
+    /// リージョンキー（短縮コード。例: "NRT"）
+    pub key: String,
+    /// 表示名（日本語。組み込みの一覧にない場合はリージョンコード）
     pub display_name: String,
+    /// テナンシがサブスクライブしているか（不明な場合は None）
+    pub subscribed: Option<bool>,
+    /// ホームリージョンかどうか
+    pub is_home_region: bool,
 }
 
-/// 利用可能な OCI リージョン一覧を取得
+/// 組み込みのリージョン一覧（リージョンコード, リージョンキー, 表示名）
+const BUILTIN_REGIONS: &[(&str, &str, &str)] = &[
+    // アジア太平洋
+    ("ap-tokyo-1", "NRT", "東京"),
+    ("ap-osaka-1", "KIX", "大阪"),
+    ("ap-seoul-1", "ICN", "ソウル"),
+    ("ap-chuncheon-1", "YNY", "春川"),
+    ("ap-singapore-1", "SIN", "シンガポール"),
+    ("ap-singapore-2", "XSP", "シンガポール西"),
+    ("ap-batam-1", "HSG", "バタム"),
+    ("ap-mumbai-1", "BOM", "ムンバイ"),
+    ("ap-hyderabad-1", "HYD", "ハイデラバード"),
+    ("ap-sydney-1", "SYD", "シドニー"),
+    ("ap-melbourne-1", "MEL", "メルボルン"),
+    // 北米
+    ("us-ashburn-1", "IAD", "アッシュバーン"),
+    ("us-phoenix-1", "PHX", "フェニックス"),
+    ("us-sanjose-1", "SJC", "サンノゼ"),
+    ("us-chicago-1", "ORD", "シカゴ"),
+    ("ca-toronto-1", "YYZ", "トロント"),
+    ("ca-montreal-1", "YUL", "モントリオール"),
+    ("mx-queretaro-1", "QRO", "ケレタロ"),
+    ("mx-monterrey-1", "MTY", "モンテレイ"),
+    // ヨーロッパ
+    ("eu-frankfurt-1", "FRA", "フランクフルト"),
+    ("eu-amsterdam-1", "AMS", "アムステルダム"),
+    ("eu-zurich-1", "ZRH", "チューリッヒ"),
+    ("eu-stockholm-1", "ARN", "ストックホルム"),
+    ("eu-madrid-1", "MAD", "マドリード"),
+    ("eu-marseille-1", "MRS", "マルセイユ"),
+    ("eu-milan-1", "LIN", "ミラノ"),
+    ("eu-paris-1", "CDG", "パリ"),
+    ("uk-london-1", "LHR", "ロンドン"),
+    ("uk-cardiff-1", "CWL", "カーディフ"),
+    // 中東・アフリカ
+    ("me-jeddah-1", "JED", "ジェッダ"),
+    ("me-riyadh-1", "RUH", "リヤド"),
+    ("me-dubai-1", "DXB", "ドバイ"),
+    ("me-abudhabi-1", "AUH", "アブダビ"),
+    ("il-jerusalem-1", "MTZ", "エルサレム"),
+    ("af-johannesburg-1", "JNB", "ヨハネスブルグ"),
+    // 南米
+    ("sa-saopaulo-1", "GRU", "サンパウロ"),
+    ("sa-vinhedo-1", "VCP", "ヴィニェード"),
+    ("sa-santiago-1", "SCL", "サンティアゴ"),
+    ("sa-valparaiso-1", "VAP", "バルパライソ"),
+    ("sa-bogota-1", "BOG", "ボゴタ"),
+];
+
+/// 組み込みの OCI リージョン一覧を取得（サブスクリプションは不明）
 pub fn get_available_regions() -> Vec<OciRegion> {
-    vec![
-        // アジア太平洋
-        OciRegion { code: "ap-tokyo-1".into(), display_name: "東京".into() },
-        OciRegion { code: "ap-osaka-1".into(), display_name: "大阪".into() },
-        OciRegion { code: "ap-seoul-1".into(), display_name: "ソウル".into() },
-        OciRegion { code: "ap-singapore-1".into(), display_name: "シンガポール".into() },
-        OciRegion { code: "ap-mumbai-1".into(), display_name: "ムンバイ".into() },
-        OciRegion { code: "ap-sydney-1".into(), display_name: "シドニー".into() },
-        OciRegion { code: "ap-melbourne-1".into(), display_name: "メルボルン".into() },
-        OciRegion { code: "ap-hyderabad-1".into(), display_name: "ハイデラバード".into() },
-        OciRegion { code: "ap-chuncheon-1".into(), display_name: "春川".into() },
-        // 北米
-        OciRegion { code: "us-ashburn-1".into(), display_name: "アッシュバーン".into() },
-        OciRegion { code: "us-phoenix-1".into(), display_name: "フェニックス".into() },
-        OciRegion { code: "us-sanjose-1".into(), display_name: "サンノゼ".into() },
-        OciRegion { code: "us-chicago-1".into(), display_name: "シカゴ".into() },
-        OciRegion { code: "ca-toronto-1".into(), display_name: "トロント".into() },
-        OciRegion { code: "ca-montreal-1".into(), display_name: "モントリオール".into() },
-        // ヨーロッパ
-        OciRegion { code: "eu-frankfurt-1".into(), display_name: "フランクフルト".into() },
-        OciRegion { code: "eu-amsterdam-1".into(), display_name: "アムステルダム".into() },
-        OciRegion { code: "eu-zurich-1".into(), display_name: "チューリッヒ".into() },
-        OciRegion { code: "eu-stockholm-1".into(), display_name: "ストックホルム".into() },
-        OciRegion { code: "eu-madrid-1".into(), display_name: "マドリード".into() },
-        OciRegion { code: "eu-marseille-1".into(), display_name: "マルセイユ".into() },
-        OciRegion { code: "eu-milan-1".into(), display_name: "ミラノ".into() },
-        OciRegion { code: "eu-paris-1".into(), display_name: "パリ".into() },
-        OciRegion { code: "uk-london-1".into(), display_name: "ロンドン".into() },
-        OciRegion { code: "uk-cardiff-1".into(), display_name: "カーディフ".into() },
-        // 中東・アフリカ
-        OciRegion { code: "me-jeddah-1".into(), display_name: "ジェッダ".into() },
-        OciRegion { code: "me-dubai-1".into(), display_name: "ドバイ".into() },
-        OciRegion { code: "af-johannesburg-1".into(), display_name: "ヨハネスブルグ".into() },
-        // 南米
-        OciRegion { code: "sa-saopaulo-1".into(), display_name: "サンパウロ".into() },
-        OciRegion { code: "sa-vinhedo-1".into(), display_name: "ヴィニェード".into() },
-    ]
+    BUILTIN_REGIONS
+        .iter()
+        .map(|(code, key, display_name)| OciRegion {
+            code: code.to_string(),
+            key: key.to_string(),
+            display_name: display_name.to_string(),
+            subscribed: None,
+            is_home_region: false,
+        })
+        .collect()
+}
+
+/// リージョンキー（"NRT" など。大文字小文字は区別しない）をリージョンコードに変換する
+///
+/// OCI CLI と同様に、設定ファイルの region にはリージョンキーも指定できる。
+/// 該当しない値はそのまま返す。
+pub fn normalize_region(region: &str) -> String {
+    BUILTIN_REGIONS
+        .iter()
+        .find(|(_, key, _)| key.eq_ignore_ascii_case(region))
+        .map(|(code, _, _)| code.to_string())
+        .unwrap_or_else(|| region.to_string())
+}
+
+/// OCID に含まれるリージョン（`ocid1.<種類>.<レルム>.<リージョン>.<ID>`）
+///
+/// 古い OCID はリージョンキー（"phx" など）を含むため、リージョンコードに変換する。
+/// テナンシなどリージョンを含まない OCID は None。
+pub fn region_of_ocid(ocid: &str) -> Option<String> {
+    let region = ocid.split('.').nth(3).filter(|r| !r.is_empty())?;
+    Some(normalize_region(region))
+}
+
+/// 既定のレルム（商用リージョン）のドメイン
+const DEFAULT_REALM_DOMAIN: &str = "oraclecloud.com";
+
+/// レルムキーと第 2 レベルドメインの対応
+const REALM_DOMAINS: &[(&str, &str)] = &[
+    ("oc1", "oraclecloud.com"),
+    ("oc2", "oraclegovcloud.com"),
+    ("oc3", "oraclegovcloud.com"),
+    ("oc4", "oraclegovcloud.uk"),
+    ("oc8", "oraclecloud8.com"),
+    ("oc9", "oraclecloud9.com"),
+    ("oc10", "oraclecloud10.com"),
+    ("oc14", "oraclecloud14.com"),
+    ("oc15", "oraclecloud15.com"),
+    ("oc16", "oraclecloud16.com"),
+    ("oc19", "oraclecloud.eu"),
+    ("oc20", "oraclecloud20.com"),
+];
+
+/// 商用レルム（oc1）以外のリージョンとレルムキーの対応
+const REGION_REALMS: &[(&str, &str)] = &[
+    ("us-langley-1", "oc2"),
+    ("us-luke-1", "oc2"),
+    ("us-gov-ashburn-1", "oc3"),
+    ("us-gov-chicago-1", "oc3"),
+    ("us-gov-phoenix-1", "oc3"),
+    ("uk-gov-london-1", "oc4"),
+    ("uk-gov-cardiff-1", "oc4"),
+    ("ap-chiyoda-1", "oc8"),
+    ("ap-ibaraki-1", "oc8"),
+    ("me-dcc-muscat-1", "oc9"),
+    ("ap-dcc-canberra-1", "oc10"),
+    ("eu-dcc-milan-1", "oc14"),
+    ("eu-dcc-milan-2", "oc14"),
+    ("eu-dcc-dublin-1", "oc14"),
+    ("eu-dcc-dublin-2", "oc14"),
+    ("eu-dcc-rating-1", "oc14"),
+    ("eu-dcc-rating-2", "oc14"),
+    ("ap-dcc-gazipur-1", "oc15"),
+    ("us-westjordan-1", "oc16"),
+    ("eu-frankfurt-2", "oc19"),
+    ("eu-madrid-2", "oc19"),
+    ("eu-jovanovac-1", "oc20"),
+];
+
+/// リージョンのレルム情報（`OCI_REGION_METADATA` / regions-config.json と同じ形式）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegionMetadata {
+    /// レルムキー（例: "OC1"）
+    pub realm_key: String,
+    /// レルムのドメイン（例: "oraclecloud.com"）
+    pub realm_domain_component: String,
+    /// リージョンキー（例: "NRT"）
+    pub region_key: String,
+    /// リージョン識別子（例: "ap-tokyo-1"）
+    pub region_identifier: String,
+}
+
+/// `OCI_REGION_METADATA` 環境変数と `~/.oci/regions-config.json` から追加のリージョン情報を読み込む
+fn load_external_region_metadata() -> Vec<RegionMetadata> {
+    let mut metadata = Vec::new();
+
+    // 環境変数（単一リージョン）を最優先
+    if let Ok(value) = std::env::var("OCI_REGION_METADATA") {
+        if let Ok(region) = serde_json::from_str::<RegionMetadata>(&value) {
+            metadata.push(region);
+        }
+    }
+
+    // regions-config.json（リージョンの配列）
+    if let Some(home) = dirs::home_dir() {
+        let path = home.join(".oci").join("regions-config.json");
+        if let Ok(content) = std::fs::read_to_string(path) {
+            if let Ok(regions) = serde_json::from_str::<Vec<RegionMetadata>>(&content) {
+                metadata.extend(regions);
+            }
+        }
+    }
+
+    metadata
+}
+
+/// 外部のリージョン情報（プロセス内で一度だけ読み込む）
+fn external_region_metadata() -> &'static [RegionMetadata] {
+    static METADATA: OnceLock<Vec<RegionMetadata>> = OnceLock::new();
+    METADATA.get_or_init(load_external_region_metadata)
+}
+
+/// リージョンが属するレルムのドメインを取得
+pub fn realm_domain(region: &str) -> String {
+    if let Some(metadata) = external_region_metadata()
+        .iter()
+        .find(|m| m.region_identifier == region)
+    {
+        return metadata.realm_domain_component.clone();
+    }
+
+    REGION_REALMS
+        .iter()
+        .find(|(code, _)| *code == region)
+        .and_then(|(_, realm)| REALM_DOMAINS.iter().find(|(key, _)| key == realm))
+        .map(|(_, domain)| domain.to_string())
+        .unwrap_or_else(|| DEFAULT_REALM_DOMAIN.to_string())
 }
 
 /// リージョンコードから API エンドポイントのホスト名を生成
 pub fn region_to_endpoint(region: &str, service: &str) -> String {
-    format!("{}.{}.oraclecloud.com", service, region)
+    format!("{}.{}.{}", service, region, realm_domain(region))
+}
+
+/// API エンドポイント（スキーム・ホスト・ベースパス）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoint {
+    /// "https" または "http"
+    pub scheme: String,
+    /// host ヘッダーに使う値（既定以外のポートを含む）
+    pub host: String,
+    /// パスの前に付けるプレフィックス（通常は空）
+    pub base_path: String,
+}
+
+impl Endpoint {
+    /// リクエストパス（クエリを含む）に対する完全な URL
+    pub fn url(&self, path_and_query: &str) -> String {
+        format!(
+            "{}://{}{}",
+            self.scheme,
+            self.host,
+            self.request_target(path_and_query)
+        )
+    }
+
+    /// 署名対象の (request-target) に使うパス
+    pub fn request_target(&self, path_and_query: &str) -> String {
+        format!("{}{}", self.base_path, path_and_query)
+    }
+}
+
+/// エンドポイント上書き URL を解析
+///
+/// 平文の http はローカルのスタブサーバー向けに loopback アドレスのみ許可する。
+pub fn parse_endpoint_override(value: &str) -> Result<Endpoint, String> {
+    let url = reqwest::Url::parse(value.trim())
+        .map_err(|e| format!("エンドポイント '{}' の形式が無効です: {}", value, e))?;
+    let host = url
+        .host_str()
+        .ok_or_else(|| format!("エンドポイント '{}' にホスト名がありません。", value))?;
+
+    match url.scheme() {
+        "https" => {}
+        "http" if matches!(host, "localhost" | "127.0.0.1" | "[::1]") => {}
+        "http" => {
+            return Err(format!(
+                "エンドポイント '{}': http は localhost のみ使用できます。",
+                value
+            ))
+        }
+        other => {
+            return Err(format!(
+                "エンドポイント '{}': 未対応のスキーム '{}' です。",
+                value, other
+            ))
+        }
+    }
+
+    let host = match url.port() {
+        Some(port) => format!("{}:{}", host, port),
+        None => host.to_string(),
+    };
+
+    Ok(Endpoint {
+        scheme: url.scheme().to_string(),
+        host,
+        base_path: url.path().trim_end_matches('/').to_string(),
+    })
+}
+
+/// プロファイルとサービス名から接続先エンドポイントを解決
+///
+/// 優先順位: サービス別の上書き（`endpoint_<service>`）→ プロファイル共通の上書き（`endpoint`）
+/// → リージョンとレルムから導出したホスト。
+pub fn resolve_endpoint(profile: &OciProfile, service: &str) -> Result<Endpoint, String> {
+    if let Some(value) = profile
+        .service_endpoints
+        .get(service)
+        .or(profile.endpoint.as_ref())
+        .filter(|value| !value.trim().is_empty())
+    {
+        return parse_endpoint_override(value);
+    }
+
+    Ok(Endpoint {
+        scheme: "https".to_string(),
+        host: region_to_endpoint(&normalize_region(&profile.region), service),
+        base_path: String::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_region_to_endpoint_uses_realm_domain() {
+        assert_eq!(
+            region_to_endpoint("ap-tokyo-1", "iaas"),
+            "iaas.ap-tokyo-1.oraclecloud.com"
+        );
+        assert_eq!(
+            region_to_endpoint("us-langley-1", "identity"),
+            "identity.us-langley-1.oraclegovcloud.com"
+        );
+        assert_eq!(
+            region_to_endpoint("uk-gov-london-1", "iaas"),
+            "iaas.uk-gov-london-1.oraclegovcloud.uk"
+        );
+        assert_eq!(
+            region_to_endpoint("eu-frankfurt-2", "iaas"),
+            "iaas.eu-frankfurt-2.oraclecloud.eu"
+        );
+    }
+
+    #[test]
+    fn test_resolve_endpoint_overrides() {
+        let mut profile = OciProfile {
+            region: "ap-tokyo-1".to_string(),
+            endpoint: Some("http://localhost:8080".to_string()),
+            ..Default::default()
+        };
+        profile.service_endpoints.insert(
+            "objectstorage".to_string(),
+            "https://os.example.com/mock/".to_string(),
+        );
+
+        let iaas = resolve_endpoint(&profile, "iaas").unwrap();
+        assert_eq!(
+            iaas.url("/20160918/instances"),
+            "http://localhost:8080/20160918/instances"
+        );
+        assert_eq!(iaas.host, "localhost:8080");
+
+        let os = resolve_endpoint(&profile, "objectstorage").unwrap();
+        assert_eq!(os.host, "os.example.com");
+        assert_eq!(os.request_target("/n/"), "/mock/n/");
+
+        assert!(parse_endpoint_override("http://iaas.example.com").is_err());
+    }
+
+    #[test]
+    fn test_region_of_ocid() {
+        assert_eq!(
+            region_of_ocid("ocid1.instance.oc1.ap-tokyo-1.anxhiljr").as_deref(),
+            Some("ap-tokyo-1")
+        );
+        assert_eq!(
+            region_of_ocid("ocid1.instance.oc1.phx.abyhqljr").as_deref(),
+            Some("us-phoenix-1")
+        );
+        assert_eq!(region_of_ocid("ocid1.tenancy.oc1..aaaa"), None);
+    }
+
+    #[test]
+    fn test_resolve_endpoint_accepts_region_key() {
+        let profile = OciProfile {
+            region: "nrt".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            resolve_endpoint(&profile, "iaas").unwrap().host,
+            "iaas.ap-tokyo-1.oraclecloud.com"
+        );
+    }
 }
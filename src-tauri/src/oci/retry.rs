@@ -0,0 +1,272 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use reqwest::{RequestBuilder, Response, StatusCode};
+
+use crate::oci::error::OciError;
+
+/// 再試行ポリシー（既定値は OCI SDK の既定ポリシーに準拠）
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// 最大試行回数（初回を含む）
+    pub max_attempts: u32,
+    /// バックオフの基準待機時間
+    pub base_delay: Duration,
+    /// 1 回あたりの最大待機時間
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 8,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// 再試行しないポリシー
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// 再試行対象のステータスかどうか（スロットリング・一時的なサーバーエラー）
+    pub fn is_retryable_status(status: StatusCode) -> bool {
+        matches!(status.as_u16(), 429 | 500 | 502 | 503 | 504)
+    }
+
+    /// 再試行対象の通信エラーかどうか（接続失敗・タイムアウト）
+    pub fn is_retryable_error(error: &reqwest::Error) -> bool {
+        error.is_connect() || error.is_timeout()
+    }
+
+    /// `attempt` 回目（1 始まり）の失敗後に待機する時間を計算
+    ///
+    /// フルジッター付き指数バックオフ: `[0, min(max_delay, base_delay * 2^(attempt-1))]` の一様乱数。
+    /// `retry-after` が指定されている場合はその値（`max_delay` で上限）を優先する。
+    pub fn delay_for(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        if let Some(retry_after) = retry_after {
+            return retry_after.min(self.max_delay);
+        }
+
+        let exponent = attempt.saturating_sub(1).min(31);
+        let ceiling = self
+            .base_delay
+            .saturating_mul(1u32 << exponent)
+            .min(self.max_delay);
+        let ceiling_ms = ceiling.as_millis() as u64;
+        if ceiling_ms == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_millis(rand::thread_rng().gen_range(0..=ceiling_ms))
+    }
+}
+
+/// 冪等な再送のための opc-retry-token を生成
+pub fn generate_retry_token() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(32)
+        .map(char::from)
+        .collect()
+}
+
+/// `retry-after` ヘッダーを解析
+fn retry_after(response: &Response) -> Option<Duration> {
+    response
+        .headers()
+        .get("retry-after")
+        .and_then(|v| v.to_str().ok())
+        .and_then(parse_retry_after)
+}
+
+/// `retry-after` の値（秒数または HTTP 日付）を待機時間に変換（過去の日付は 0）
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&Utc) - Utc::now())
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+/// ポリシーに従ってリクエストを送信し、必要に応じて再試行する
+///
+/// `build` は試行ごとに呼ばれる（署名の日時ヘッダーを毎回生成し直すため）。
+/// 再試行回数を使い切った場合は最後のレスポンスをそのまま返す。
+pub async fn send_with_retry<F>(policy: &RetryPolicy, mut build: F) -> Result<Response, OciError>
+where
+    F: FnMut() -> Result<RequestBuilder, OciError>,
+{
+    let max_attempts = policy.max_attempts.max(1);
+    let mut attempt = 1;
+
+    loop {
+        let result = build()?.send().await;
+        let is_last = attempt >= max_attempts;
+
+        let delay = match result {
+            Ok(response) => {
+                if is_last || !RetryPolicy::is_retryable_status(response.status()) {
+                    return Ok(response);
+                }
+                policy.delay_for(attempt, retry_after(&response))
+            }
+            Err(e) => {
+                if is_last || !RetryPolicy::is_retryable_error(&e) {
+                    return Err(OciError::transport(e.to_string()));
+                }
+                policy.delay_for(attempt, None)
+            }
+        };
+
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oci::test_server::{start_sequential_server, MockResponse};
+
+    fn fast_policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_throttling_and_server_errors_until_success() {
+        let (url, requests) = start_sequential_server(vec![
+            MockResponse::status(429).header("retry-after", "0"),
+            MockResponse::status(503),
+            MockResponse::status(200),
+        ])
+        .await;
+
+        let client = reqwest::Client::new();
+        let token = generate_retry_token();
+        let response = send_with_retry(&fast_policy(5), || {
+            Ok(client
+                .post(&url)
+                .header("opc-retry-token", &token)
+                .body("{}"))
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 3);
+        // 再送でも同じ opc-retry-token が送られること
+        let token_header = format!("opc-retry-token: {}", token.to_lowercase());
+        assert!(requests
+            .iter()
+            .all(|r| r.to_lowercase().contains(&token_header)));
+    }
+
+    #[tokio::test]
+    async fn test_stops_after_max_attempts() {
+        let (url, requests) = start_sequential_server(vec![
+            MockResponse::status(500),
+            MockResponse::status(502),
+            MockResponse::status(504),
+        ])
+        .await;
+
+        let client = reqwest::Client::new();
+        let response = send_with_retry(&fast_policy(2), || Ok(client.get(&url)))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_client_errors() {
+        let (url, requests) =
+            start_sequential_server(vec![MockResponse::status(404), MockResponse::status(200)])
+                .await;
+
+        let client = reqwest::Client::new();
+        let response = send_with_retry(&fast_policy(5), || Ok(client.get(&url)))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retries_connection_failures() {
+        // 接続を受け付けないポートに送ると接続エラーになる
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let client = reqwest::Client::new();
+        let mut attempts = 0;
+        let result = send_with_retry(&fast_policy(3), || {
+            attempts += 1;
+            Ok(client.get(&url))
+        })
+        .await;
+
+        assert!(matches!(result, Err(OciError::Transport { .. })));
+        assert_eq!(attempts, 3);
+    }
+
+    #[test]
+    fn test_delay_for_uses_full_jitter_and_retry_after() {
+        let policy = RetryPolicy {
+            max_attempts: 8,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+        };
+
+        for attempt in 1..=8 {
+            let ceiling = Duration::from_millis(100 * (1 << (attempt - 1))).min(policy.max_delay);
+            assert!(policy.delay_for(attempt, None) <= ceiling);
+        }
+        assert_eq!(
+            policy.delay_for(1, Some(Duration::from_millis(500))),
+            Duration::from_millis(500)
+        );
+        assert_eq!(
+            policy.delay_for(1, Some(Duration::from_secs(60))),
+            policy.max_delay
+        );
+    }
+
+    #[test]
+    fn test_parse_retry_after_seconds_and_http_date() {
+        assert_eq!(parse_retry_after(" 3 "), Some(Duration::from_secs(3)));
+
+        let later = (Utc::now() + chrono::Duration::seconds(30))
+            .format("%a, %d %b %Y %H:%M:%S GMT")
+            .to_string();
+        let delay = parse_retry_after(&later).unwrap();
+        assert!(delay > Duration::from_secs(25) && delay <= Duration::from_secs(30));
+
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon"), None);
+    }
+}
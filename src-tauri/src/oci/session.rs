@@ -0,0 +1,292 @@
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Duration, Utc};
+use reqwest::Method;
+use rsa::RsaPrivateKey;
+use serde::Deserialize;
+
+use crate::models::profile::{OciProfile, SessionStatus};
+use crate::oci::client::{send_signed_request, OciClient, OciRequest};
+use crate::oci::config_backup;
+use crate::oci::error::OciError;
+use crate::oci::signature::SigningCredentials;
+
+/// 有効期限までの残り時間がこれを下回ったらトークンを更新する
+const REFRESH_MARGIN_MINUTES: i64 = 5;
+
+/// セッション更新 API のパス
+const REFRESH_PATH: &str = "/v1/authentication/refresh";
+
+/// セッション更新 API のレスポンス
+#[derive(Debug, Deserialize)]
+struct RefreshResponse {
+    token: String,
+}
+
+/// JWT のペイロード（必要なクレームのみ）
+#[derive(Debug, Deserialize)]
+struct TokenClaims {
+    exp: Option<i64>,
+    tenant: Option<String>,
+}
+
+/// セッショントークン認証の keyId
+pub fn session_key_id(token: &str) -> String {
+    format!("ST${}", token)
+}
+
+/// セッショントークンファイルを読み込む
+pub fn read_security_token(path: &str) -> Result<String, OciError> {
+    let token = fs::read_to_string(path).map_err(|e| {
+        OciError::auth(format!(
+            "セッショントークンファイル '{}' の読み込みに失敗しました: {}",
+            path, e
+        ))
+    })?;
+    Ok(token.trim().to_string())
+}
+
+/// トークン（JWT）のペイロードを解析
+fn token_claims(token: &str) -> Option<TokenClaims> {
+    let payload = token.split('.').nth(1)?;
+    let decoded = URL_SAFE_NO_PAD.decode(payload.trim_end_matches('=')).ok()?;
+    serde_json::from_slice(&decoded).ok()
+}
+
+/// トークン（JWT）の exp クレームから有効期限を取得
+pub fn token_expiry(token: &str) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(token_claims(token)?.exp?, 0)
+}
+
+/// トークン（JWT）の tenant クレームからテナンシー OCID を取得
+pub fn token_tenancy(token: &str) -> Option<String> {
+    token_claims(token)?.tenant
+}
+
+/// プロファイルのセッショントークンの状態を取得
+pub fn session_status(profile: &OciProfile) -> Result<SessionStatus, OciError> {
+    let token_file = security_token_path(profile)?;
+    let expiry = token_expiry(&read_security_token(token_file)?);
+    let now = Utc::now();
+
+    Ok(SessionStatus {
+        expires_at: expiry.map(|exp| exp.to_rfc3339()),
+        seconds_remaining: expiry.map(|exp| (exp - now).num_seconds().max(0)),
+        expired: expiry.is_some_and(|exp| exp <= now),
+    })
+}
+
+/// 署名に使う現在のセッショントークンを取得
+///
+/// 有効期限が近い場合は更新 API で更新してから返す。期限切れの場合は再認証を促すエラーを返す。
+pub async fn current_security_token(
+    client: &OciClient,
+    profile: &OciProfile,
+    private_key: &Arc<RsaPrivateKey>,
+) -> Result<String, OciError> {
+    let token_file = security_token_path(profile)?;
+    let token = read_security_token(token_file)?;
+    if !needs_refresh(&token)? {
+        return Ok(token);
+    }
+
+    // 同時に複数のリクエストが更新しないよう直列化し、先に更新済みなら読み直したトークンを使う
+    let _guard = client.session_refresh.lock().await;
+    let token = read_security_token(token_file)?;
+    if !needs_refresh(&token)? {
+        return Ok(token);
+    }
+
+    // トークン自体はまだ有効なため、更新に失敗した場合は記録して現在のトークンで続行する
+    match refresh_security_token(client, profile, private_key, &token).await {
+        Ok(refreshed) => Ok(refreshed),
+        Err(e) => {
+            log::warn!(
+                "プロファイル '{}' のセッショントークンを更新できません: {}",
+                profile.name,
+                e
+            );
+            Ok(token)
+        }
+    }
+}
+
+/// セッション更新 API でトークンを更新し、トークンファイルを書き換える
+pub async fn refresh_security_token(
+    client: &OciClient,
+    profile: &OciProfile,
+    private_key: &Arc<RsaPrivateKey>,
+    token: &str,
+) -> Result<String, OciError> {
+    let token_file = security_token_path(profile)?;
+    let request = OciRequest::new(Method::POST, "auth", REFRESH_PATH)
+        .json(&serde_json::json!({ "currentToken": token }))?;
+    let credentials = SigningCredentials {
+        key_id: session_key_id(token),
+        private_key: private_key.clone(),
+    };
+
+    let response =
+        send_signed_request(client, profile, request, &credentials, &client.retry_policy).await?;
+    let refreshed: RefreshResponse =
+        serde_json::from_str(&response.body).map_err(|e| OciError::parse(e.to_string()))?;
+    write_security_token(token_file, &refreshed.token)?;
+    Ok(refreshed.token)
+}
+
+/// プロファイルのセッショントークンファイルのパス
+pub fn security_token_path(profile: &OciProfile) -> Result<&str, OciError> {
+    profile.security_token_file.as_deref().ok_or_else(|| {
+        OciError::config(format!(
+            "プロファイル '{}' はセッショントークン認証ではありません。",
+            profile.name
+        ))
+    })
+}
+
+/// トークンの有効期限が近い（または切れている）かどうか
+pub fn expires_soon(token: &str) -> bool {
+    token_expiry(token)
+        .is_some_and(|expiry| expiry - Utc::now() <= Duration::minutes(REFRESH_MARGIN_MINUTES))
+}
+
+/// 更新が必要かどうか（期限切れの場合はエラー）
+fn needs_refresh(token: &str) -> Result<bool, OciError> {
+    if token_expiry(token).is_some_and(|expiry| expiry <= Utc::now()) {
+        return Err(OciError::auth(
+            "セッションの有効期限が切れています。`oci session authenticate` で再認証してください。",
+        ));
+    }
+    Ok(expires_soon(token))
+}
+
+/// トークンファイルを置き換える（パーミッションは 600）
+///
+/// 他のプロセス（OCI CLI など）が読み込み中でも、書きかけのトークンを読まないようにする。
+fn write_security_token(path: &str, token: &str) -> Result<(), OciError> {
+    config_backup::atomic_write(Path::new(path), token.as_bytes()).map_err(|e| {
+        OciError::auth(format!(
+            "セッショントークンファイルの書き込みに失敗しました: {}",
+            e
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oci::signature::load_private_key;
+    use crate::oci::test_server::{start_sequential_server, MockResponse};
+    use std::sync::Arc;
+    use tempfile::NamedTempFile;
+
+    /// exp クレームだけを持つ署名なしのテスト用トークン
+    fn make_token(expires_in: Duration) -> String {
+        let claims = serde_json::json!({ "exp": (Utc::now() + expires_in).timestamp() });
+        format!(
+            "eyJhbGciOiJSUzI1NiJ9.{}.c2ln",
+            URL_SAFE_NO_PAD.encode(claims.to_string())
+        )
+    }
+
+    #[test]
+    fn test_token_expiry_reads_exp_claim() {
+        let token = make_token(Duration::hours(1));
+        let expiry = token_expiry(&token).unwrap();
+        assert!(
+            (expiry - Utc::now() - Duration::hours(1))
+                .num_seconds()
+                .abs()
+                <= 1
+        );
+        assert!(token_expiry("not-a-jwt").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_refreshes_token_close_to_expiry() {
+        let old_token = make_token(Duration::minutes(1));
+        let new_token = make_token(Duration::hours(1));
+        let body = serde_json::json!({ "token": new_token }).to_string();
+        let (url, requests) = start_sequential_server(vec![
+            MockResponse::ok(body).header("content-type", "application/json")
+        ])
+        .await;
+
+        let mut token_file = NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut token_file, old_token.as_bytes()).unwrap();
+        let key_file = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/keys/plain_pkcs1.pem"
+        );
+
+        let mut profile = OciProfile {
+            name: "SESSION".to_string(),
+            region: "ap-tokyo-1".to_string(),
+            key_file: key_file.to_string(),
+            security_token_file: Some(token_file.path().to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        profile.service_endpoints.insert("auth".to_string(), url);
+
+        let client = OciClient::new();
+        let key = Arc::new(load_private_key(key_file, None).unwrap());
+        let token = current_security_token(&client, &profile, &key)
+            .await
+            .unwrap();
+
+        assert_eq!(token, new_token);
+        assert_eq!(
+            read_security_token(profile.security_token_file.as_deref().unwrap()).unwrap(),
+            new_token
+        );
+        let request = &requests.lock().unwrap()[0];
+        assert!(request.starts_with("POST /v1/authentication/refresh "));
+        assert!(request.contains(&format!("keyId=\"ST${}\"", old_token)));
+        assert!(request.contains(&format!("\"currentToken\":\"{}\"", old_token)));
+        assert!(!session_status(&profile).unwrap().expired);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(token_file.path())
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_keeps_current_token_when_refresh_fails() {
+        let old_token = make_token(Duration::minutes(1));
+        let (url, requests) = start_sequential_server(vec![MockResponse::status(401)]).await;
+
+        let mut token_file = NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut token_file, old_token.as_bytes()).unwrap();
+        let key_file = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/keys/plain_pkcs1.pem"
+        );
+        let mut profile = OciProfile {
+            name: "SESSION".to_string(),
+            region: "ap-tokyo-1".to_string(),
+            key_file: key_file.to_string(),
+            security_token_file: Some(token_file.path().to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        profile.service_endpoints.insert("auth".to_string(), url);
+
+        let key = Arc::new(load_private_key(key_file, None).unwrap());
+        let token = current_security_token(&OciClient::new(), &profile, &key)
+            .await
+            .unwrap();
+
+        assert_eq!(token, old_token);
+        assert_eq!(requests.lock().unwrap().len(), 1);
+        assert_eq!(fs::read_to_string(token_file.path()).unwrap(), old_token);
+    }
+}
@@ -0,0 +1,399 @@
+use std::path::Path;
+use std::time::Duration;
+
+use base64::engine::general_purpose::{URL_SAFE, URL_SAFE_NO_PAD};
+use base64::Engine;
+use percent_encoding::{percent_decode_str, utf8_percent_encode, NON_ALPHANUMERIC};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use rsa::traits::PublicKeyParts;
+use rsa::RsaPublicKey;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::models::profile::OciProfile;
+use crate::oci::api_keys::{generate_key_pair, save_key_pair, write_private_file};
+use crate::oci::config_parser;
+use crate::oci::error::OciError;
+use crate::oci::regions::region_to_endpoint;
+use crate::oci::session::token_tenancy;
+
+/// コールバックを受け付けるローカルポート（OCI CLI と同じ）
+pub const SSO_CALLBACK_PORT: u16 = 8181;
+
+/// ブラウザでのログイン完了を待つ最大時間
+const LOGIN_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// セッション用に生成する鍵の長さ
+const SESSION_KEY_BITS: usize = 2048;
+
+/// ブラウザでのログイン要求
+#[derive(Debug, Clone)]
+pub struct SsoLoginRequest {
+    /// 作成するプロファイル名
+    pub profile_name: String,
+    /// ログインするリージョン
+    pub region: String,
+    /// テナンシー名（指定するとテナンシー選択画面を省略できる）
+    pub tenancy_name: Option<String>,
+}
+
+/// ブラウザでログインしてセッショントークンを取得し、セッションプロファイルを設定ファイルに書き込む
+///
+/// `open_browser` には認可 URL が渡される（Tauri では opener プラグインで開く）。
+pub async fn login<F>(
+    request: &SsoLoginRequest,
+    config_path: &Path,
+    open_browser: F,
+) -> Result<OciProfile, OciError>
+where
+    F: FnOnce(&str) -> Result<(), OciError>,
+{
+    let profile_name = request.profile_name.trim();
+    if profile_name.is_empty() {
+        return Err(OciError::config("プロファイル名を入力してください。"));
+    }
+    // 名前は鍵・トークンの保存先のディレクトリ名にも使う
+    config_parser::check_profile_name(profile_name)?;
+
+    let profiles = if config_path.exists() {
+        config_parser::parse_config(config_path).map_err(OciError::config)?
+    } else {
+        Vec::new()
+    };
+    // 既存のセッションプロファイルは再ログインで上書きする
+    if profiles
+        .iter()
+        .any(|p| p.name == profile_name && p.security_token_file.is_none())
+    {
+        return Err(OciError::config(format!(
+            "プロファイル '{}' は既に存在します。",
+            profile_name
+        )));
+    }
+
+    let listener = TcpListener::bind(("127.0.0.1", SSO_CALLBACK_PORT))
+        .await
+        .map_err(|e| {
+            OciError::config(format!(
+                "ポート {} でコールバックを待ち受けできません: {}",
+                SSO_CALLBACK_PORT, e
+            ))
+        })?;
+
+    complete_login(request, config_path, &listener, open_browser).await
+}
+
+/// 認可 URL を開いてコールバックを待ち、取得したセッションのプロファイルを書き込む
+async fn complete_login<F>(
+    request: &SsoLoginRequest,
+    config_path: &Path,
+    listener: &TcpListener,
+    open_browser: F,
+) -> Result<OciProfile, OciError>
+where
+    F: FnOnce(&str) -> Result<(), OciError>,
+{
+    let profile_name = request.profile_name.trim();
+    let private_key = generate_key_pair(SESSION_KEY_BITS).await?;
+    let public_key = private_key.to_public_key();
+
+    // 他のローカルプロセスがコールバックにトークンを送り込めないよう、state を照合する
+    let state = random_value();
+    let url = authorize_url(request, &public_key, &state)?;
+    open_browser(&url)?;
+
+    let token = tokio::time::timeout(LOGIN_TIMEOUT, wait_for_token(listener, &state))
+        .await
+        .map_err(|_| OciError::auth("ブラウザでのログインがタイムアウトしました。"))??;
+
+    let tenancy = token_tenancy(&token)
+        .ok_or_else(|| OciError::parse("セッショントークンにテナンシー情報が含まれていません。"))?;
+
+    // 鍵とトークンは <設定ディレクトリ>/sessions/<プロファイル名>/ に保存する
+    let session_dir = config_path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join("sessions")
+        .join(profile_name);
+    let saved = save_key_pair(&session_dir, "oci_api_key", &private_key, true)?;
+    let token_file = session_dir.join("token");
+    write_private_file(&token_file, token.as_bytes())?;
+
+    let profile = OciProfile {
+        name: profile_name.to_string(),
+        tenancy,
+        region: request.region.clone(),
+        fingerprint: saved.fingerprint,
+        key_file: saved.private_key_file.to_string_lossy().to_string(),
+        security_token_file: Some(token_file.to_string_lossy().to_string()),
+        ..Default::default()
+    };
+
+    config_parser::upsert_profile(config_path, None, &profile).map_err(OciError::config)?;
+
+    Ok(profile)
+}
+
+/// フェデレーションログインページの認可 URL を生成
+fn authorize_url(
+    request: &SsoLoginRequest,
+    public_key: &RsaPublicKey,
+    state: &str,
+) -> Result<String, OciError> {
+    let jwk = serde_json::json!({
+        "kty": "RSA",
+        "n": URL_SAFE_NO_PAD.encode(public_key.n().to_bytes_be()),
+        "e": URL_SAFE_NO_PAD.encode(public_key.e().to_bytes_be()),
+        "kid": "Ignored",
+    });
+    let redirect_uri = format!("http://localhost:{}", SSO_CALLBACK_PORT);
+    let encode = |value: &str| utf8_percent_encode(value, NON_ALPHANUMERIC).to_string();
+
+    let mut url = format!(
+        "https://{}/v1/oauth2/authorize?action=login&client_id=iaas_console&response_type=token+id_token&nonce={}&state={}&scope=openid&public_key={}&redirect_uri={}",
+        region_to_endpoint(&request.region, "login"),
+        random_value(),
+        state,
+        encode(&URL_SAFE.encode(jwk.to_string())),
+        encode(&redirect_uri),
+    );
+    if let Some(tenancy_name) = request
+        .tenancy_name
+        .as_deref()
+        .filter(|t| !t.trim().is_empty())
+    {
+        url.push_str(&format!("&tenant={}", encode(tenancy_name.trim())));
+    }
+    Ok(url)
+}
+
+/// 認可リクエストの state・nonce に使うランダムな値
+fn random_value() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(32)
+        .map(char::from)
+        .collect()
+}
+
+/// リダイレクトを受け付け、セッショントークンを取り出す
+///
+/// トークンは URL フラグメントで渡されるため、最初の応答でフラグメントをクエリに付け替えて
+/// `/token` に遷移させるスクリプトを返す。`state` が認可リクエストと一致しないコールバックは
+/// 拒否して待ち続ける。
+async fn wait_for_token(listener: &TcpListener, state: &str) -> Result<String, OciError> {
+    loop {
+        let (mut socket, _) = listener
+            .accept()
+            .await
+            .map_err(|e| OciError::transport(e.to_string()))?;
+        let Some(target) = read_request_target(&mut socket).await else {
+            continue;
+        };
+
+        let (path, query) = target.split_once('?').unwrap_or((target.as_str(), ""));
+        match path {
+            "/" => respond(&mut socket, 200, REDIRECT_PAGE).await,
+            "/token" => {
+                let params = parse_query(query);
+                let param = |name: &str| {
+                    params
+                        .iter()
+                        .find(|(key, _)| key == name)
+                        .map(|(_, value)| value.clone())
+                };
+
+                if param("state").as_deref() != Some(state) {
+                    respond(&mut socket, 400, FAILURE_PAGE).await;
+                    continue;
+                }
+                if let Some(token) = param("security_token") {
+                    respond(&mut socket, 200, SUCCESS_PAGE).await;
+                    return Ok(token);
+                }
+                respond(&mut socket, 400, FAILURE_PAGE).await;
+                let reason = param("error_description")
+                    .or_else(|| param("error"))
+                    .unwrap_or_else(|| "セッショントークンが返されませんでした。".to_string());
+                return Err(OciError::auth(format!(
+                    "ログインに失敗しました: {}",
+                    reason
+                )));
+            }
+            _ => respond(&mut socket, 404, "").await,
+        }
+    }
+}
+
+/// HTTP リクエスト行からリクエストターゲットを取り出す
+async fn read_request_target(socket: &mut TcpStream) -> Option<String> {
+    let mut received = Vec::new();
+    let mut buf = [0u8; 4096];
+    while !received.windows(4).any(|w| w == b"\r\n\r\n") {
+        let n = socket.read(&mut buf).await.ok()?;
+        if n == 0 || received.len() > 64 * 1024 {
+            break;
+        }
+        received.extend_from_slice(&buf[..n]);
+    }
+
+    let text = String::from_utf8_lossy(&received);
+    let mut parts = text.lines().next()?.split_whitespace();
+    match (parts.next(), parts.next()) {
+        (Some("GET"), Some(target)) => Some(target.to_string()),
+        _ => None,
+    }
+}
+
+/// クエリ文字列を解析
+fn parse_query(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(key, value)| {
+            let decode = |v: &str| {
+                percent_decode_str(&v.replace('+', " "))
+                    .decode_utf8_lossy()
+                    .to_string()
+            };
+            (decode(key), decode(value))
+        })
+        .collect()
+}
+
+/// HTML レスポンスを返す
+async fn respond(socket: &mut TcpStream, status: u16, body: &str) {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        _ => "Not Found",
+    };
+    let response = format!(
+        "HTTP/1.1 {} {}\r\ncontent-type: text/html; charset=utf-8\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    );
+    socket.write_all(response.as_bytes()).await.ok();
+    socket.shutdown().await.ok();
+}
+
+/// フラグメントをクエリに付け替えて /token に遷移するページ
+const REDIRECT_PAGE: &str = r#"<!DOCTYPE html>
+<html><head><meta charset="utf-8"><title>OCI Desktop</title></head>
+<body><p>ログイン情報を処理しています...</p>
+<script>window.location.replace("/token?" + window.location.hash.substring(1));</script>
+</body></html>"#;
+
+/// ログイン成功ページ
+const SUCCESS_PAGE: &str = r#"<!DOCTYPE html>
+<html><head><meta charset="utf-8"><title>OCI Desktop</title></head>
+<body><p>ログインが完了しました。このウィンドウを閉じてアプリに戻ってください。</p></body></html>"#;
+
+/// ログイン失敗ページ
+const FAILURE_PAGE: &str = r#"<!DOCTYPE html>
+<html><head><meta charset="utf-8"><title>OCI Desktop</title></head>
+<body><p>ログインに失敗しました。アプリに戻ってやり直してください。</p></body></html>"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[tokio::test]
+    async fn test_wait_for_token_moves_fragment_to_query() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move { wait_for_token(&listener, "expected").await });
+
+        // ブラウザはフラグメントを送らないため、まずスクリプト入りのページが返る
+        let page = reqwest::get(&base).await.unwrap().text().await.unwrap();
+        assert!(page.contains("window.location.hash"));
+
+        // state が一致しないコールバックは拒否し、待ち続ける
+        for forged in ["security_token=forged", "security_token=forged&state=other"] {
+            let response = reqwest::get(format!("{}/token?{}", base, forged))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), 400);
+        }
+        assert!(!server.is_finished());
+
+        let response = reqwest::get(format!(
+            "{}/token?security_token=abc.def%3D&expires_in=3600&state=expected",
+            base
+        ))
+        .await
+        .unwrap();
+        assert!(response.status().is_success());
+        assert_eq!(server.await.unwrap().unwrap(), "abc.def=");
+    }
+
+    #[tokio::test]
+    async fn test_login_writes_session_profile() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("config");
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let claims = serde_json::json!({ "tenant": "ocid1.tenancy.oc1..test" });
+        let token = format!(
+            "eyJhbGciOiJSUzI1NiJ9.{}.c2ln",
+            URL_SAFE_NO_PAD.encode(claims.to_string())
+        );
+        let request = SsoLoginRequest {
+            profile_name: "SSO".to_string(),
+            region: "ap-tokyo-1".to_string(),
+            tenancy_name: None,
+        };
+
+        // ブラウザの代わりに、認可 URL の state を付けてコールバックを呼ぶ
+        let callback = token.clone();
+        let profile = complete_login(&request, &config_path, &listener, move |url| {
+            let (_, query) = url.split_once('?').unwrap();
+            let state = parse_query(query)
+                .into_iter()
+                .find(|(key, _)| key == "state")
+                .map(|(_, value)| value)
+                .unwrap();
+            tokio::spawn(async move {
+                reqwest::get(format!(
+                    "{}/token?security_token={}&state={}",
+                    base, callback, state
+                ))
+                .await
+                .unwrap();
+            });
+            Ok(())
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(profile.tenancy, "ocid1.tenancy.oc1..test");
+        let token_file = profile.security_token_file.clone().unwrap();
+        assert!(Path::new(&token_file).starts_with(dir.path().join("sessions").join("SSO")));
+        assert_eq!(fs::read_to_string(&token_file).unwrap(), token);
+        assert!(Path::new(&profile.key_file).exists());
+
+        let saved = config_parser::parse_config(&config_path).unwrap();
+        assert_eq!(saved, vec![profile]);
+    }
+
+    #[tokio::test]
+    async fn test_login_rejects_unsafe_profile_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let request = SsoLoginRequest {
+            profile_name: "../../.ssh".to_string(),
+            region: "ap-tokyo-1".to_string(),
+            tenancy_name: None,
+        };
+        let error = login(&request, &dir.path().join("config"), |_| {
+            panic!("ブラウザを開く前に拒否されるはず")
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(error.kind(), "config");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+}
@@ -0,0 +1,161 @@
+// テスト用のモック OCI サーバー
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::models::profile::OciProfile;
+
+/// 受信したリクエスト（リクエスト行・ヘッダー・ボディ）の記録
+pub type RecordedRequests = Arc<Mutex<Vec<String>>>;
+
+/// モックサーバーが返すレスポンス
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    pub status: u16,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: String,
+}
+
+impl MockResponse {
+    /// 200 でボディを返す
+    pub fn ok(body: impl Into<String>) -> Self {
+        Self {
+            status: 200,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    /// 指定したステータスで空の JSON を返す
+    pub fn status(status: u16) -> Self {
+        Self {
+            status,
+            ..Self::ok("{}")
+        }
+    }
+
+    /// ヘッダーを追加
+    pub fn header(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.headers.push((name, value.into()));
+        self
+    }
+}
+
+/// 接続ごとに `responses` を順番に返すモックサーバーを起動する
+pub async fn start_sequential_server(responses: Vec<MockResponse>) -> (String, RecordedRequests) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let requests = RecordedRequests::default();
+    let recorded = requests.clone();
+
+    tokio::spawn(async move {
+        for response in responses {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let request = read_request(&mut socket).await;
+            recorded.lock().unwrap().push(request);
+            write_response(&mut socket, &response).await;
+        }
+    });
+
+    (format!("http://{}", addr), requests)
+}
+
+/// パスごとに固定のボディを返す（該当しないパスは 404）モックサーバーを起動する
+pub async fn start_routed_server(
+    routes: HashMap<&'static str, String>,
+) -> (String, RecordedRequests) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let requests = RecordedRequests::default();
+    let recorded = requests.clone();
+
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let request = read_request(&mut socket).await;
+            let path = request.split_whitespace().nth(1).unwrap_or("").to_string();
+            recorded.lock().unwrap().push(request);
+
+            let response = match routes.get(path.as_str()) {
+                Some(body) => MockResponse::ok(body.clone()),
+                None => MockResponse {
+                    status: 404,
+                    ..MockResponse::ok("")
+                },
+            };
+            write_response(&mut socket, &response).await;
+        }
+    });
+
+    (format!("http://{}", addr), requests)
+}
+
+/// ヘッダーと content-length 分のボディを読み込む
+async fn read_request(socket: &mut TcpStream) -> String {
+    let mut received = Vec::new();
+    let mut buf = vec![0u8; 16384];
+    loop {
+        let n = socket.read(&mut buf).await.unwrap();
+        received.extend_from_slice(&buf[..n]);
+        let text = String::from_utf8_lossy(&received).to_string();
+        let Some(header_end) = text.find("\r\n\r\n") else {
+            if n == 0 {
+                break;
+            }
+            continue;
+        };
+        let content_length = text[..header_end]
+            .to_lowercase()
+            .lines()
+            .find_map(|l| {
+                l.strip_prefix("content-length:")
+                    .map(str::trim)
+                    .map(String::from)
+            })
+            .and_then(|v| v.parse::<usize>().ok())
+            .unwrap_or(0);
+        if n == 0 || received.len() >= header_end + 4 + content_length {
+            break;
+        }
+    }
+    String::from_utf8_lossy(&received).to_string()
+}
+
+async fn write_response(socket: &mut TcpStream, response: &MockResponse) {
+    let headers: String = response
+        .headers
+        .iter()
+        .map(|(name, value)| format!("{}: {}\r\n", name, value))
+        .collect();
+    let raw = format!(
+        "HTTP/1.1 {} Mock\r\ncontent-length: {}\r\n{}connection: close\r\n\r\n{}",
+        response.status,
+        response.body.len(),
+        headers,
+        response.body
+    );
+    socket.write_all(raw.as_bytes()).await.unwrap();
+    socket.shutdown().await.ok();
+}
+
+/// `service` のエンドポイントをモックサーバーに向けたテスト用プロファイル
+pub fn test_profile(service: &str, url: &str) -> OciProfile {
+    let mut profile = OciProfile {
+        region: "ap-tokyo-1".to_string(),
+        tenancy: "ocid1.tenancy.oc1..test".to_string(),
+        user: "ocid1.user.oc1..test".to_string(),
+        fingerprint: "aa:bb".to_string(),
+        key_file: concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/keys/plain_pkcs1.pem"
+        )
+        .to_string(),
+        ..Default::default()
+    };
+    profile
+        .service_endpoints
+        .insert(service.to_string(), url.to_string());
+    profile
+}
@@ -0,0 +1,308 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::Notify;
+use tokio::time::Instant;
+
+use crate::models::profile::OciProfile;
+use crate::oci::client::{self, OciClient};
+use crate::oci::error::OciError;
+use crate::oci::regions::region_of_ocid;
+
+/// 状態を確認する間隔とタイムアウト
+#[derive(Debug, Clone)]
+pub struct WaitPolicy {
+    /// 最初の確認までの待機時間
+    pub initial_interval: Duration,
+    /// 確認間隔の上限
+    pub max_interval: Duration,
+    /// 待機する最大時間
+    pub timeout: Duration,
+}
+
+impl Default for WaitPolicy {
+    fn default() -> Self {
+        Self {
+            initial_interval: Duration::from_secs(2),
+            max_interval: Duration::from_secs(30),
+            timeout: Duration::from_secs(20 * 60),
+        }
+    }
+}
+
+impl WaitPolicy {
+    /// タイムアウトを秒数で指定したポリシー（未指定の場合は既定値）
+    pub fn with_timeout_secs(timeout_secs: Option<u64>) -> Self {
+        let mut policy = Self::default();
+        if let Some(secs) = timeout_secs {
+            policy.timeout = Duration::from_secs(secs);
+        }
+        policy
+    }
+
+    /// `attempt` 回目（0 始まり）の確認の前に待機する時間（1.5 倍ずつ `max_interval` まで延ばす）
+    pub fn interval_for(&self, attempt: u32) -> Duration {
+        let factor = 1.5f64.powi(attempt.min(32) as i32);
+        self.initial_interval.mul_f64(factor).min(self.max_interval)
+    }
+}
+
+/// 待機の中止
+#[derive(Debug, Default)]
+pub struct CancelToken {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+impl CancelToken {
+    /// 待機を中止する
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.notify.notify_waiters();
+    }
+
+    /// 中止されたかどうか
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// 中止されるまで待つ
+    pub async fn cancelled(&self) {
+        let notified = self.notify.notified();
+        if self.is_cancelled() {
+            return;
+        }
+        notified.await;
+    }
+}
+
+/// 実行中の待機（ID ごとの中止トークン）
+#[derive(Debug, Default)]
+pub struct WaitRegistry {
+    tokens: Mutex<HashMap<String, Arc<CancelToken>>>,
+}
+
+impl WaitRegistry {
+    /// 待機を登録する（返り値を破棄すると登録を解除する）
+    pub fn register(&self, id: &str) -> WaitHandle<'_> {
+        let token = Arc::new(CancelToken::default());
+        self.tokens
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(id.to_string(), token.clone());
+        WaitHandle {
+            registry: self,
+            id: id.to_string(),
+            token,
+        }
+    }
+
+    /// ID の待機を中止する（実行中の待機がなければ false）
+    pub fn cancel(&self, id: &str) -> bool {
+        let tokens = self.tokens.lock().unwrap_or_else(|e| e.into_inner());
+        match tokens.get(id) {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// 登録中の待機
+pub struct WaitHandle<'a> {
+    registry: &'a WaitRegistry,
+    id: String,
+    token: Arc<CancelToken>,
+}
+
+impl WaitHandle<'_> {
+    /// この待機の中止トークン
+    pub fn token(&self) -> &CancelToken {
+        &self.token
+    }
+}
+
+impl Drop for WaitHandle<'_> {
+    fn drop(&mut self) {
+        let mut tokens = self
+            .registry
+            .tokens
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        // 同じ ID で後から登録された待機は残す
+        if tokens
+            .get(&self.id)
+            .is_some_and(|token| Arc::ptr_eq(token, &self.token))
+        {
+            tokens.remove(&self.id);
+        }
+    }
+}
+
+/// バックオフしながら状態を確認するためのタイマー
+///
+/// 操作直後は状態が変わっていないことがあるため、最初の確認の前にも待機する。
+pub struct Poller<'a> {
+    policy: WaitPolicy,
+    cancel: &'a CancelToken,
+    deadline: Instant,
+    attempt: u32,
+}
+
+impl<'a> Poller<'a> {
+    pub fn new(policy: WaitPolicy, cancel: &'a CancelToken) -> Self {
+        let deadline = Instant::now() + policy.timeout;
+        Self {
+            policy,
+            cancel,
+            deadline,
+            attempt: 0,
+        }
+    }
+
+    /// 次の確認まで待機する（中止された場合とタイムアウトした場合はエラー）
+    pub async fn tick(&mut self) -> Result<(), OciError> {
+        let now = Instant::now();
+        if now >= self.deadline {
+            return Err(OciError::wait(format!(
+                "{} 秒待っても完了しませんでした。",
+                self.policy.timeout.as_secs()
+            )));
+        }
+        let delay = self
+            .policy
+            .interval_for(self.attempt)
+            .min(self.deadline - now);
+        self.attempt += 1;
+
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => Ok(()),
+            _ = self.cancel.cancelled() => Err(OciError::wait("待機を中止しました。")),
+        }
+    }
+}
+
+/// OCID のリージョンを使うプロファイル（OCID にリージョンがない場合はそのまま）
+pub fn profile_for_resource(profile: &OciProfile, ocid: &str) -> OciProfile {
+    OciProfile {
+        region: region_of_ocid(ocid).unwrap_or_else(|| profile.region.clone()),
+        ..profile.clone()
+    }
+}
+
+/// OCID の種類から、リソースを取得するサービスとパスを決める
+pub fn resource_endpoint(ocid: &str) -> Option<(&'static str, String)> {
+    let (service, collection) = match ocid.split('.').nth(1)? {
+        "instance" => ("iaas", "instances"),
+        "vcn" => ("iaas", "vcns"),
+        "subnet" => ("iaas", "subnets"),
+        "volume" => ("iaas", "volumes"),
+        "bootvolume" => ("iaas", "bootVolumes"),
+        "dbsystem" => ("database", "dbSystems"),
+        "compartment" => ("identity", "compartments"),
+        _ => return None,
+    };
+    Some((service, format!("/20160918/{}/{}", collection, ocid)))
+}
+
+/// リソースの現在のライフサイクル状態を取得
+pub async fn get_lifecycle_state(
+    client: &OciClient,
+    profile: &OciProfile,
+    resource_id: &str,
+) -> Result<String, OciError> {
+    let (service, path) = resource_endpoint(resource_id).ok_or_else(|| {
+        OciError::config(format!("状態を確認できないリソースです: {}", resource_id))
+    })?;
+    let body = client::oci_get_request(client, profile, service, &path, None).await?;
+    let value: serde_json::Value =
+        serde_json::from_str(&body).map_err(|e| OciError::parse(e.to_string()))?;
+    value["lifecycleState"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| OciError::parse("lifecycleState がありません"))
+}
+
+/// 目的の状態にならないまま止まる状態（目的の状態に含まれていない場合はエラーにする）
+const FAILED_STATES: &[&str] = &["FAILED", "TERMINATING", "TERMINATED", "DELETING", "DELETED"];
+
+/// リソースが `target_states` のいずれかになるまで待ち、その状態を返す
+///
+/// 状態を取得するたびに `on_progress` を呼び出す。
+pub async fn wait_for_lifecycle_state<F>(
+    client: &OciClient,
+    profile: &OciProfile,
+    resource_id: &str,
+    target_states: &[String],
+    mut poller: Poller<'_>,
+    on_progress: F,
+) -> Result<String, OciError>
+where
+    F: Fn(&str),
+{
+    loop {
+        poller.tick().await?;
+        let state = get_lifecycle_state(client, profile, resource_id).await?;
+        on_progress(&state);
+
+        if target_states.contains(&state) {
+            return Ok(state);
+        }
+        if FAILED_STATES.contains(&state.as_str()) {
+            return Err(OciError::wait(format!(
+                "リソース '{}' は {} になりました。",
+                resource_id, state
+            )));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval_backs_off_up_to_max() {
+        let policy = WaitPolicy::default();
+        assert_eq!(policy.interval_for(0), Duration::from_secs(2));
+        assert_eq!(policy.interval_for(1), Duration::from_secs(3));
+        assert_eq!(policy.interval_for(2), Duration::from_millis(4500));
+        assert_eq!(policy.interval_for(20), Duration::from_secs(30));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_stops_poller_and_unregisters() {
+        let registry = WaitRegistry::default();
+        let handle = registry.register("ocid1.workrequest.oc1..test");
+        let mut poller = Poller::new(
+            WaitPolicy {
+                initial_interval: Duration::from_secs(60),
+                ..WaitPolicy::default()
+            },
+            handle.token(),
+        );
+
+        assert!(registry.cancel("ocid1.workrequest.oc1..test"));
+        let error = poller.tick().await.unwrap_err();
+        assert_eq!(error.kind(), "wait");
+
+        drop(handle);
+        assert!(!registry.cancel("ocid1.workrequest.oc1..test"));
+    }
+
+    #[test]
+    fn test_resource_endpoint() {
+        assert_eq!(
+            resource_endpoint("ocid1.dbsystem.oc1.ap-tokyo-1.xyz"),
+            Some((
+                "database",
+                "/20160918/dbSystems/ocid1.dbsystem.oc1.ap-tokyo-1.xyz".to_string()
+            ))
+        );
+        assert_eq!(resource_endpoint("ocid1.bucket.oc1..xyz"), None);
+    }
+}
@@ -0,0 +1,269 @@
+use crate::models::profile::OciProfile;
+use crate::models::work_request::{
+    WorkRequest, WorkRequestError, WorkRequestLogEntry, WorkRequestProgress,
+};
+use crate::oci::client::{self, OciClient};
+use crate::oci::error::OciError;
+use crate::oci::waiter::Poller;
+
+/// ワークリクエストを提供するサービスとパス
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct WorkRequestApi {
+    service: &'static str,
+    /// ワークリクエストのコレクションのパス
+    collection: &'static str,
+    /// 一覧を対象リソースで絞り込むクエリパラメータ
+    resource_filter: &'static str,
+}
+
+/// コンピュート・ネットワーク・ブロックボリュームなどの共通の Work Requests API
+const CORE_WORK_REQUESTS: WorkRequestApi = WorkRequestApi {
+    service: "iaas",
+    collection: "/20160918/workRequests",
+    resource_filter: "resourceId",
+};
+
+/// IAM（コンパートメントの削除など）のワークリクエスト
+const IAM_WORK_REQUESTS: WorkRequestApi = WorkRequestApi {
+    service: "identity",
+    collection: "/20160918/iamWorkRequests",
+    resource_filter: "resourceIdentifier",
+};
+
+/// Container Engine（クラスタ・ノードプール）のワークリクエスト
+const CLUSTER_WORK_REQUESTS: WorkRequestApi = WorkRequestApi {
+    service: "containerengine",
+    collection: "/20180222/workRequests",
+    resource_filter: "resourceId",
+};
+
+impl WorkRequestApi {
+    /// ワークリクエスト OCID の種類から決める
+    fn for_work_request(work_request_id: &str) -> Result<Self, OciError> {
+        match work_request_id.split('.').nth(1) {
+            Some("coreservicesworkrequest" | "workrequest") => Ok(CORE_WORK_REQUESTS),
+            Some("iamworkrequest") => Ok(IAM_WORK_REQUESTS),
+            Some("clustersworkrequest") => Ok(CLUSTER_WORK_REQUESTS),
+            _ => Err(OciError::config(format!(
+                "状態を確認できないワークリクエストです: {}",
+                work_request_id
+            ))),
+        }
+    }
+
+    /// 一覧の対象リソース OCID の種類から決める（未指定・その他の種類は共通の API）
+    fn for_resource(resource_id: Option<&str>) -> Self {
+        match resource_id.and_then(|id| id.split('.').nth(1)) {
+            Some("compartment") => IAM_WORK_REQUESTS,
+            Some("cluster" | "nodepool") => CLUSTER_WORK_REQUESTS,
+            _ => CORE_WORK_REQUESTS,
+        }
+    }
+
+    /// ワークリクエスト（`suffix` は "/errors" などのサブリソース）のパス
+    fn path(&self, work_request_id: &str, suffix: &str) -> String {
+        format!("{}/{}{}", self.collection, work_request_id, suffix)
+    }
+}
+
+/// ワークリクエストを取得
+pub async fn get_work_request(
+    client: &OciClient,
+    profile: &OciProfile,
+    work_request_id: &str,
+) -> Result<WorkRequest, OciError> {
+    let api = WorkRequestApi::for_work_request(work_request_id)?;
+    let path = api.path(work_request_id, "");
+    let body = client::oci_get_request(client, profile, api.service, &path, None).await?;
+    serde_json::from_str(&body).map_err(|e| OciError::parse(e.to_string()))
+}
+
+/// コンパートメントのワークリクエスト一覧を取得（`resource_id` で対象リソースを絞り込める）
+///
+/// 対象リソースの種類から、ワークリクエストを提供するサービスを選ぶ。
+pub async fn list_work_requests(
+    client: &OciClient,
+    profile: &OciProfile,
+    compartment_id: &str,
+    resource_id: Option<&str>,
+    max_items: Option<usize>,
+) -> Result<Vec<WorkRequest>, OciError> {
+    let api = WorkRequestApi::for_resource(resource_id);
+    let mut query = format!("compartmentId={}", compartment_id);
+    if let Some(resource_id) = resource_id {
+        query.push_str(&format!("&{}={}", api.resource_filter, resource_id));
+    }
+    client::oci_list_request(
+        client,
+        profile,
+        api.service,
+        api.collection,
+        Some(&query),
+        max_items,
+    )
+    .await
+}
+
+/// ワークリクエストのエラー一覧を取得
+pub async fn list_work_request_errors(
+    client: &OciClient,
+    profile: &OciProfile,
+    work_request_id: &str,
+) -> Result<Vec<WorkRequestError>, OciError> {
+    let api = WorkRequestApi::for_work_request(work_request_id)?;
+    let path = api.path(work_request_id, "/errors");
+    client::oci_list_request(client, profile, api.service, &path, None, None).await
+}
+
+/// ワークリクエストのログ一覧を取得
+pub async fn list_work_request_logs(
+    client: &OciClient,
+    profile: &OciProfile,
+    work_request_id: &str,
+) -> Result<Vec<WorkRequestLogEntry>, OciError> {
+    let api = WorkRequestApi::for_work_request(work_request_id)?;
+    let path = api.path(work_request_id, "/logs");
+    client::oci_list_request(client, profile, api.service, &path, None, None).await
+}
+
+/// ワークリクエストが完了（SUCCEEDED / FAILED / CANCELED）するまで待ち、完了したワークリクエストを返す
+///
+/// 状態を確認するたびに、状態・進捗率と新しいエラー・ログを `on_progress` に渡す。
+/// FAILED・CANCELED でもエラーにはしない（結果は返り値の `status` で判断する）。
+pub async fn wait_for_work_request<F>(
+    client: &OciClient,
+    profile: &OciProfile,
+    work_request_id: &str,
+    mut poller: Poller<'_>,
+    on_progress: F,
+) -> Result<WorkRequest, OciError>
+where
+    F: Fn(&WorkRequestProgress),
+{
+    let mut seen_errors = 0;
+    let mut seen_logs = 0;
+    loop {
+        poller.tick().await?;
+        let work_request = get_work_request(client, profile, work_request_id).await?;
+        let errors = list_work_request_errors(client, profile, work_request_id).await?;
+        let log_entries = list_work_request_logs(client, profile, work_request_id).await?;
+
+        let progress = WorkRequestProgress {
+            work_request_id: work_request_id.to_string(),
+            status: work_request.status.clone(),
+            percent_complete: work_request.percent_complete,
+            errors: errors.into_iter().skip(seen_errors).collect(),
+            log_entries: log_entries.into_iter().skip(seen_logs).collect(),
+            done: work_request.is_finished(),
+        };
+        seen_errors += progress.errors.len();
+        seen_logs += progress.log_entries.len();
+        on_progress(&progress);
+
+        if progress.done {
+            return Ok(work_request);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oci::test_server::{start_sequential_server, test_profile, MockResponse};
+    use crate::oci::waiter::{CancelToken, WaitPolicy};
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    fn work_request(status: &str, percent: u32) -> String {
+        format!(
+            r#"{{"id":"ocid1.coreservicesworkrequest.oc1..test","operationType":"LaunchInstance","status":"{}","percentComplete":{}}}"#,
+            status, percent
+        )
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_work_request_streams_new_logs() {
+        let log1 = r#"{"message":"Accepted","timestamp":"2026-01-01T00:00:00Z"}"#;
+        let log2 = r#"{"message":"Provisioned","timestamp":"2026-01-01T00:01:00Z"}"#;
+        let (url, requests) = start_sequential_server(vec![
+            MockResponse::ok(work_request("IN_PROGRESS", 40)),
+            MockResponse::ok("[]".to_string()),
+            MockResponse::ok(format!("[{}]", log1)),
+            MockResponse::ok(work_request("SUCCEEDED", 100)),
+            MockResponse::ok("[]".to_string()),
+            MockResponse::ok(format!("[{},{}]", log1, log2)),
+        ])
+        .await;
+
+        let cancel = CancelToken::default();
+        let policy = WaitPolicy {
+            initial_interval: Duration::from_millis(10),
+            ..WaitPolicy::default()
+        };
+        let updates = Mutex::new(Vec::new());
+        let finished = wait_for_work_request(
+            &OciClient::new(),
+            &test_profile("iaas", &url),
+            "ocid1.coreservicesworkrequest.oc1..test",
+            Poller::new(policy, &cancel),
+            |progress| updates.lock().unwrap().push(progress.clone()),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(finished.status, "SUCCEEDED");
+        let updates = updates.lock().unwrap();
+        assert_eq!(updates.len(), 2);
+        assert_eq!(updates[0].percent_complete, Some(40.0));
+        assert_eq!(updates[0].log_entries[0].message, "Accepted");
+        assert!(updates[1].done);
+        assert_eq!(updates[1].log_entries.len(), 1);
+        assert_eq!(updates[1].log_entries[0].message, "Provisioned");
+        assert!(requests.lock().unwrap()[2].starts_with(
+            "GET /20160918/workRequests/ocid1.coreservicesworkrequest.oc1..test/logs "
+        ));
+    }
+
+    #[test]
+    fn test_work_request_api_follows_ocid_type() {
+        let api = WorkRequestApi::for_work_request("ocid1.iamworkrequest.oc1..test").unwrap();
+        assert_eq!(api.service, "identity");
+        assert_eq!(
+            api.path("ocid1.iamworkrequest.oc1..test", "/logs"),
+            "/20160918/iamWorkRequests/ocid1.iamworkrequest.oc1..test/logs"
+        );
+        assert_eq!(
+            WorkRequestApi::for_work_request("ocid1.clustersworkrequest.oc1..test").unwrap(),
+            CLUSTER_WORK_REQUESTS
+        );
+        assert!(WorkRequestApi::for_work_request("ocid1.instance.oc1..test").is_err());
+
+        assert_eq!(WorkRequestApi::for_resource(None), CORE_WORK_REQUESTS);
+        assert_eq!(
+            WorkRequestApi::for_resource(Some("ocid1.instance.oc1..test")),
+            CORE_WORK_REQUESTS
+        );
+        assert_eq!(
+            WorkRequestApi::for_resource(Some("ocid1.compartment.oc1..test")),
+            IAM_WORK_REQUESTS
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_work_requests_uses_service_of_resource() {
+        let (url, requests) = start_sequential_server(vec![MockResponse::ok("[]")]).await;
+        list_work_requests(
+            &OciClient::new(),
+            &test_profile("identity", &url),
+            "ocid1.tenancy.oc1..test",
+            Some("ocid1.compartment.oc1..child"),
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert!(requests.lock().unwrap()[0].starts_with(
+            "GET /20160918/iamWorkRequests?compartmentId=ocid1.tenancy.oc1..test&resourceIdentifier=ocid1.compartment.oc1..child "
+        ));
+    }
+}
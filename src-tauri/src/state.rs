@@ -0,0 +1,75 @@
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock, RwLock};
+
+use crate::oci::client::OciClient;
+use crate::oci::compartments::CompartmentCache;
+use crate::oci::config_parser;
+use crate::oci::config_watcher::ConfigWatcher;
+use crate::oci::waiter::WaitRegistry;
+
+/// Tauri で管理するアプリケーション状態
+pub struct AppState {
+    /// 共有 OCI クライアント（HTTP コネクションプール・秘密鍵キャッシュ）
+    pub oci: OciClient,
+    /// テナンシごとのコンパートメントツリー
+    pub compartments: CompartmentCache,
+    /// 実行中の待機（ワークリクエスト・ライフサイクル状態）
+    pub waits: WaitRegistry,
+    /// 全コマンドが使う設定ファイル（UI で切り替えられる）
+    config_path: RwLock<PathBuf>,
+    /// 現在の設定ファイルの監視（設定ファイルを切り替えると作り直す）
+    pub config_watcher: Mutex<Option<ConfigWatcher>>,
+    /// アプリのキャッシュディレクトリ（起動時に設定する）
+    cache_dir: OnceLock<PathBuf>,
+}
+
+impl AppState {
+    /// 現在の設定ファイルのパス
+    pub fn config_path(&self) -> PathBuf {
+        self.config_path
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// 設定ファイルを切り替える
+    pub fn set_config_path(&self, path: PathBuf) {
+        *self.config_path.write().unwrap_or_else(|e| e.into_inner()) = path;
+        self.oci.keys.clear();
+        self.compartments.clear();
+    }
+
+    /// アプリのキャッシュディレクトリ（リージョン情報などを保存する）
+    pub fn cache_dir(&self) -> PathBuf {
+        self.cache_dir
+            .get()
+            .cloned()
+            .unwrap_or_else(|| std::env::temp_dir().join("oci-desktop"))
+    }
+
+    /// キャッシュディレクトリを設定する（最初の 1 回のみ有効）
+    pub fn set_cache_dir(&self, dir: PathBuf) {
+        self.cache_dir.set(dir).ok();
+    }
+
+    /// コマンドで指定されたパス（未指定の場合は現在の設定ファイル）
+    pub fn resolve_config_path(&self, path: Option<String>) -> PathBuf {
+        match path {
+            Some(p) => PathBuf::from(p),
+            None => self.config_path(),
+        }
+    }
+}
+
+impl Default for AppState {
+    fn default() -> Self {
+        Self {
+            oci: OciClient::default(),
+            compartments: CompartmentCache::default(),
+            waits: WaitRegistry::default(),
+            config_path: RwLock::new(config_parser::default_config_path()),
+            config_watcher: Mutex::new(None),
+            cache_dir: OnceLock::new(),
+        }
+    }
+}
@@ -9,6 +9,8 @@ import StoragePage from "./components/storage/StoragePage";
 import NetworkPage from "./components/network/NetworkPage";
 import DatabasePage from "./components/database/DatabasePage";
 import IamPage from "./components/iam/IamPage";
+import WorkRequestsPage from "./components/workrequests/WorkRequestsPage";
+import PassphraseDialog from "./components/common/PassphraseDialog";
 import "./App.css";
 
 function AppContent() {
@@ -28,6 +30,8 @@ function AppContent() {
         return <DatabasePage />;
       case "iam":
         return <IamPage />;
+      case "work-requests":
+        return <WorkRequestsPage />;
       case "settings":
         return <SettingsPage />;
       default:
@@ -47,6 +51,7 @@ export default function App() {
     <ZoomProvider>
       <ProfileProvider>
         <AppContent />
+        <PassphraseDialog />
       </ProfileProvider>
     </ZoomProvider>
   );
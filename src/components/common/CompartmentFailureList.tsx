@@ -0,0 +1,24 @@
+import type { CompartmentFailure } from "../../types/profile";
+import { formatError } from "../../utils/ociError";
+
+interface CompartmentFailureListProps {
+    failures: CompartmentFailure[];
+}
+
+// 一覧を取得できなかったリージョン・コンパートメント（他の結果は表示する）
+export default function CompartmentFailureList({ failures }: CompartmentFailureListProps) {
+    if (failures.length === 0) return null;
+
+    return (
+        <div className="resource-warning">
+            {failures.length} 件のリージョン・コンパートメントは一覧を取得できませんでした。
+            <ul>
+                {failures.map((f) => (
+                    <li key={`${f.region}/${f.compartmentId}`} title={f.compartmentId}>
+                        {f.region} {f.compartmentPath}: {formatError(f.error)}
+                    </li>
+                ))}
+            </ul>
+        </div>
+    );
+}
@@ -0,0 +1,72 @@
+import { useEffect, useState } from "react";
+import { registerPassphrasePrompt } from "../../services/invoke";
+import "./PassphraseDialog.css";
+
+interface PendingRequest {
+  profileName: string;
+  message: string;
+  resolve: (passphrase: string | null) => void;
+}
+
+// 暗号化された秘密鍵のパスフレーズ入力ダイアログ
+// 入力値はセッション中のみバックエンドのメモリに保持される
+export default function PassphraseDialog() {
+  const [pending, setPending] = useState<PendingRequest | null>(null);
+  const [passphrase, setPassphrase] = useState("");
+
+  useEffect(() => {
+    registerPassphrasePrompt(
+      (profileName, message) =>
+        new Promise((resolve) => {
+          setPassphrase("");
+          setPending({ profileName, message, resolve });
+        })
+    );
+    return () => registerPassphrasePrompt(null);
+  }, []);
+
+  if (!pending) return null;
+
+  const close = (value: string | null) => {
+    pending.resolve(value);
+    setPending(null);
+    setPassphrase("");
+  };
+
+  return (
+    <div className="passphrase-overlay">
+      <div className="passphrase-dialog">
+        <h3>秘密鍵のパスフレーズ</h3>
+        <p className="passphrase-message">
+          プロファイル「{pending.profileName}」: {pending.message}
+        </p>
+        <input
+          type="password"
+          className="passphrase-input"
+          value={passphrase}
+          autoFocus
+          onChange={(e) => setPassphrase(e.target.value)}
+          onKeyDown={(e) => {
+            if (e.key === "Enter" && passphrase) close(passphrase);
+            if (e.key === "Escape") close(null);
+          }}
+        />
+        <p className="passphrase-note">
+          パスフレーズは設定ファイルには保存されず、アプリ終了まで保持されます。
+        </p>
+        <div className="passphrase-actions">
+          <button className="btn btn-secondary" onClick={() => close(null)}>
+            キャンセル
+          </button>
+          <button
+            className="btn btn-primary"
+            onClick={() => close(passphrase)}
+            disabled={!passphrase}
+          >
+            OK
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,80 @@
+import { useState, useEffect } from "react";
+import type { OciRegion, RegionScope } from "../../types/profile";
+import { getRegions } from "../../services/profile.service";
+import { formatError } from "../../utils/ociError";
+
+interface RegionScopeSelectorProps {
+    profileName: string;
+    value: RegionScope;
+    onChange: (value: RegionScope) => void;
+}
+
+// 一覧を取得するリージョン（プロファイルのリージョン・選択したリージョン・サブスクライブ済みのすべて）
+export default function RegionScopeSelector({
+    profileName,
+    value,
+    onChange,
+}: RegionScopeSelectorProps) {
+    const [regions, setRegions] = useState<OciRegion[]>([]);
+    const [error, setError] = useState<string | null>(null);
+
+    useEffect(() => {
+        setError(null);
+        getRegions(profileName)
+            .then(setRegions)
+            .catch((e) => {
+                // テナンシのリージョン一覧を取得できない場合は組み込みの一覧から選択できるようにする
+                setError(formatError(e));
+                getRegions()
+                    .then(setRegions)
+                    .catch(() => setRegions([]));
+            });
+    }, [profileName]);
+
+    const selected = value.type === "regions" ? value.regions : [];
+    const subscribed = regions.filter((r) => r.subscribed === true);
+    const choices = subscribed.length > 0 ? subscribed : regions;
+
+    const handleTypeChange = (type: RegionScope["type"]) => {
+        onChange(type === "regions" ? { type, regions: selected } : { type });
+    };
+
+    return (
+        <div className="field-group region-scope">
+            <span className="field-label">リージョン</span>
+            <select
+                className="field-input"
+                value={value.type}
+                onChange={(e) => handleTypeChange(e.target.value as RegionScope["type"])}
+            >
+                <option value="profile">プロファイルのリージョン</option>
+                <option value="regions">選択したリージョン</option>
+                <option value="subscribed">サブスクライブ済みのすべてのリージョン</option>
+            </select>
+            {value.type === "regions" && (
+                <select
+                    className="field-input"
+                    multiple
+                    value={selected}
+                    onChange={(e) =>
+                        onChange({
+                            type: "regions",
+                            regions: Array.from(e.target.selectedOptions, (o) => o.value),
+                        })
+                    }
+                >
+                    {choices.map((r) => (
+                        <option key={r.code} value={r.code}>
+                            {r.code} ({r.display_name})
+                        </option>
+                    ))}
+                </select>
+            )}
+            {error && (
+                <span className="region-scope-error" title={error}>
+                    リージョン一覧を取得できません
+                </span>
+            )}
+        </div>
+    );
+}
@@ -1,7 +1,19 @@
-import { useState } from "react";
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
 import { useProfiles } from "../../context/ProfileContext";
-import { listInstances } from "../../services/compute.service";
-import type { ComputeInstance } from "../../types/profile";
+import { instanceAction, listInstances } from "../../services/compute.service";
+import { cancelWait } from "../../services/workRequest.service";
+import type {
+    ComputeInstance,
+    CompartmentFailure,
+    CompartmentItem,
+    InstanceAction,
+    InstanceActionProgress,
+    RegionScope,
+} from "../../types/profile";
+import { formatError } from "../../utils/ociError";
+import CompartmentFailureList from "../common/CompartmentFailureList";
+import RegionScopeSelector from "../common/RegionScopeSelector";
 import "../ResourcePage.css";
 
 function stateClass(state: string): string {
@@ -14,13 +26,43 @@ function stateClass(state: string): string {
     return "default";
 }
 
+const ACTIONS: { action: InstanceAction; label: string }[] = [
+    { action: "START", label: "起動" },
+    { action: "SOFTSTOP", label: "停止" },
+    { action: "STOP", label: "強制停止" },
+    { action: "SOFTRESET", label: "再起動" },
+    { action: "RESET", label: "強制再起動" },
+];
+
 export default function ComputePage() {
     const { currentProfile } = useProfiles();
     const [compartmentId, setCompartmentId] = useState("");
-    const [instances, setInstances] = useState<ComputeInstance[]>([]);
+    const [instances, setInstances] = useState<CompartmentItem<ComputeInstance>[]>([]);
+    const [failures, setFailures] = useState<CompartmentFailure[]>([]);
+    const [includeSubtree, setIncludeSubtree] = useState(false);
+    const [regionScope, setRegionScope] = useState<RegionScope>({ type: "profile" });
     const [loading, setLoading] = useState(false);
     const [error, setError] = useState<string | null>(null);
     const [fetched, setFetched] = useState(false);
+    const [selected, setSelected] = useState<string[]>([]);
+    const [waitForState, setWaitForState] = useState(true);
+    const [acting, setActing] = useState(false);
+    const [waitingIds, setWaitingIds] = useState<string[]>([]);
+
+    // 電源操作の進捗を受け取って状態を更新する
+    useEffect(() => {
+        const unlisten = listen<InstanceActionProgress>("instance-action-progress", (event) => {
+            const { instanceId, lifecycleState } = event.payload;
+            setInstances((current) =>
+                current.map((inst) =>
+                    inst.id === instanceId ? { ...inst, lifecycle_state: lifecycleState } : inst
+                )
+            );
+        });
+        return () => {
+            unlisten.then((f) => f());
+        };
+    }, []);
 
     if (!currentProfile) {
         return (
@@ -37,16 +79,57 @@ export default function ComputePage() {
         setLoading(true);
         setError(null);
         try {
-            const data = await listInstances(currentProfile.name, compartmentId);
-            setInstances(data);
+            const data = await listInstances(currentProfile.name, compartmentId, includeSubtree, regionScope);
+            setInstances(data.items);
+            setFailures(data.failures);
+            setSelected([]);
             setFetched(true);
         } catch (e) {
-            setError(String(e));
+            setError(formatError(e));
         } finally {
             setLoading(false);
         }
     };
 
+    const toggle = (id: string) => {
+        setSelected((current) =>
+            current.includes(id) ? current.filter((i) => i !== id) : [...current, id]
+        );
+    };
+
+    const handleAction = async (action: InstanceAction) => {
+        setActing(true);
+        setWaitingIds(waitForState ? selected : []);
+        setError(null);
+        try {
+            const results = await instanceAction(currentProfile.name, selected, action, waitForState);
+            const states: Record<string, string> = {};
+            const errors: string[] = [];
+            for (const result of results) {
+                if (result.lifecycleState) states[result.instanceId] = result.lifecycleState;
+                if (result.error) {
+                    const name = instances.find((i) => i.id === result.instanceId)?.display_name;
+                    errors.push(`${name ?? result.instanceId}: ${formatError(result.error)}`);
+                }
+            }
+            setInstances((current) =>
+                current.map((inst) =>
+                    states[inst.id] ? { ...inst, lifecycle_state: states[inst.id] } : inst
+                )
+            );
+            if (errors.length > 0) setError(errors.join("\n"));
+        } catch (e) {
+            setError(formatError(e));
+        } finally {
+            setActing(false);
+            setWaitingIds([]);
+        }
+    };
+
+    const handleCancelWait = () => {
+        waitingIds.forEach((id) => cancelWait(id));
+    };
+
     return (
         <div className="resource-page">
             <div className="resource-page-header">
@@ -67,16 +150,34 @@ export default function ComputePage() {
                         placeholder="ocid1.compartment.oc1..xxxx"
                     />
                 </div>
+                <RegionScopeSelector
+                    profileName={currentProfile.name}
+                    value={regionScope}
+                    onChange={setRegionScope}
+                />
+                <label className="subtree-toggle">
+                    <input
+                        type="checkbox"
+                        checked={includeSubtree}
+                        onChange={(e) => setIncludeSubtree(e.target.checked)}
+                    />
+                    サブコンパートメントを含む
+                </label>
                 <button
                     className="btn-fetch"
                     onClick={handleFetch}
-                    disabled={loading || !compartmentId.trim()}
+                    disabled={
+                        loading ||
+                        !compartmentId.trim() ||
+                        (regionScope.type === "regions" && regionScope.regions.length === 0)
+                    }
                 >
                     {loading ? "取得中..." : "取得"}
                 </button>
             </div>
 
             {error && <div className="resource-error">{error}</div>}
+            {!loading && <CompartmentFailureList failures={failures} />}
 
             {loading && (
                 <div className="resource-loading">
@@ -93,12 +194,51 @@ export default function ComputePage() {
                 </div>
             )}
 
+            {!loading && instances.length > 0 && (
+                <div className="instance-actions">
+                    <span>{selected.length} 件選択</span>
+                    {ACTIONS.map(({ action, label }) => (
+                        <button
+                            key={action}
+                            onClick={() => handleAction(action)}
+                            disabled={acting || selected.length === 0}
+                        >
+                            {label}
+                        </button>
+                    ))}
+                    {acting && <span>実行中...</span>}
+                    {acting && waitingIds.length > 0 && (
+                        <button onClick={handleCancelWait}>待機を中止</button>
+                    )}
+                    <label className="wait-toggle">
+                        <input
+                            type="checkbox"
+                            checked={waitForState}
+                            onChange={(e) => setWaitForState(e.target.checked)}
+                            disabled={acting}
+                        />
+                        完了まで待つ
+                    </label>
+                </div>
+            )}
+
             {!loading && instances.length > 0 && (
                 <div className="resource-table-wrapper">
                     <table className="resource-table">
                         <thead>
                             <tr>
+                                <th className="select-cell">
+                                    <input
+                                        type="checkbox"
+                                        checked={selected.length === instances.length}
+                                        onChange={(e) =>
+                                            setSelected(e.target.checked ? instances.map((i) => i.id) : [])
+                                        }
+                                    />
+                                </th>
                                 <th>表示名</th>
+                                <th>コンパートメント</th>
+                                <th>リージョン</th>
                                 <th>状態</th>
                                 <th>シェイプ</th>
                                 <th>可用性ドメイン</th>
@@ -108,7 +248,16 @@ export default function ComputePage() {
                         <tbody>
                             {instances.map((inst) => (
                                 <tr key={inst.id}>
+                                    <td className="select-cell">
+                                        <input
+                                            type="checkbox"
+                                            checked={selected.includes(inst.id)}
+                                            onChange={() => toggle(inst.id)}
+                                        />
+                                    </td>
                                     <td title={inst.display_name}>{inst.display_name}</td>
+                                    <td title={inst.compartmentId}>{inst.compartmentPath}</td>
+                                    <td>{inst.region}</td>
                                     <td>
                                         <span
                                             className={`state-badge ${stateClass(inst.lifecycle_state)}`}
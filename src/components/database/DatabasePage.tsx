@@ -1,7 +1,10 @@
 import { useState } from "react";
 import { useProfiles } from "../../context/ProfileContext";
 import { listDbSystems } from "../../services/database.service";
-import type { DbSystem } from "../../types/profile";
+import type { DbSystem, CompartmentFailure, CompartmentItem, RegionScope } from "../../types/profile";
+import { formatError } from "../../utils/ociError";
+import CompartmentFailureList from "../common/CompartmentFailureList";
+import RegionScopeSelector from "../common/RegionScopeSelector";
 import "../ResourcePage.css";
 
 function stateClass(state: string): string {
@@ -16,7 +19,10 @@ function stateClass(state: string): string {
 export default function DatabasePage() {
     const { currentProfile } = useProfiles();
     const [compartmentId, setCompartmentId] = useState("");
-    const [dbSystems, setDbSystems] = useState<DbSystem[]>([]);
+    const [dbSystems, setDbSystems] = useState<CompartmentItem<DbSystem>[]>([]);
+    const [failures, setFailures] = useState<CompartmentFailure[]>([]);
+    const [includeSubtree, setIncludeSubtree] = useState(false);
+    const [regionScope, setRegionScope] = useState<RegionScope>({ type: "profile" });
     const [loading, setLoading] = useState(false);
     const [error, setError] = useState<string | null>(null);
     const [fetched, setFetched] = useState(false);
@@ -36,11 +42,12 @@ export default function DatabasePage() {
         setLoading(true);
         setError(null);
         try {
-            const data = await listDbSystems(currentProfile.name, compartmentId);
-            setDbSystems(data);
+            const data = await listDbSystems(currentProfile.name, compartmentId, includeSubtree, regionScope);
+            setDbSystems(data.items);
+            setFailures(data.failures);
             setFetched(true);
         } catch (e) {
-            setError(String(e));
+            setError(formatError(e));
         } finally {
             setLoading(false);
         }
@@ -66,16 +73,34 @@ export default function DatabasePage() {
                         placeholder="ocid1.compartment.oc1..xxxx"
                     />
                 </div>
+                <RegionScopeSelector
+                    profileName={currentProfile.name}
+                    value={regionScope}
+                    onChange={setRegionScope}
+                />
+                <label className="subtree-toggle">
+                    <input
+                        type="checkbox"
+                        checked={includeSubtree}
+                        onChange={(e) => setIncludeSubtree(e.target.checked)}
+                    />
+                    サブコンパートメントを含む
+                </label>
                 <button
                     className="btn-fetch"
                     onClick={handleFetch}
-                    disabled={loading || !compartmentId.trim()}
+                    disabled={
+                        loading ||
+                        !compartmentId.trim() ||
+                        (regionScope.type === "regions" && regionScope.regions.length === 0)
+                    }
                 >
                     {loading ? "取得中..." : "取得"}
                 </button>
             </div>
 
             {error && <div className="resource-error">{error}</div>}
+            {!loading && <CompartmentFailureList failures={failures} />}
 
             {loading && (
                 <div className="resource-loading">
@@ -98,6 +123,8 @@ export default function DatabasePage() {
                         <thead>
                             <tr>
                                 <th>表示名</th>
+                                <th>コンパートメント</th>
+                                <th>リージョン</th>
                                 <th>状態</th>
                                 <th>バージョン</th>
                                 <th>シェイプ</th>
@@ -108,6 +135,8 @@ export default function DatabasePage() {
                             {dbSystems.map((db) => (
                                 <tr key={db.id}>
                                     <td title={db.display_name}>{db.display_name}</td>
+                                    <td title={db.compartmentId}>{db.compartmentPath}</td>
+                                    <td>{db.region}</td>
                                     <td>
                                         <span
                                             className={`state-badge ${stateClass(db.lifecycle_state)}`}
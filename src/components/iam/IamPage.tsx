@@ -2,6 +2,7 @@ import { useState } from "react";
 import { useProfiles } from "../../context/ProfileContext";
 import { listUsers, listGroups } from "../../services/iam.service";
 import type { IamUser, IamGroup } from "../../types/profile";
+import { formatError } from "../../utils/ociError";
 import "../ResourcePage.css";
 
 function stateClass(state: string): string {
@@ -40,7 +41,7 @@ export default function IamPage() {
             setUsers(data);
             setUsersFetched(true);
         } catch (e) {
-            setError(String(e));
+            setError(formatError(e));
         } finally {
             setLoading(false);
         }
@@ -54,7 +55,7 @@ export default function IamPage() {
             setGroups(data);
             setGroupsFetched(true);
         } catch (e) {
-            setError(String(e));
+            setError(formatError(e));
         } finally {
             setLoading(false);
         }
@@ -19,6 +19,7 @@ const menuItems: MenuItem[] = [
   { id: "network", label: "ネットワーク", icon: "🌐", enabled: true },
   { id: "database", label: "データベース", icon: "🗄️", enabled: true },
   { id: "iam", label: "IAM", icon: "🔑", enabled: true },
+  { id: "work-requests", label: "ワークリクエスト", icon: "⏳", enabled: true },
   { id: "settings", label: "設定", icon: "⚙️", enabled: true },
 ];
 
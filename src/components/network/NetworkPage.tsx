@@ -1,7 +1,10 @@
 import { useState } from "react";
 import { useProfiles } from "../../context/ProfileContext";
 import { listVcns } from "../../services/network.service";
-import type { Vcn } from "../../types/profile";
+import type { Vcn, CompartmentFailure, CompartmentItem, RegionScope } from "../../types/profile";
+import { formatError } from "../../utils/ociError";
+import CompartmentFailureList from "../common/CompartmentFailureList";
+import RegionScopeSelector from "../common/RegionScopeSelector";
 import "../ResourcePage.css";
 
 function stateClass(state: string): string {
@@ -15,7 +18,10 @@ function stateClass(state: string): string {
 export default function NetworkPage() {
     const { currentProfile } = useProfiles();
     const [compartmentId, setCompartmentId] = useState("");
-    const [vcns, setVcns] = useState<Vcn[]>([]);
+    const [vcns, setVcns] = useState<CompartmentItem<Vcn>[]>([]);
+    const [failures, setFailures] = useState<CompartmentFailure[]>([]);
+    const [includeSubtree, setIncludeSubtree] = useState(false);
+    const [regionScope, setRegionScope] = useState<RegionScope>({ type: "profile" });
     const [loading, setLoading] = useState(false);
     const [error, setError] = useState<string | null>(null);
     const [fetched, setFetched] = useState(false);
@@ -35,11 +41,12 @@ export default function NetworkPage() {
         setLoading(true);
         setError(null);
         try {
-            const data = await listVcns(currentProfile.name, compartmentId);
-            setVcns(data);
+            const data = await listVcns(currentProfile.name, compartmentId, includeSubtree, regionScope);
+            setVcns(data.items);
+            setFailures(data.failures);
             setFetched(true);
         } catch (e) {
-            setError(String(e));
+            setError(formatError(e));
         } finally {
             setLoading(false);
         }
@@ -65,16 +72,34 @@ export default function NetworkPage() {
                         placeholder="ocid1.compartment.oc1..xxxx"
                     />
                 </div>
+                <RegionScopeSelector
+                    profileName={currentProfile.name}
+                    value={regionScope}
+                    onChange={setRegionScope}
+                />
+                <label className="subtree-toggle">
+                    <input
+                        type="checkbox"
+                        checked={includeSubtree}
+                        onChange={(e) => setIncludeSubtree(e.target.checked)}
+                    />
+                    サブコンパートメントを含む
+                </label>
                 <button
                     className="btn-fetch"
                     onClick={handleFetch}
-                    disabled={loading || !compartmentId.trim()}
+                    disabled={
+                        loading ||
+                        !compartmentId.trim() ||
+                        (regionScope.type === "regions" && regionScope.regions.length === 0)
+                    }
                 >
                     {loading ? "取得中..." : "取得"}
                 </button>
             </div>
 
             {error && <div className="resource-error">{error}</div>}
+            {!loading && <CompartmentFailureList failures={failures} />}
 
             {loading && (
                 <div className="resource-loading">
@@ -97,6 +122,8 @@ export default function NetworkPage() {
                         <thead>
                             <tr>
                                 <th>表示名</th>
+                                <th>コンパートメント</th>
+                                <th>リージョン</th>
                                 <th>状態</th>
                                 <th>CIDR ブロック</th>
                                 <th>作成日時</th>
@@ -106,6 +133,8 @@ export default function NetworkPage() {
                             {vcns.map((vcn) => (
                                 <tr key={vcn.id}>
                                     <td title={vcn.display_name}>{vcn.display_name}</td>
+                                    <td title={vcn.compartmentId}>{vcn.compartmentPath}</td>
+                                    <td>{vcn.region}</td>
                                     <td>
                                         <span
                                             className={`state-badge ${stateClass(vcn.lifecycle_state)}`}
@@ -0,0 +1,99 @@
+import { useState } from "react";
+import type { OciProfile } from "../../types/profile";
+import Notification from "../common/Notification";
+import { generateApiKey } from "../../services/auth.service";
+import { formatError } from "../../utils/ociError";
+import "../common/Dialog.css";
+
+interface ApiKeyDialogProps {
+  sourceProfile: string;
+  onCreated: (profile: OciProfile) => void;
+  onClose: () => void;
+}
+
+// 新しい API キーを生成・登録し、その鍵を使うプロファイルを作成するダイアログ
+export default function ApiKeyDialog({
+  sourceProfile,
+  onCreated,
+  onClose,
+}: ApiKeyDialogProps) {
+  const [profileName, setProfileName] = useState("");
+  const [keySize, setKeySize] = useState(2048);
+  const [generating, setGenerating] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const handleGenerate = async () => {
+    setGenerating(true);
+    setError(null);
+    try {
+      const profile = await generateApiKey(sourceProfile, profileName, keySize);
+      onCreated(profile);
+    } catch (e) {
+      setError(formatError(e));
+    } finally {
+      setGenerating(false);
+    }
+  };
+
+  return (
+    <div className="dialog-overlay">
+      <div className="dialog">
+        <h3>API キーを生成</h3>
+        <p className="dialog-note">
+          RSA 鍵ペアを ~/.oci に生成し、プロファイル「{sourceProfile}」の認証情報で
+          ユーザーに公開鍵を登録します。登録した鍵で新しいプロファイルを作成します。
+        </p>
+
+        {error && (
+          <Notification
+            type="error"
+            message={error}
+            onClose={() => setError(null)}
+          />
+        )}
+
+        <div className="form-group">
+          <label className="form-label">新しいプロファイル名</label>
+          <input
+            type="text"
+            className="form-input"
+            value={profileName}
+            onChange={(e) => setProfileName(e.target.value)}
+            placeholder="例: NEWKEY"
+            disabled={generating}
+          />
+        </div>
+
+        <div className="form-group">
+          <label className="form-label">鍵の長さ</label>
+          <select
+            className="form-input"
+            value={keySize}
+            onChange={(e) => setKeySize(Number(e.target.value))}
+            disabled={generating}
+          >
+            <option value={2048}>2048 ビット</option>
+            <option value={4096}>4096 ビット</option>
+          </select>
+        </div>
+
+        <div className="dialog-actions">
+          <button
+            className="btn btn-secondary"
+            onClick={onClose}
+            disabled={generating}
+          >
+            キャンセル
+          </button>
+          <button
+            className="btn btn-primary"
+            onClick={handleGenerate}
+            disabled={generating || !profileName.trim()}
+          >
+            {generating ? "生成中..." : "生成して登録"}
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,127 @@
+import { useState, useEffect } from "react";
+import type { ConfigBackup, DiffLine } from "../../types/profile";
+import Notification from "../common/Notification";
+import {
+  diffConfigBackup,
+  listConfigBackups,
+  restoreConfigBackup,
+} from "../../services/config.service";
+import { formatError } from "../../utils/ociError";
+import "../common/Dialog.css";
+import "./BackupDialog.css";
+
+interface BackupDialogProps {
+  onRestored: () => void;
+  onClose: () => void;
+}
+
+const diffPrefix: Record<DiffLine["kind"], string> = {
+  same: " ",
+  added: "+",
+  removed: "-",
+};
+
+// 設定ファイルのバックアップを確認・復元するダイアログ
+export default function BackupDialog({ onRestored, onClose }: BackupDialogProps) {
+  const [backups, setBackups] = useState<ConfigBackup[]>([]);
+  const [selectedId, setSelectedId] = useState<string | null>(null);
+  const [diff, setDiff] = useState<DiffLine[]>([]);
+  const [restoring, setRestoring] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    listConfigBackups()
+      .then(setBackups)
+      .catch((e) => setError(formatError(e)));
+  }, []);
+
+  const handleSelect = async (id: string) => {
+    setSelectedId(id);
+    setError(null);
+    try {
+      setDiff(await diffConfigBackup(id));
+    } catch (e) {
+      setDiff([]);
+      setError(formatError(e));
+    }
+  };
+
+  const handleRestore = async () => {
+    if (!selectedId) return;
+    if (!confirm("このバックアップを復元しますか？現在の設定ファイルもバックアップされます。")) {
+      return;
+    }
+    setRestoring(true);
+    setError(null);
+    try {
+      await restoreConfigBackup(selectedId);
+      onRestored();
+    } catch (e) {
+      setError(formatError(e));
+    } finally {
+      setRestoring(false);
+    }
+  };
+
+  const changed = diff.some((line) => line.kind !== "same");
+
+  return (
+    <div className="dialog-overlay">
+      <div className="dialog backup-dialog">
+        <h3>設定ファイルのバックアップ</h3>
+        <p className="dialog-note">
+          設定ファイルを保存するたびに、保存前の内容が .backups に保存されます。
+          差分はバックアップから現在の内容への変更です。
+        </p>
+
+        {error && (
+          <Notification
+            type="error"
+            message={error}
+            onClose={() => setError(null)}
+          />
+        )}
+
+        <div className="backup-dialog-body">
+          <ul className="backup-list">
+            {backups.length === 0 && (
+              <li className="backup-list-empty">バックアップはありません。</li>
+            )}
+            {backups.map((backup) => (
+              <li
+                key={backup.id}
+                className={`backup-item ${selectedId === backup.id ? "selected" : ""}`}
+                onClick={() => handleSelect(backup.id)}
+              >
+                <div>{new Date(backup.createdAt).toLocaleString()}</div>
+                <div className="backup-item-size">{backup.size} バイト</div>
+              </li>
+            ))}
+          </ul>
+          <pre className="backup-diff">
+            {selectedId && !changed && "現在の設定ファイルと同じ内容です。"}
+            {changed &&
+              diff.map((line, i) => (
+                <div key={i} className={`backup-diff-line ${line.kind}`}>
+                  {diffPrefix[line.kind]} {line.text}
+                </div>
+              ))}
+          </pre>
+        </div>
+
+        <div className="dialog-actions">
+          <button className="btn btn-secondary" onClick={onClose} disabled={restoring}>
+            閉じる
+          </button>
+          <button
+            className="btn btn-primary"
+            onClick={handleRestore}
+            disabled={restoring || !selectedId || !changed}
+          >
+            {restoring ? "復元中..." : "復元"}
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,216 @@
+import { useState } from "react";
+import { open, save } from "@tauri-apps/plugin-dialog";
+import type { ImportedProfile } from "../../types/profile";
+import Notification from "../common/Notification";
+import { useProfiles } from "../../context/ProfileContext";
+import {
+  exportProfileBundle,
+  importProfileBundle,
+} from "../../services/profile.service";
+import { formatError } from "../../utils/ociError";
+import "../common/Dialog.css";
+import "./BundleDialog.css";
+
+const MIN_PASSPHRASE_LENGTH = 8;
+
+interface BundleDialogProps {
+  mode: "export" | "import";
+  onImported: () => void;
+  onClose: () => void;
+}
+
+// プロファイルを暗号化したバンドルでエクスポート・インポートするダイアログ
+export default function BundleDialog({ mode, onImported, onClose }: BundleDialogProps) {
+  const { profiles } = useProfiles();
+  const [selected, setSelected] = useState<string[]>([]);
+  const [bundlePath, setBundlePath] = useState<string | null>(null);
+  const [passphrase, setPassphrase] = useState("");
+  const [confirmation, setConfirmation] = useState("");
+  const [allowEndpoints, setAllowEndpoints] = useState(false);
+  const [imported, setImported] = useState<ImportedProfile[] | null>(null);
+  const [done, setDone] = useState(false);
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const toggle = (name: string) => {
+    setSelected((current) =>
+      current.includes(name)
+        ? current.filter((n) => n !== name)
+        : [...current, name]
+    );
+  };
+
+  const handleChooseBundle = async () => {
+    const path = await open({
+      multiple: false,
+      filters: [{ name: "プロファイルバンドル", extensions: ["ocibundle"] }],
+    });
+    if (path) {
+      setBundlePath(path as string);
+    }
+  };
+
+  const handleExport = async () => {
+    if (passphrase !== confirmation) {
+      setError("パスフレーズが一致しません。");
+      return;
+    }
+    const path = await save({
+      defaultPath: "profiles.ocibundle",
+      filters: [{ name: "プロファイルバンドル", extensions: ["ocibundle"] }],
+    });
+    if (!path) return;
+
+    setBusy(true);
+    setError(null);
+    try {
+      await exportProfileBundle(selected, passphrase, path);
+      setDone(true);
+    } catch (e) {
+      setError(formatError(e));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const handleImport = async () => {
+    if (!bundlePath) return;
+    setBusy(true);
+    setError(null);
+    try {
+      setImported(await importProfileBundle(bundlePath, passphrase, allowEndpoints));
+      onImported();
+    } catch (e) {
+      setError(formatError(e));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const renderExport = () => (
+    <>
+      <p className="dialog-note">
+        選択したプロファイルを、鍵ファイル・セッショントークンとともにパスフレーズで暗号化して書き出します。
+        パスフレーズはバンドルとは別の手段で共有してください。
+      </p>
+      <ul className="bundle-profile-list">
+        {profiles.map((profile) => (
+          <li key={profile.name}>
+            <label>
+              <input
+                type="checkbox"
+                checked={selected.includes(profile.name)}
+                onChange={() => toggle(profile.name)}
+              />
+              {profile.name}
+              <span className="bundle-profile-region">{profile.region}</span>
+            </label>
+          </li>
+        ))}
+      </ul>
+      <div className="form-group">
+        <label>パスフレーズ（{MIN_PASSPHRASE_LENGTH} 文字以上）</label>
+        <input
+          type="password"
+          value={passphrase}
+          onChange={(e) => setPassphrase(e.target.value)}
+        />
+      </div>
+      <div className="form-group">
+        <label>パスフレーズ（確認）</label>
+        <input
+          type="password"
+          value={confirmation}
+          onChange={(e) => setConfirmation(e.target.value)}
+        />
+      </div>
+      {done && <div className="bundle-result">バンドルを書き出しました。</div>}
+    </>
+  );
+
+  const renderImport = () => (
+    <>
+      <p className="dialog-note">
+        バンドルのプロファイルを現在の設定ファイルに追加します。鍵ファイル・セッショントークンは
+        設定ファイルと同じディレクトリの imported に保存されます。
+      </p>
+      <div className="bundle-file">
+        <span className="bundle-file-path">{bundlePath ?? "ファイルが選択されていません"}</span>
+        <button className="btn btn-secondary" onClick={handleChooseBundle} disabled={busy}>
+          選択...
+        </button>
+      </div>
+      <div className="form-group">
+        <label>パスフレーズ</label>
+        <input
+          type="password"
+          value={passphrase}
+          onChange={(e) => setPassphrase(e.target.value)}
+        />
+      </div>
+      <label className="bundle-option">
+        <input
+          type="checkbox"
+          checked={allowEndpoints}
+          onChange={(e) => setAllowEndpoints(e.target.checked)}
+        />
+        エンドポイントの上書き設定も取り込む（信頼できるバンドルのみ）
+      </label>
+      {imported && (
+        <div className="bundle-result">
+          {imported.length} 件のプロファイルをインポートしました。
+          <ul>
+            {imported.map((profile) => (
+              <li key={profile.name}>
+                {profile.name}
+                {profile.name !== profile.originalName &&
+                  `（同名のプロファイルがあるため ${profile.originalName} から変更）`}
+                {profile.droppedEndpoints && "（エンドポイントの上書き設定は取り込んでいません）"}
+              </li>
+            ))}
+          </ul>
+        </div>
+      )}
+    </>
+  );
+
+  const canSubmit =
+    mode === "export"
+      ? selected.length > 0 && passphrase.length >= MIN_PASSPHRASE_LENGTH && confirmation !== ""
+      : bundlePath !== null && passphrase !== "" && imported === null;
+
+  return (
+    <div className="dialog-overlay">
+      <div className="dialog bundle-dialog">
+        <h3>{mode === "export" ? "プロファイルのエクスポート" : "プロファイルのインポート"}</h3>
+
+        {error && (
+          <Notification
+            type="error"
+            message={error}
+            onClose={() => setError(null)}
+          />
+        )}
+
+        {mode === "export" ? renderExport() : renderImport()}
+
+        <div className="dialog-actions">
+          <button className="btn btn-secondary" onClick={onClose} disabled={busy}>
+            閉じる
+          </button>
+          <button
+            className="btn btn-primary"
+            onClick={mode === "export" ? handleExport : handleImport}
+            disabled={busy || !canSubmit}
+          >
+            {busy
+              ? "処理中..."
+              : mode === "export"
+                ? "エクスポート..."
+                : "インポート"}
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
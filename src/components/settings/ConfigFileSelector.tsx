@@ -0,0 +1,74 @@
+import { useState } from "react";
+import { open } from "@tauri-apps/plugin-dialog";
+import { useProfiles } from "../../context/ProfileContext";
+import BackupDialog from "./BackupDialog";
+import BundleDialog from "./BundleDialog";
+import { formatError } from "../../utils/ociError";
+import "./ConfigFileSelector.css";
+
+// 全コマンドが使う設定ファイルの表示と切り替え
+export default function ConfigFileSelector() {
+  const { configPath, changeConfigPath, reloadProfiles } = useProfiles();
+  const [error, setError] = useState<string | null>(null);
+  const [showBackups, setShowBackups] = useState(false);
+  const [bundleMode, setBundleMode] = useState<"export" | "import" | null>(null);
+
+  const handleChange = async (path: string | null) => {
+    setError(null);
+    try {
+      await changeConfigPath(path);
+    } catch (e) {
+      setError(formatError(e));
+    }
+  };
+
+  const handleBrowse = async () => {
+    const selected = await open({ multiple: false, defaultPath: configPath || undefined });
+    if (selected) {
+      await handleChange(selected as string);
+    }
+  };
+
+  return (
+    <div className="config-file-selector">
+      <div className="config-file-label">設定ファイル</div>
+      <div className="config-file-path" title={configPath}>
+        {configPath || "-"}
+      </div>
+      <div className="config-file-actions">
+        <button className="config-file-btn" onClick={handleBrowse}>
+          変更...
+        </button>
+        <button className="config-file-btn" onClick={() => handleChange(null)}>
+          デフォルトに戻す
+        </button>
+        <button className="config-file-btn" onClick={() => setShowBackups(true)}>
+          バックアップ
+        </button>
+        <button className="config-file-btn" onClick={() => setBundleMode("export")}>
+          エクスポート
+        </button>
+        <button className="config-file-btn" onClick={() => setBundleMode("import")}>
+          インポート
+        </button>
+      </div>
+      {error && <div className="config-file-error">{error}</div>}
+      {showBackups && (
+        <BackupDialog
+          onRestored={() => {
+            setShowBackups(false);
+            reloadProfiles();
+          }}
+          onClose={() => setShowBackups(false)}
+        />
+      )}
+      {bundleMode && (
+        <BundleDialog
+          mode={bundleMode}
+          onImported={reloadProfiles}
+          onClose={() => setBundleMode(null)}
+        />
+      )}
+    </div>
+  );
+}
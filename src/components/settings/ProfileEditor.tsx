@@ -1,10 +1,20 @@
 import { useState, useEffect } from "react";
-import type { OciProfile } from "../../types/profile";
+import type {
+  OciProfile,
+  PrincipalType,
+  SessionStatus,
+} from "../../types/profile";
 import RegionSelector from "./RegionSelector";
 import FilePickerButton from "../common/FilePickerButton";
+import ApiKeyDialog from "./ApiKeyDialog";
 import Notification from "../common/Notification";
 import { validateProfile } from "../../services/auth.service";
 import { testConnection } from "../../services/auth.service";
+import {
+  getSessionStatus,
+  refreshSession,
+} from "../../services/auth.service";
+import { formatError } from "../../utils/ociError";
 import {
   createProfile,
   updateProfile,
@@ -40,6 +50,9 @@ export default function ProfileEditor({
   const [originalName, setOriginalName] = useState<string>("");
   const [saving, setSaving] = useState(false);
   const [testing, setTesting] = useState(false);
+  const [session, setSession] = useState<SessionStatus | null>(null);
+  const [refreshing, setRefreshing] = useState(false);
+  const [showApiKeyDialog, setShowApiKeyDialog] = useState(false);
   const [notification, setNotification] = useState<{
     type: "success" | "error" | "info";
     message: string;
@@ -54,6 +67,12 @@ export default function ProfileEditor({
       setOriginalName("");
     }
     setNotification(null);
+    setSession(null);
+    if (profile?.security_token_file) {
+      getSessionStatus(profile.name)
+        .then(setSession)
+        .catch(() => setSession(null));
+    }
   }, [profile]);
 
   const updateField = (field: keyof OciProfile, value: string) => {
@@ -81,12 +100,12 @@ export default function ProfileEditor({
         await updateProfile(originalName, form);
       }
       setNotification({
-        type: "success",
-        message: "プロファイルを保存しました。",
+        type: validation.warnings.length > 0 ? "info" : "success",
+        message: ["プロファイルを保存しました。", ...validation.warnings].join("\n"),
       });
       onSaved();
     } catch (e) {
-      setNotification({ type: "error", message: String(e) });
+      setNotification({ type: "error", message: formatError(e) });
     } finally {
       setSaving(false);
     }
@@ -102,7 +121,7 @@ export default function ProfileEditor({
       });
       onDeleted();
     } catch (e) {
-      setNotification({ type: "error", message: String(e) });
+      setNotification({ type: "error", message: formatError(e) });
     }
   };
 
@@ -123,12 +142,35 @@ export default function ProfileEditor({
         message: result.message,
       });
     } catch (e) {
-      setNotification({ type: "error", message: String(e) });
+      setNotification({ type: "error", message: formatError(e) });
     } finally {
       setTesting(false);
     }
   };
 
+  const handleRefreshSession = async () => {
+    setRefreshing(true);
+    setNotification(null);
+    try {
+      setSession(await refreshSession(originalName));
+      setNotification({
+        type: "success",
+        message: "セッションを更新しました。",
+      });
+    } catch (e) {
+      setNotification({ type: "error", message: formatError(e) });
+    } finally {
+      setRefreshing(false);
+    }
+  };
+
+  const sessionLabel = (status: SessionStatus) => {
+    if (status.expired) return "期限切れ（oci session authenticate で再認証してください）";
+    if (!status.expiresAt) return "有効期限不明";
+    const minutes = Math.floor((status.secondsRemaining ?? 0) / 60);
+    return `${new Date(status.expiresAt).toLocaleString()} まで（残り ${minutes} 分）`;
+  };
+
   if (!profile && !isNew) {
     return (
       <div className="profile-editor-empty">
@@ -165,16 +207,36 @@ export default function ProfileEditor({
       </div>
 
       <div className="form-group">
-        <label className="form-label">ユーザー OCID</label>
-        <input
-          type="text"
+        <label className="form-label">認証方式</label>
+        <select
           className="form-input"
-          value={form.user}
-          onChange={(e) => updateField("user", e.target.value)}
-          placeholder="ocid1.user.oc1..xxxx"
-        />
+          value={form.principal ?? ""}
+          onChange={(e) =>
+            setForm((prev) => ({
+              ...prev,
+              principal: (e.target.value || null) as PrincipalType | null,
+            }))
+          }
+        >
+          <option value="">API キー / セッショントークン</option>
+          <option value="instance_principal">インスタンス・プリンシパル</option>
+          <option value="resource_principal">リソース・プリンシパル</option>
+        </select>
       </div>
 
+      {!form.principal && (
+        <div className="form-group">
+          <label className="form-label">ユーザー OCID</label>
+          <input
+            type="text"
+            className="form-input"
+            value={form.user}
+            onChange={(e) => updateField("user", e.target.value)}
+            placeholder="ocid1.user.oc1..xxxx"
+          />
+        </div>
+      )}
+
       <div className="form-group">
         <label className="form-label">テナンシー OCID</label>
         <input
@@ -191,25 +253,75 @@ export default function ProfileEditor({
         <RegionSelector
           value={form.region}
           onChange={(v) => updateField("region", v)}
+          profileName={isNew ? undefined : originalName || undefined}
         />
       </div>
 
+      {!form.principal && (
+        <>
+          <div className="form-group">
+            <label className="form-label">フィンガープリント</label>
+            <input
+              type="text"
+              className="form-input"
+              value={form.fingerprint}
+              onChange={(e) => updateField("fingerprint", e.target.value)}
+              placeholder="aa:bb:cc:dd:ee:ff:..."
+            />
+          </div>
+
+          <div className="form-group">
+            <label className="form-label">秘密鍵ファイル</label>
+            <FilePickerButton
+              value={form.key_file}
+              onChange={(v) => updateField("key_file", v)}
+            />
+          </div>
+
+          <div className="form-group">
+            <label className="form-label">セッショントークンファイル（任意）</label>
+            <FilePickerButton
+              value={form.security_token_file ?? ""}
+              onChange={(v) =>
+                setForm((prev) => ({
+                  ...prev,
+                  security_token_file: v.trim() ? v : undefined,
+                }))
+              }
+            />
+            {session && (
+              <div className="session-status">
+                <span className={session.expired ? "session-expired" : ""}>
+                  セッション有効期限: {sessionLabel(session)}
+                </span>
+                {!session.expired && (
+                  <button
+                    className="btn btn-secondary"
+                    onClick={handleRefreshSession}
+                    disabled={refreshing}
+                  >
+                    {refreshing ? "更新中..." : "セッションを更新"}
+                  </button>
+                )}
+              </div>
+            )}
+          </div>
+        </>
+      )}
+
       <div className="form-group">
-        <label className="form-label">フィンガープリント</label>
+        <label className="form-label">エンドポイント上書き（任意）</label>
         <input
           type="text"
           className="form-input"
-          value={form.fingerprint}
-          onChange={(e) => updateField("fingerprint", e.target.value)}
-          placeholder="aa:bb:cc:dd:ee:ff:..."
-        />
-      </div>
-
-      <div className="form-group">
-        <label className="form-label">秘密鍵ファイル</label>
-        <FilePickerButton
-          value={form.key_file}
-          onChange={(v) => updateField("key_file", v)}
+          value={form.endpoint ?? ""}
+          onChange={(e) =>
+            setForm((prev) => ({
+              ...prev,
+              endpoint: e.target.value.trim() ? e.target.value : undefined,
+            }))
+          }
+          placeholder="例: http://localhost:8080"
         />
       </div>
 
@@ -228,6 +340,14 @@ export default function ProfileEditor({
         >
           {testing ? "テスト中..." : "接続テスト"}
         </button>
+        {!isNew && !profile?.security_token_file && !profile?.principal && (
+          <button
+            className="btn btn-secondary"
+            onClick={() => setShowApiKeyDialog(true)}
+          >
+            API キーを生成
+          </button>
+        )}
         {!isNew && (
           <button className="btn btn-danger" onClick={handleDelete}>
             削除
@@ -239,6 +359,21 @@ export default function ProfileEditor({
           </button>
         )}
       </div>
+
+      {showApiKeyDialog && (
+        <ApiKeyDialog
+          sourceProfile={originalName}
+          onCreated={(created) => {
+            setShowApiKeyDialog(false);
+            setNotification({
+              type: "success",
+              message: `API キーを登録し、プロファイル「${created.name}」を作成しました。`,
+            });
+            onSaved();
+          }}
+          onClose={() => setShowApiKeyDialog(false)}
+        />
+      )}
     </div>
   );
 }
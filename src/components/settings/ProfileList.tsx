@@ -1,27 +1,60 @@
-import type { OciProfile } from "../../types/profile";
+import type { OciProfile, SectionDiagnostic } from "../../types/profile";
+import ConfigFileSelector from "./ConfigFileSelector";
 import "./ProfileList.css";
 
 interface ProfileListProps {
   profiles: OciProfile[];
+  brokenSections: SectionDiagnostic[];
   selectedName: string | null;
+  repairingLine: number | null;
   onSelect: (name: string) => void;
+  onRepair: (section: SectionDiagnostic) => void;
   onAdd: () => void;
+  onLogin: () => void;
+}
+
+// セクションを読み込めない理由
+function describeIssues(section: SectionDiagnostic): string[] {
+  const issues: string[] = [];
+  if (section.duplicate) {
+    issues.push("同名のセクションが先にあるため無視されています");
+  }
+  if (section.missingKeys.length > 0) {
+    issues.push(`不足しているキー: ${section.missingKeys.join(", ")}`);
+  }
+  for (const line of section.unparsableLines) {
+    issues.push(`${line.line} 行目を解析できません: ${line.text.trim()}`);
+  }
+  for (const file of section.unresolvedFiles) {
+    issues.push(`ファイルが見つかりません: ${file}`);
+  }
+  return issues;
 }
 
 export default function ProfileList({
   profiles,
+  brokenSections,
   selectedName,
+  repairingLine,
   onSelect,
+  onRepair,
   onAdd,
+  onLogin,
 }: ProfileListProps) {
   return (
     <div className="profile-list">
       <div className="profile-list-header">
         <h3 className="profile-list-title">プロファイル</h3>
-        <button className="profile-add-btn" onClick={onAdd}>
-          + 新規作成
-        </button>
+        <div className="profile-list-actions">
+          <button className="profile-login-btn" onClick={onLogin}>
+            ログイン
+          </button>
+          <button className="profile-add-btn" onClick={onAdd}>
+            + 新規作成
+          </button>
+        </div>
       </div>
+      <ConfigFileSelector />
       <div className="profile-list-items">
         {profiles.length === 0 ? (
           <div className="profile-list-empty">
@@ -37,10 +70,42 @@ export default function ProfileList({
               onClick={() => onSelect(p.name)}
             >
               <div className="profile-card-name">{p.name}</div>
-              <div className="profile-card-region">{p.region}</div>
+              <div className="profile-card-region">
+                {p.region}
+                {p.security_token_file && " ・ セッション"}
+              </div>
             </div>
           ))
         )}
+        {brokenSections.length > 0 && (
+          <>
+            <div className="profile-list-section-title">読み込めないセクション</div>
+            {brokenSections.map((section) => (
+              <div
+                key={section.startLine}
+                className={`profile-card broken ${repairingLine === section.startLine ? "selected" : ""}`}
+              >
+                <div className="profile-card-name">{section.name}</div>
+                <div className="profile-card-region">
+                  {section.startLine}〜{section.endLine} 行目
+                </div>
+                <ul className="profile-card-issues">
+                  {describeIssues(section).map((issue) => (
+                    <li key={issue}>{issue}</li>
+                  ))}
+                </ul>
+                {!section.duplicate && (
+                  <button
+                    className="profile-repair-btn"
+                    onClick={() => onRepair(section)}
+                  >
+                    修復
+                  </button>
+                )}
+              </div>
+            ))}
+          </>
+        )}
       </div>
     </div>
   );
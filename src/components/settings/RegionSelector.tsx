@@ -1,34 +1,83 @@
 import { useState, useEffect } from "react";
 import type { OciRegion } from "../../types/profile";
 import { getRegions } from "../../services/profile.service";
+import { formatError } from "../../utils/ociError";
 import "./RegionSelector.css";
 
 interface RegionSelectorProps {
   value: string;
   onChange: (value: string) => void;
+  // 指定するとテナンシのサブスクリプションに従って並べる
+  profileName?: string;
 }
 
-export default function RegionSelector({ value, onChange }: RegionSelectorProps) {
+function regionLabel(region: OciRegion): string {
+  const home = region.is_home_region ? " [ホーム]" : "";
+  return `${region.code} (${region.display_name}, ${region.key})${home}`;
+}
+
+export default function RegionSelector({ value, onChange, profileName }: RegionSelectorProps) {
   const [regions, setRegions] = useState<OciRegion[]>([]);
+  const [error, setError] = useState<string | null>(null);
 
   useEffect(() => {
-    getRegions()
+    setError(null);
+    getRegions(profileName)
       .then(setRegions)
-      .catch(() => setRegions([]));
-  }, []);
+      .catch((e) => {
+        // テナンシのリージョン一覧を取得できない場合は組み込みの一覧で選択できるようにする
+        setError(formatError(e));
+        getRegions()
+          .then(setRegions)
+          .catch(() => setRegions([]));
+      });
+  }, [profileName]);
+
+  const subscribed = regions.filter((r) => r.subscribed === true);
+  const others = regions.filter((r) => r.subscribed !== true);
+  const known =
+    !value ||
+    regions.some((r) => r.code === value || r.key.toLowerCase() === value.toLowerCase());
 
   return (
-    <select
-      className="region-selector"
-      value={value}
-      onChange={(e) => onChange(e.target.value)}
-    >
-      <option value="">-- リージョンを選択 --</option>
-      {regions.map((r) => (
-        <option key={r.code} value={r.code}>
-          {r.code} ({r.display_name})
-        </option>
-      ))}
-    </select>
+    <>
+      <select
+        className="region-selector"
+        value={value}
+        onChange={(e) => onChange(e.target.value)}
+      >
+        <option value="">-- リージョンを選択 --</option>
+        {!known && <option value={value}>{value}</option>}
+        {subscribed.length > 0 ? (
+          <>
+            <optgroup label="サブスクライブ済み">
+              {subscribed.map((r) => (
+                <option key={r.code} value={r.code}>
+                  {regionLabel(r)}
+                </option>
+              ))}
+            </optgroup>
+            <optgroup label="未サブスクライブ">
+              {others.map((r) => (
+                <option key={r.code} value={r.code}>
+                  {regionLabel(r)}
+                </option>
+              ))}
+            </optgroup>
+          </>
+        ) : (
+          regions.map((r) => (
+            <option key={r.code} value={r.code}>
+              {regionLabel(r)}
+            </option>
+          ))
+        )}
+      </select>
+      {error && (
+        <div className="region-selector-error">
+          テナンシのリージョン一覧を取得できないため、組み込みの一覧を表示しています: {error}
+        </div>
+      )}
+    </>
   );
 }
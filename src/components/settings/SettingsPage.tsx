@@ -1,16 +1,20 @@
 import { useState, useEffect, useCallback } from "react";
-import type { OciProfile } from "../../types/profile";
-import { loadOciConfig } from "../../services/config.service";
+import type { OciProfile, SectionDiagnostic } from "../../types/profile";
+import { diagnoseConfig, loadOciConfig } from "../../services/config.service";
 import ProfileList from "./ProfileList";
 import ProfileEditor from "./ProfileEditor";
+import SsoLoginDialog from "./SsoLoginDialog";
 import { useProfiles } from "../../context/ProfileContext";
 import "./SettingsPage.css";
 
 export default function SettingsPage() {
-  const { reloadProfiles } = useProfiles();
+  const { profiles: contextProfiles, reloadProfiles, configPath } = useProfiles();
   const [profiles, setProfiles] = useState<OciProfile[]>([]);
+  const [brokenSections, setBrokenSections] = useState<SectionDiagnostic[]>([]);
   const [selectedName, setSelectedName] = useState<string | null>(null);
+  const [repairing, setRepairing] = useState<SectionDiagnostic | null>(null);
   const [isNew, setIsNew] = useState(false);
+  const [showLogin, setShowLogin] = useState(false);
 
   const loadProfiles = useCallback(async () => {
     try {
@@ -19,38 +23,83 @@ export default function SettingsPage() {
     } catch {
       setProfiles([]);
     }
+    try {
+      const diagnostics = await diagnoseConfig();
+      // 必須キーが揃っていない DEFAULT は継承元として使われるため、解析エラーがなければ表示しない
+      setBrokenSections(
+        diagnostics.sections.filter(
+          (s) =>
+            !s.valid &&
+            !(s.name === "DEFAULT" && !s.duplicate && s.unparsableLines.length === 0),
+        ),
+      );
+    } catch {
+      setBrokenSections([]);
+    }
   }, []);
 
+  // 設定ファイルが切り替わったら読み込み直す
   useEffect(() => {
+    setSelectedName(null);
+    setRepairing(null);
     loadProfiles();
-  }, [loadProfiles]);
+  }, [loadProfiles, configPath]);
 
-  const selectedProfile = profiles.find((p) => p.name === selectedName) ?? null;
+  // 設定ファイルが外部で変更されたら一覧と診断結果を更新する
+  useEffect(() => {
+    loadProfiles();
+  }, [loadProfiles, contextProfiles]);
+
+  const selectedProfile = repairing
+    ? repairing.draft
+    : (profiles.find((p) => p.name === selectedName) ?? null);
 
   const handleAdd = () => {
     setSelectedName(null);
+    setRepairing(null);
     setIsNew(true);
   };
 
   const handleSelect = (name: string) => {
     setSelectedName(name);
+    setRepairing(null);
+    setIsNew(false);
+  };
+
+  const handleRepair = (section: SectionDiagnostic) => {
+    setSelectedName(null);
+    setRepairing(section);
     setIsNew(false);
   };
 
   const handleSaved = async () => {
     await loadProfiles();
     await reloadProfiles();
+    if (repairing) {
+      setSelectedName(repairing.draft.name);
+      setRepairing(null);
+    }
     setIsNew(false);
   };
 
   const handleDeleted = async () => {
     setSelectedName(null);
+    setRepairing(null);
     setIsNew(false);
     await loadProfiles();
     await reloadProfiles();
   };
 
+  const handleLoggedIn = async (profile: OciProfile) => {
+    setShowLogin(false);
+    await loadProfiles();
+    await reloadProfiles();
+    setSelectedName(profile.name);
+    setIsNew(false);
+  };
+
   const handleCancel = () => {
+    setRepairing(null);
     setIsNew(false);
   };
 
@@ -58,9 +107,13 @@ export default function SettingsPage() {
     <div className="settings-page">
       <ProfileList
         profiles={profiles}
+        brokenSections={brokenSections}
         selectedName={isNew ? null : selectedName}
+        repairingLine={repairing?.startLine ?? null}
         onSelect={handleSelect}
+        onRepair={handleRepair}
         onAdd={handleAdd}
+        onLogin={() => setShowLogin(true)}
       />
       <ProfileEditor
         profile={isNew ? null : selectedProfile}
@@ -69,6 +122,12 @@ export default function SettingsPage() {
         onDeleted={handleDeleted}
         onCancel={handleCancel}
       />
+      {showLogin && (
+        <SsoLoginDialog
+          onLoggedIn={handleLoggedIn}
+          onClose={() => setShowLogin(false)}
+        />
+      )}
     </div>
   );
 }
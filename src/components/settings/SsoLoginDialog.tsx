@@ -0,0 +1,109 @@
+import { useState } from "react";
+import type { OciProfile } from "../../types/profile";
+import RegionSelector from "./RegionSelector";
+import Notification from "../common/Notification";
+import { ssoLogin } from "../../services/auth.service";
+import { formatError } from "../../utils/ociError";
+import "../common/Dialog.css";
+
+interface SsoLoginDialogProps {
+  onLoggedIn: (profile: OciProfile) => void;
+  onClose: () => void;
+}
+
+// ブラウザでログインしてセッショントークン認証のプロファイルを作成するダイアログ
+export default function SsoLoginDialog({
+  onLoggedIn,
+  onClose,
+}: SsoLoginDialogProps) {
+  const [profileName, setProfileName] = useState("");
+  const [region, setRegion] = useState("");
+  const [tenancyName, setTenancyName] = useState("");
+  const [waiting, setWaiting] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const handleLogin = async () => {
+    setWaiting(true);
+    setError(null);
+    try {
+      const profile = await ssoLogin(profileName, region, tenancyName);
+      onLoggedIn(profile);
+    } catch (e) {
+      setError(formatError(e));
+    } finally {
+      setWaiting(false);
+    }
+  };
+
+  return (
+    <div className="dialog-overlay">
+      <div className="dialog">
+        <h3>ブラウザでログイン</h3>
+        <p className="dialog-note">
+          ブラウザでテナンシーにログインし、セッショントークン認証のプロファイルを作成します。
+          OCI CLI は不要です。
+        </p>
+
+        {error && (
+          <Notification
+            type="error"
+            message={error}
+            onClose={() => setError(null)}
+          />
+        )}
+
+        <div className="form-group">
+          <label className="form-label">プロファイル名</label>
+          <input
+            type="text"
+            className="form-input"
+            value={profileName}
+            onChange={(e) => setProfileName(e.target.value)}
+            placeholder="例: SESSION"
+            disabled={waiting}
+          />
+        </div>
+
+        <div className="form-group">
+          <label className="form-label">リージョン</label>
+          <RegionSelector value={region} onChange={setRegion} />
+        </div>
+
+        <div className="form-group">
+          <label className="form-label">テナンシー名（任意）</label>
+          <input
+            type="text"
+            className="form-input"
+            value={tenancyName}
+            onChange={(e) => setTenancyName(e.target.value)}
+            placeholder="例: mytenancy"
+            disabled={waiting}
+          />
+        </div>
+
+        {waiting && (
+          <p className="dialog-note">
+            ブラウザでログインを完了してください...
+          </p>
+        )}
+
+        <div className="dialog-actions">
+          <button
+            className="btn btn-secondary"
+            onClick={onClose}
+            disabled={waiting}
+          >
+            キャンセル
+          </button>
+          <button
+            className="btn btn-primary"
+            onClick={handleLogin}
+            disabled={waiting || !profileName.trim() || !region}
+          >
+            {waiting ? "ログイン待ち..." : "ログイン"}
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -1,13 +1,19 @@
 import { useState } from "react";
 import { useProfiles } from "../../context/ProfileContext";
 import { getNamespace, listBuckets } from "../../services/storage.service";
-import type { ObjectStorageBucket } from "../../types/profile";
+import type { ObjectStorageBucket, CompartmentFailure, CompartmentItem, RegionScope } from "../../types/profile";
+import { formatError } from "../../utils/ociError";
+import CompartmentFailureList from "../common/CompartmentFailureList";
+import RegionScopeSelector from "../common/RegionScopeSelector";
 import "../ResourcePage.css";
 
 export default function StoragePage() {
     const { currentProfile } = useProfiles();
     const [compartmentId, setCompartmentId] = useState("");
-    const [buckets, setBuckets] = useState<ObjectStorageBucket[]>([]);
+    const [buckets, setBuckets] = useState<CompartmentItem<ObjectStorageBucket>[]>([]);
+    const [failures, setFailures] = useState<CompartmentFailure[]>([]);
+    const [includeSubtree, setIncludeSubtree] = useState(false);
+    const [regionScope, setRegionScope] = useState<RegionScope>({ type: "profile" });
     const [loading, setLoading] = useState(false);
     const [error, setError] = useState<string | null>(null);
     const [fetched, setFetched] = useState(false);
@@ -28,11 +34,12 @@ export default function StoragePage() {
         setError(null);
         try {
             const ns = await getNamespace(currentProfile.name);
-            const data = await listBuckets(currentProfile.name, compartmentId, ns);
-            setBuckets(data);
+            const data = await listBuckets(currentProfile.name, compartmentId, ns, includeSubtree, regionScope);
+            setBuckets(data.items);
+            setFailures(data.failures);
             setFetched(true);
         } catch (e) {
-            setError(String(e));
+            setError(formatError(e));
         } finally {
             setLoading(false);
         }
@@ -58,16 +65,34 @@ export default function StoragePage() {
                         placeholder="ocid1.compartment.oc1..xxxx"
                     />
                 </div>
+                <RegionScopeSelector
+                    profileName={currentProfile.name}
+                    value={regionScope}
+                    onChange={setRegionScope}
+                />
+                <label className="subtree-toggle">
+                    <input
+                        type="checkbox"
+                        checked={includeSubtree}
+                        onChange={(e) => setIncludeSubtree(e.target.checked)}
+                    />
+                    サブコンパートメントを含む
+                </label>
                 <button
                     className="btn-fetch"
                     onClick={handleFetch}
-                    disabled={loading || !compartmentId.trim()}
+                    disabled={
+                        loading ||
+                        !compartmentId.trim() ||
+                        (regionScope.type === "regions" && regionScope.regions.length === 0)
+                    }
                 >
                     {loading ? "取得中..." : "取得"}
                 </button>
             </div>
 
             {error && <div className="resource-error">{error}</div>}
+            {!loading && <CompartmentFailureList failures={failures} />}
 
             {loading && (
                 <div className="resource-loading">
@@ -91,16 +116,18 @@ export default function StoragePage() {
                             <tr>
                                 <th>バケット名</th>
                                 <th>ネームスペース</th>
-                                <th>コンパートメント ID</th>
+                                <th>コンパートメント</th>
+                                <th>リージョン</th>
                                 <th>作成日時</th>
                             </tr>
                         </thead>
                         <tbody>
                             {buckets.map((b) => (
-                                <tr key={b.name}>
+                                <tr key={`${b.region}/${b.name}`}>
                                     <td title={b.name}>{b.name}</td>
                                     <td>{b.namespace}</td>
-                                    <td title={b.compartment_id}>{b.compartment_id}</td>
+                                    <td title={b.compartment_id}>{b.compartmentPath}</td>
+                                    <td>{b.region}</td>
                                     <td>{b.time_created ?? "—"}</td>
                                 </tr>
                             ))}
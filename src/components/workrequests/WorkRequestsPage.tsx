@@ -0,0 +1,232 @@
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { useProfiles } from "../../context/ProfileContext";
+import {
+    cancelWait,
+    listWorkRequests,
+    waitForWorkRequest,
+} from "../../services/workRequest.service";
+import type {
+    WorkRequest,
+    WorkRequestError,
+    WorkRequestLogEntry,
+    WorkRequestProgress,
+} from "../../types/profile";
+import { formatError } from "../../utils/ociError";
+import "../ResourcePage.css";
+
+function stateClass(status: string): string {
+    const s = status.toLowerCase();
+    if (s === "succeeded") return "available";
+    if (s === "failed" || s === "canceled") return "terminated";
+    if (["accepted", "in_progress", "canceling"].includes(s)) return "provisioning";
+    return "default";
+}
+
+function isFinished(status: string): boolean {
+    return ["SUCCEEDED", "FAILED", "CANCELED"].includes(status);
+}
+
+// 追跡中のワークリクエストのエラー・ログ
+interface TrackedDetails {
+    errors: WorkRequestError[];
+    logEntries: WorkRequestLogEntry[];
+}
+
+export default function WorkRequestsPage() {
+    const { currentProfile } = useProfiles();
+    const [compartmentId, setCompartmentId] = useState("");
+    const [workRequests, setWorkRequests] = useState<WorkRequest[]>([]);
+    const [tracking, setTracking] = useState<string[]>([]);
+    const [details, setDetails] = useState<Record<string, TrackedDetails>>({});
+    const [shown, setShown] = useState<string | null>(null);
+    const [loading, setLoading] = useState(false);
+    const [error, setError] = useState<string | null>(null);
+    const [fetched, setFetched] = useState(false);
+
+    // 追跡中のワークリクエストの進捗を受け取る
+    useEffect(() => {
+        const unlisten = listen<WorkRequestProgress>("work-request-progress", (event) => {
+            const progress = event.payload;
+            setWorkRequests((current) =>
+                current.map((wr) =>
+                    wr.id === progress.workRequestId
+                        ? { ...wr, status: progress.status, percentComplete: progress.percentComplete }
+                        : wr
+                )
+            );
+            setDetails((current) => {
+                const previous = current[progress.workRequestId] ?? { errors: [], logEntries: [] };
+                return {
+                    ...current,
+                    [progress.workRequestId]: {
+                        errors: [...previous.errors, ...progress.errors],
+                        logEntries: [...previous.logEntries, ...progress.logEntries],
+                    },
+                };
+            });
+        });
+        return () => {
+            unlisten.then((f) => f());
+        };
+    }, []);
+
+    if (!currentProfile) {
+        return (
+            <div className="no-profile">
+                <div className="no-profile-icon">⏳</div>
+                <h3>プロファイルが未設定です</h3>
+                <p>設定ページでプロファイルを追加してください。</p>
+            </div>
+        );
+    }
+
+    const handleFetch = async () => {
+        if (!compartmentId.trim()) return;
+        setLoading(true);
+        setError(null);
+        try {
+            const data = await listWorkRequests(currentProfile.name, compartmentId);
+            setWorkRequests(data);
+            setFetched(true);
+        } catch (e) {
+            setError(formatError(e));
+        } finally {
+            setLoading(false);
+        }
+    };
+
+    const handleTrack = async (id: string) => {
+        setTracking((current) => [...current, id]);
+        setShown(id);
+        try {
+            const finished = await waitForWorkRequest(currentProfile.name, id);
+            setWorkRequests((current) => current.map((wr) => (wr.id === id ? finished : wr)));
+        } catch (e) {
+            setError(formatError(e));
+        } finally {
+            setTracking((current) => current.filter((t) => t !== id));
+        }
+    };
+
+    const shownDetails = shown ? details[shown] : undefined;
+
+    return (
+        <div className="resource-page">
+            <div className="resource-page-header">
+                <h2>
+                    <span className="header-icon">⏳</span>
+                    ワークリクエスト
+                </h2>
+            </div>
+
+            <div className="compartment-bar">
+                <div className="field-group">
+                    <span className="field-label">コンパートメント OCID</span>
+                    <input
+                        className="field-input"
+                        type="text"
+                        value={compartmentId}
+                        onChange={(e) => setCompartmentId(e.target.value)}
+                        placeholder="ocid1.compartment.oc1..xxxx"
+                    />
+                </div>
+                <button
+                    className="btn-fetch"
+                    onClick={handleFetch}
+                    disabled={loading || !compartmentId.trim()}
+                >
+                    {loading ? "取得中..." : "取得"}
+                </button>
+            </div>
+
+            {error && <div className="resource-error">{error}</div>}
+
+            {loading && (
+                <div className="resource-loading">
+                    <div className="spinner" />
+                    データを取得中...
+                </div>
+            )}
+
+            {!loading && fetched && workRequests.length === 0 && (
+                <div className="resource-empty">
+                    <div className="empty-icon">⏳</div>
+                    <h3>ワークリクエストが見つかりません</h3>
+                    <p>このコンパートメントにはワークリクエストがありません。</p>
+                </div>
+            )}
+
+            {shownDetails && (
+                <div className="work-request-details">
+                    <div className="work-request-details-header">
+                        <span>{shown}</span>
+                        <button onClick={() => setShown(null)}>閉じる</button>
+                    </div>
+                    {shownDetails.errors.map((e, i) => (
+                        <div key={`error-${i}`} className="work-request-error">
+                            {e.timestamp} [{e.code}] {e.message}
+                        </div>
+                    ))}
+                    {shownDetails.logEntries.map((entry, i) => (
+                        <div key={`log-${i}`}>
+                            {entry.timestamp} {entry.message}
+                        </div>
+                    ))}
+                </div>
+            )}
+
+            {!loading && workRequests.length > 0 && (
+                <div className="resource-table-wrapper">
+                    <table className="resource-table">
+                        <thead>
+                            <tr>
+                                <th>操作</th>
+                                <th>状態</th>
+                                <th>進捗</th>
+                                <th>対象</th>
+                                <th>受付日時</th>
+                                <th>完了日時</th>
+                                <th />
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {workRequests.map((wr) => (
+                                <tr key={wr.id}>
+                                    <td title={wr.id}>{wr.operationType}</td>
+                                    <td>
+                                        <span className={`state-badge ${stateClass(wr.status)}`}>
+                                            {wr.status}
+                                        </span>
+                                    </td>
+                                    <td>
+                                        {wr.percentComplete != null
+                                            ? `${Math.round(wr.percentComplete)}%`
+                                            : "—"}
+                                    </td>
+                                    <td title={wr.resources.map((r) => r.identifier).join("\n")}>
+                                        {wr.resources.map((r) => r.entityType).join(", ") || "—"}
+                                    </td>
+                                    <td>{wr.timeAccepted ?? "—"}</td>
+                                    <td>{wr.timeFinished ?? "—"}</td>
+                                    <td className="row-actions">
+                                        {tracking.includes(wr.id) ? (
+                                            <button onClick={() => cancelWait(wr.id)}>追跡を中止</button>
+                                        ) : (
+                                            !isFinished(wr.status) && (
+                                                <button onClick={() => handleTrack(wr.id)}>追跡</button>
+                                            )
+                                        )}
+                                        {details[wr.id] && (
+                                            <button onClick={() => setShown(wr.id)}>ログ</button>
+                                        )}
+                                    </td>
+                                </tr>
+                            ))}
+                        </tbody>
+                    </table>
+                </div>
+            )}
+        </div>
+    );
+}
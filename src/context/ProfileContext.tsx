@@ -6,16 +6,38 @@ import {
   useCallback,
   type ReactNode,
 } from "react";
-import type { OciProfile } from "../types/profile";
-import { loadOciConfig } from "../services/config.service";
+import { listen } from "@tauri-apps/api/event";
+import type { OciProfile, ProfileChanges } from "../types/profile";
+import {
+  getActiveConfigPath,
+  getDefaultProfileName,
+  loadOciConfig,
+  setActiveConfigPath,
+} from "../services/config.service";
+import { formatError } from "../utils/ociError";
 
 interface ProfileContextType {
   profiles: OciProfile[];
   currentProfile: OciProfile | null;
+  // 現在使用している設定ファイル
+  configPath: string;
   loading: boolean;
   error: string | null;
   reloadProfiles: () => Promise<void>;
   selectProfile: (name: string) => void;
+  // 設定ファイルを切り替える（null でデフォルトに戻す）
+  changeConfigPath: (path: string | null) => Promise<void>;
+}
+
+// 変更通知をプロファイル一覧に反映する
+function applyProfileChanges(
+  profiles: OciProfile[],
+  changes: ProfileChanges
+): OciProfile[] {
+  const next = profiles
+    .filter((p) => !changes.removed.includes(p.name))
+    .map((p) => changes.changed.find((c) => c.name === p.name) ?? p);
+  return [...next, ...changes.added];
 }
 
 const ProfileContext = createContext<ProfileContextType | undefined>(undefined);
@@ -23,10 +45,11 @@ const ProfileContext = createContext<ProfileContextType | undefined>(undefined);
 export function ProfileProvider({ children }: { children: ReactNode }) {
   const [profiles, setProfiles] = useState<OciProfile[]>([]);
   const [currentProfile, setCurrentProfile] = useState<OciProfile | null>(null);
+  const [configPath, setConfigPath] = useState("");
   const [loading, setLoading] = useState(true);
   const [error, setError] = useState<string | null>(null);
 
-  const reloadProfiles = useCallback(async () => {
+  const loadProfiles = useCallback(async (preferredName: string | null) => {
     setLoading(true);
     setError(null);
     try {
@@ -34,8 +57,8 @@ export function ProfileProvider({ children }: { children: ReactNode }) {
       setProfiles(loaded);
       // 現在のプロファイルが削除されている場合は先頭を選択
       if (loaded.length > 0) {
-        const current = currentProfile
-          ? loaded.find((p) => p.name === currentProfile.name)
+        const current = preferredName
+          ? loaded.find((p) => p.name === preferredName)
           : null;
         if (!current) {
           setCurrentProfile(loaded[0]);
@@ -46,11 +69,24 @@ export function ProfileProvider({ children }: { children: ReactNode }) {
         setCurrentProfile(null);
       }
     } catch (e) {
-      setError(String(e));
+      setError(formatError(e));
     } finally {
       setLoading(false);
     }
-  }, [currentProfile]);
+  }, []);
+
+  const reloadProfiles = useCallback(
+    () => loadProfiles(currentProfile?.name ?? null),
+    [loadProfiles, currentProfile]
+  );
+
+  const changeConfigPath = useCallback(
+    async (path: string | null) => {
+      setConfigPath(await setActiveConfigPath(path));
+      await loadProfiles(currentProfile?.name ?? null);
+    },
+    [loadProfiles, currentProfile]
+  );
 
   const selectProfile = useCallback(
     (name: string) => {
@@ -62,20 +98,50 @@ export function ProfileProvider({ children }: { children: ReactNode }) {
     [profiles]
   );
 
+  // 設定ファイル・鍵ファイルの変更をバックエンドから受け取る
   useEffect(() => {
-    reloadProfiles();
-    // eslint-disable-next-line react-hooks/exhaustive-deps
+    const unlisten = listen<ProfileChanges>("profiles-changed", (event) => {
+      setProfiles((prev) => applyProfileChanges(prev, event.payload));
+    });
+    return () => {
+      unlisten.then((f) => f());
+    };
   }, []);
 
+  // 選択中のプロファイルを最新の内容に合わせる（削除された場合は先頭を選択）
+  useEffect(() => {
+    setCurrentProfile((current) => {
+      if (profiles.length === 0) return null;
+      const latest = current
+        ? profiles.find((p) => p.name === current.name)
+        : undefined;
+      return latest ?? profiles[0];
+    });
+  }, [profiles]);
+
+  // 起動時は OCI_CLI_PROFILE で指定されたプロファイルを選択する
+  useEffect(() => {
+    (async () => {
+      const [path, defaultName] = await Promise.all([
+        getActiveConfigPath(),
+        getDefaultProfileName().catch(() => null),
+      ]);
+      setConfigPath(path);
+      await loadProfiles(defaultName);
+    })().catch((e) => setError(formatError(e)));
+  }, [loadProfiles]);
+
   return (
     <ProfileContext.Provider
       value={{
         profiles,
         currentProfile,
+        configPath,
         loading,
         error,
         reloadProfiles,
         selectProfile,
+        changeConfigPath,
       }}
     >
       {children}
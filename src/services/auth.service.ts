@@ -1,7 +1,9 @@
 import { invoke } from "@tauri-apps/api/core";
+import { invokeOci } from "./invoke";
 import type {
   ConnectionResult,
   OciProfile,
+  SessionStatus,
   ValidationResult,
 } from "../types/profile";
 
@@ -12,13 +14,61 @@ export async function validateProfile(
   return invoke<ValidationResult>("validate_profile", { profile });
 }
 
-// 接続テスト
+// 接続テスト（暗号化された秘密鍵の場合はパスフレーズの入力を求める）
 export async function testConnection(
   profileName: string,
   configPath?: string
 ): Promise<ConnectionResult> {
-  return invoke<ConnectionResult>("test_connection", {
+  return invokeOci<ConnectionResult>("test_connection", {
     profileName,
     configPath: configPath ?? null,
   });
 }
+
+// 暗号化された秘密鍵のパスフレーズをセッション中のみ登録
+export async function setSessionPassphrase(
+  profileName: string,
+  passphrase: string
+): Promise<void> {
+  return invoke<void>("set_session_passphrase", { profileName, passphrase });
+}
+
+// セッショントークンの有効期限を取得
+export async function getSessionStatus(
+  profileName: string
+): Promise<SessionStatus> {
+  return invoke<SessionStatus>("get_session_status", { profileName });
+}
+
+// セッショントークンを更新
+export async function refreshSession(
+  profileName: string
+): Promise<SessionStatus> {
+  return invoke<SessionStatus>("refresh_session", { profileName });
+}
+
+// ブラウザでログインしてセッショントークン認証のプロファイルを作成
+export async function ssoLogin(
+  profileName: string,
+  region: string,
+  tenancyName?: string
+): Promise<OciProfile> {
+  return invoke<OciProfile>("sso_login", {
+    profileName,
+    region,
+    tenancyName: tenancyName?.trim() ? tenancyName : null,
+  });
+}
+
+// API キーを生成・登録し、その鍵を使うプロファイルを作成
+export async function generateApiKey(
+  sourceProfile: string,
+  profileName: string,
+  keySize: number
+): Promise<OciProfile> {
+  return invoke<OciProfile>("generate_api_key", {
+    sourceProfile,
+    profileName,
+    keySize,
+  });
+}
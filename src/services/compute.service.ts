@@ -1,13 +1,40 @@
-import { invoke } from "@tauri-apps/api/core";
-import type { ComputeInstance } from "../types/profile";
+import { invokeOci } from "./invoke";
+import type {
+    ComputeInstance,
+    CompartmentListing,
+    InstanceAction,
+    InstanceActionResult,
+    RegionScope,
+} from "../types/profile";
 
-// コンピュートインスタンス一覧を取得
+// コンピュートインスタンス一覧を取得（includeSubtree で子孫コンパートメント、regions で複数リージョンも含む）
 export async function listInstances(
     profileName: string,
-    compartmentId: string
-): Promise<ComputeInstance[]> {
-    return invoke<ComputeInstance[]>("list_instances", {
+    compartmentId: string,
+    includeSubtree?: boolean,
+    regions?: RegionScope,
+    maxItems?: number
+): Promise<CompartmentListing<ComputeInstance>> {
+    return invokeOci<CompartmentListing<ComputeInstance>>("list_instances", {
         profileName,
         compartmentId,
+        includeSubtree: includeSubtree ?? null,
+        regions: regions ?? null,
+        maxItems: maxItems ?? null,
+    });
+}
+
+// インスタンスの電源操作を実行（wait を指定すると RUNNING / STOPPED になるまで待つ）
+export async function instanceAction(
+    profileName: string,
+    instanceIds: string[],
+    action: InstanceAction,
+    wait?: boolean
+): Promise<InstanceActionResult[]> {
+    return invokeOci<InstanceActionResult[]>("instance_action", {
+        profileName,
+        instanceIds,
+        action,
+        wait: wait ?? null,
     });
 }
@@ -1,16 +1,56 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { OciProfile } from "../types/profile";
+import type {
+  ConfigBackup,
+  ConfigDiagnostics,
+  DiffLine,
+  OciProfile,
+} from "../types/profile";
 
 // OCI 設定ファイルのデフォルトパスを取得
 export async function getDefaultConfigPath(): Promise<string> {
   return invoke<string>("get_default_config_path");
 }
 
+// 現在使用している設定ファイルのパスを取得
+export async function getActiveConfigPath(): Promise<string> {
+  return invoke<string>("get_active_config_path");
+}
+
+// 使用する設定ファイルを切り替える（null で環境変数またはデフォルトのパスに戻す）
+export async function setActiveConfigPath(path: string | null): Promise<string> {
+  return invoke<string>("set_active_config_path", { path });
+}
+
+// 起動時に選択するプロファイル名（OCI_CLI_PROFILE 環境変数）を取得
+export async function getDefaultProfileName(): Promise<string | null> {
+  return invoke<string | null>("get_default_profile_name");
+}
+
 // OCI 設定ファイルからプロファイル一覧を読み込む
 export async function loadOciConfig(path?: string): Promise<OciProfile[]> {
   return invoke<OciProfile[]>("load_oci_config", { path: path ?? null });
 }
 
+// OCI 設定ファイルを検査し、読み込めないセクションとその理由を取得
+export async function diagnoseConfig(path?: string): Promise<ConfigDiagnostics> {
+  return invoke<ConfigDiagnostics>("diagnose_config", { path: path ?? null });
+}
+
+// 現在の設定ファイルのバックアップ一覧を取得（新しい順）
+export async function listConfigBackups(): Promise<ConfigBackup[]> {
+  return invoke<ConfigBackup[]>("list_config_backups");
+}
+
+// バックアップから現在の設定ファイルへの差分を取得
+export async function diffConfigBackup(id: string): Promise<DiffLine[]> {
+  return invoke<DiffLine[]>("diff_config_backup", { id });
+}
+
+// バックアップを復元する
+export async function restoreConfigBackup(id: string): Promise<void> {
+  return invoke("restore_config_backup", { id });
+}
+
 // OCI CLI 設定ファイルをインポート
 export async function importOciCliConfig(path: string): Promise<OciProfile[]> {
   return invoke<OciProfile[]>("import_oci_cli_config", { path });
@@ -1,10 +1,19 @@
-import { invoke } from "@tauri-apps/api/core";
-import type { DbSystem } from "../types/profile";
+import { invokeOci } from "./invoke";
+import type { DbSystem, CompartmentListing, RegionScope } from "../types/profile";
 
-// DB システム一覧を取得
+// DB システム一覧を取得（includeSubtree で子孫コンパートメント、regions で複数リージョンも含む）
 export async function listDbSystems(
     profileName: string,
-    compartmentId: string
-): Promise<DbSystem[]> {
-    return invoke<DbSystem[]>("list_db_systems", { profileName, compartmentId });
+    compartmentId: string,
+    includeSubtree?: boolean,
+    regions?: RegionScope,
+    maxItems?: number
+): Promise<CompartmentListing<DbSystem>> {
+    return invokeOci<CompartmentListing<DbSystem>>("list_db_systems", {
+        profileName,
+        compartmentId,
+        includeSubtree: includeSubtree ?? null,
+        regions: regions ?? null,
+        maxItems: maxItems ?? null,
+    });
 }
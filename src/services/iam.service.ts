@@ -1,12 +1,37 @@
-import { invoke } from "@tauri-apps/api/core";
-import type { IamUser, IamGroup } from "../types/profile";
+import { invokeOci } from "./invoke";
+import type { IamUser, IamGroup, CompartmentNode } from "../types/profile";
 
 // IAM ユーザー一覧を取得
-export async function listUsers(profileName: string): Promise<IamUser[]> {
-    return invoke<IamUser[]>("list_users", { profileName });
+export async function listUsers(
+    profileName: string,
+    maxItems?: number
+): Promise<IamUser[]> {
+    return invokeOci<IamUser[]>("list_users", {
+        profileName,
+        maxItems: maxItems ?? null,
+    });
 }
 
 // IAM グループ一覧を取得
-export async function listGroups(profileName: string): Promise<IamGroup[]> {
-    return invoke<IamGroup[]>("list_groups", { profileName });
+export async function listGroups(
+    profileName: string,
+    maxItems?: number
+): Promise<IamGroup[]> {
+    return invokeOci<IamGroup[]>("list_groups", {
+        profileName,
+        maxItems: maxItems ?? null,
+    });
+}
+
+// コンパートメントツリーを取得（query を指定すると一致するものと祖先のみ）
+export async function listCompartments(
+    profileName: string,
+    query?: string,
+    refresh?: boolean
+): Promise<CompartmentNode | null> {
+    return invokeOci<CompartmentNode | null>("list_compartments", {
+        profileName,
+        query: query ?? null,
+        refresh: refresh ?? null,
+    });
 }
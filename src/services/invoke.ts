@@ -0,0 +1,35 @@
+import { invoke } from "@tauri-apps/api/core";
+import { isOciError } from "../utils/ociError";
+import { setSessionPassphrase } from "./auth.service";
+
+// パスフレーズ入力ダイアログ（入力がキャンセルされた場合は null）
+type PassphrasePrompt = (
+  profileName: string,
+  message: string
+) => Promise<string | null>;
+
+let passphrasePrompt: PassphrasePrompt | null = null;
+
+// パスフレーズ入力ダイアログを登録
+export function registerPassphrasePrompt(prompt: PassphrasePrompt | null) {
+  passphrasePrompt = prompt;
+}
+
+// OCI API を呼ぶコマンドを実行する
+// 秘密鍵のパスフレーズが必要な場合は入力を求め、セッションに登録してから再実行する
+export async function invokeOci<T>(
+  command: string,
+  args: { profileName: string } & Record<string, unknown>
+): Promise<T> {
+  try {
+    return await invoke<T>(command, args);
+  } catch (e) {
+    if (!isOciError(e) || e.kind !== "passphraseRequired" || !passphrasePrompt) {
+      throw e;
+    }
+    const passphrase = await passphrasePrompt(args.profileName, e.message);
+    if (passphrase === null) throw e;
+    await setSessionPassphrase(args.profileName, passphrase);
+    return invoke<T>(command, args);
+  }
+}
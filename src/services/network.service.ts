@@ -1,10 +1,19 @@
-import { invoke } from "@tauri-apps/api/core";
-import type { Vcn } from "../types/profile";
+import { invokeOci } from "./invoke";
+import type { Vcn, CompartmentListing, RegionScope } from "../types/profile";
 
-// VCN 一覧を取得
+// VCN 一覧を取得（includeSubtree で子孫コンパートメント、regions で複数リージョンも含む）
 export async function listVcns(
     profileName: string,
-    compartmentId: string
-): Promise<Vcn[]> {
-    return invoke<Vcn[]>("list_vcns", { profileName, compartmentId });
+    compartmentId: string,
+    includeSubtree?: boolean,
+    regions?: RegionScope,
+    maxItems?: number
+): Promise<CompartmentListing<Vcn>> {
+    return invokeOci<CompartmentListing<Vcn>>("list_vcns", {
+        profileName,
+        compartmentId,
+        includeSubtree: includeSubtree ?? null,
+        regions: regions ?? null,
+        maxItems: maxItems ?? null,
+    });
 }
@@ -1,5 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { OciProfile, OciRegion } from "../types/profile";
+import type { ImportedProfile, OciProfile, OciRegion } from "../types/profile";
 
 // プロファイル名一覧を取得
 export async function listProfiles(path?: string): Promise<string[]> {
@@ -39,7 +39,35 @@ export async function deleteProfile(
   return invoke("delete_profile", { name, path: path ?? null });
 }
 
-// 利用可能なリージョン一覧を取得
-export async function getRegions(): Promise<OciRegion[]> {
-  return invoke<OciRegion[]>("get_regions");
+// プロファイルを鍵・トークンとともに暗号化したバンドルに書き出す
+export async function exportProfileBundle(
+  names: string[],
+  passphrase: string,
+  path: string
+): Promise<void> {
+  return invoke("export_profile_bundle", { names, passphrase, path });
+}
+
+// 暗号化したバンドルからプロファイルをインポート
+export async function importProfileBundle(
+  path: string,
+  passphrase: string,
+  allowEndpoints: boolean
+): Promise<ImportedProfile[]> {
+  return invoke<ImportedProfile[]>("import_profile_bundle", {
+    path,
+    passphrase,
+    allowEndpoints,
+  });
+}
+
+// 利用可能なリージョン一覧を取得（プロファイルを指定するとサブスクライブ済みが先頭）
+export async function getRegions(
+  profileName?: string,
+  refresh?: boolean
+): Promise<OciRegion[]> {
+  return invoke<OciRegion[]>("get_regions", {
+    profileName: profileName ?? null,
+    refresh: refresh ?? null,
+  });
 }
@@ -1,20 +1,43 @@
-import { invoke } from "@tauri-apps/api/core";
-import type { ObjectStorageBucket } from "../types/profile";
+import { invokeOci } from "./invoke";
+import type { ObjectStorageBucket, ObjectSummary, CompartmentListing, RegionScope } from "../types/profile";
 
 // オブジェクトストレージのネームスペースを取得
 export async function getNamespace(profileName: string): Promise<string> {
-    return invoke<string>("get_namespace", { profileName });
+    return invokeOci<string>("get_namespace", { profileName });
 }
 
-// バケット一覧を取得
+// バケット一覧を取得（includeSubtree で子孫コンパートメント、regions で複数リージョンも含む）
 export async function listBuckets(
     profileName: string,
     compartmentId: string,
-    namespace: string
-): Promise<ObjectStorageBucket[]> {
-    return invoke<ObjectStorageBucket[]>("list_buckets", {
+    namespace: string,
+    includeSubtree?: boolean,
+    regions?: RegionScope,
+    maxItems?: number
+): Promise<CompartmentListing<ObjectStorageBucket>> {
+    return invokeOci<CompartmentListing<ObjectStorageBucket>>("list_buckets", {
         profileName,
         compartmentId,
         namespace,
+        includeSubtree: includeSubtree ?? null,
+        regions: regions ?? null,
+        maxItems: maxItems ?? null,
+    });
+}
+
+// バケット内のオブジェクト一覧を取得
+export async function listObjects(
+    profileName: string,
+    namespace: string,
+    bucketName: string,
+    prefix?: string,
+    maxItems?: number
+): Promise<ObjectSummary[]> {
+    return invokeOci<ObjectSummary[]>("list_objects", {
+        profileName,
+        namespace,
+        bucketName,
+        prefix: prefix ?? null,
+        maxItems: maxItems ?? null,
     });
 }
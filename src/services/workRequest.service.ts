@@ -0,0 +1,51 @@
+import { invoke } from "@tauri-apps/api/core";
+import { invokeOci } from "./invoke";
+import type { WorkRequest } from "../types/profile";
+
+// コンパートメントのワークリクエスト一覧を取得（resourceId で対象リソースを絞り込める）
+export async function listWorkRequests(
+    profileName: string,
+    compartmentId: string,
+    resourceId?: string,
+    maxItems?: number
+): Promise<WorkRequest[]> {
+    return invokeOci<WorkRequest[]>("list_work_requests", {
+        profileName,
+        compartmentId,
+        resourceId: resourceId ?? null,
+        maxItems: maxItems ?? null,
+    });
+}
+
+// ワークリクエストが完了するまで待つ（進捗は work-request-progress イベントで通知される）
+export async function waitForWorkRequest(
+    profileName: string,
+    workRequestId: string,
+    timeoutSecs?: number
+): Promise<WorkRequest> {
+    return invokeOci<WorkRequest>("wait_for_work_request", {
+        profileName,
+        workRequestId,
+        timeoutSecs: timeoutSecs ?? null,
+    });
+}
+
+// リソースが targetStates のいずれかになるまで待つ（進捗は lifecycle-state-progress イベントで通知される）
+export async function waitForLifecycleState(
+    profileName: string,
+    resourceId: string,
+    targetStates: string[],
+    timeoutSecs?: number
+): Promise<string> {
+    return invokeOci<string>("wait_for_lifecycle_state", {
+        profileName,
+        resourceId,
+        targetStates,
+        timeoutSecs: timeoutSecs ?? null,
+    });
+}
+
+// 実行中の待機を中止（id はワークリクエストまたはリソースの OCID）
+export async function cancelWait(id: string): Promise<boolean> {
+    return invoke<boolean>("cancel_wait", { id });
+}
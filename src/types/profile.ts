@@ -1,5 +1,7 @@
 // OCI プロファイル型定義
 
+export type PrincipalType = "instance_principal" | "resource_principal";
+
 export interface OciProfile {
   name: string;
   user: string;
@@ -7,11 +9,26 @@ export interface OciProfile {
   region: string;
   fingerprint: string;
   key_file: string;
+  // 秘密鍵のパスフレーズ（保存時のみ指定できる。読み込んだプロファイルには含まれない）
+  pass_phrase?: string;
+  // セッショントークンファイル（oci session authenticate で作成したプロファイル）
+  security_token_file?: string;
+  // プリンシパル認証（設定ファイルの auth_type）
+  principal?: PrincipalType | null;
+  // 全サービス共通のエンドポイント上書き（例: http://localhost:8080）
+  endpoint?: string;
+  // サービス別のエンドポイント上書き（キーはサービス名）
+  service_endpoints?: Record<string, string>;
 }
 
 export interface OciRegion {
   code: string;
+  // リージョンキー（例: NRT）
+  key: string;
   display_name: string;
+  // テナンシがサブスクライブしているか（不明な場合は null）
+  subscribed: boolean | null;
+  is_home_region: boolean;
 }
 
 export interface ConnectionResult {
@@ -19,9 +36,72 @@ export interface ConnectionResult {
   message: string;
 }
 
+export interface SessionStatus {
+  expiresAt: string | null;
+  secondsRemaining: number | null;
+  expired: boolean;
+}
+
 export interface ValidationResult {
   valid: boolean;
   errors: string[];
+  warnings: string[];
+}
+
+// 設定ファイルの行
+export interface ConfigLine {
+  line: number;
+  text: string;
+}
+
+// 設定ファイルのセクションの診断結果
+export interface SectionDiagnostic {
+  name: string;
+  startLine: number;
+  endLine: number;
+  valid: boolean;
+  // 同名のセクションが先にある（このセクションは無視される）
+  duplicate: boolean;
+  missingKeys: string[];
+  unknownKeys: string[];
+  unresolvedFiles: string[];
+  unparsableLines: ConfigLine[];
+  // 読み込めた値で作成したプロファイル（修復の初期値）
+  draft: OciProfile;
+}
+
+// 設定ファイルの変更で追加・削除・変更されたプロファイル（profiles-changed イベント）
+export interface ProfileChanges {
+  added: OciProfile[];
+  removed: string[];
+  changed: OciProfile[];
+}
+
+export interface ConfigDiagnostics {
+  sections: SectionDiagnostic[];
+  orphanLines: ConfigLine[];
+}
+
+// 設定ファイルのバックアップ
+export interface ConfigBackup {
+  id: string;
+  createdAt: string;
+  size: number;
+}
+
+// 差分の 1 行
+export interface DiffLine {
+  kind: "same" | "added" | "removed";
+  text: string;
+}
+
+// バンドルからインポートしたプロファイル
+export interface ImportedProfile {
+  name: string;
+  // バンドル内の名前（同名のプロファイルがある場合は name と異なる）
+  originalName: string;
+  // バンドルにあったエンドポイントの上書きを取り込まなかったかどうか
+  droppedEndpoints: boolean;
 }
 
 // コンピュートインスタンス
@@ -34,6 +114,27 @@ export interface ComputeInstance {
   time_created?: string;
 }
 
+// インスタンスの電源操作
+export type InstanceAction = "START" | "STOP" | "SOFTSTOP" | "RESET" | "SOFTRESET";
+
+// インスタンスごとの電源操作の結果
+export interface InstanceActionResult {
+  instanceId: string;
+  // 操作後（待機した場合は待機後）のライフサイクル状態
+  lifecycleState?: string;
+  workRequestId?: string;
+  error?: OciError;
+}
+
+// 電源操作の進捗（instance-action-progress イベント）
+export interface InstanceActionProgress {
+  instanceId: string;
+  action: InstanceAction;
+  lifecycleState: string;
+  // 目的の状態に達したか
+  done: boolean;
+}
+
 // オブジェクトストレージバケット
 export interface ObjectStorageBucket {
   name: string;
@@ -42,6 +143,13 @@ export interface ObjectStorageBucket {
   time_created?: string;
 }
 
+// オブジェクトストレージのオブジェクト
+export interface ObjectSummary {
+  name: string;
+  size?: number;
+  time_created?: string;
+}
+
 // VCN
 export interface Vcn {
   id: string;
@@ -70,6 +178,44 @@ export interface IamUser {
   time_created?: string;
 }
 
+// コンパートメントツリーのノード
+export interface CompartmentNode {
+  id: string;
+  name: string;
+  // ルートからのパス（例: root/prod/app）
+  path: string;
+  description?: string;
+  children: CompartmentNode[];
+}
+
+// リージョン・コンパートメントのパスを付けた一覧の要素
+export type CompartmentItem<T> = T & {
+  region: string;
+  compartmentId: string;
+  // 例: root/prod/app
+  compartmentPath: string;
+};
+
+// 一覧を取得できなかったリージョン・コンパートメント
+export interface CompartmentFailure {
+  region: string;
+  compartmentId: string;
+  compartmentPath: string;
+  error: OciError;
+}
+
+// 一覧を取得するリージョンの指定
+export type RegionScope =
+  | { type: "profile" }
+  | { type: "regions"; regions: string[] }
+  | { type: "subscribed" };
+
+// 複数のリージョン・コンパートメントにまたがる一覧
+export interface CompartmentListing<T> {
+  items: CompartmentItem<T>[];
+  failures: CompartmentFailure[];
+}
+
 // IAM グループ
 export interface IamGroup {
   id: string;
@@ -78,3 +224,66 @@ export interface IamGroup {
   lifecycle_state: string;
   time_created?: string;
 }
+
+// ワークリクエスト
+export interface WorkRequest {
+  id: string;
+  operationType: string;
+  // ACCEPTED / IN_PROGRESS / SUCCEEDED / FAILED / CANCELING / CANCELED
+  status: string;
+  compartmentId?: string;
+  percentComplete?: number;
+  resources: WorkRequestResource[];
+  timeAccepted?: string;
+  timeStarted?: string;
+  timeFinished?: string;
+}
+
+// ワークリクエストの操作対象
+export interface WorkRequestResource {
+  entityType: string;
+  actionType: string;
+  identifier: string;
+  entityUri?: string;
+}
+
+// ワークリクエストのエラー
+export interface WorkRequestError {
+  code: string;
+  message: string;
+  timestamp?: string;
+}
+
+// ワークリクエストのログ
+export interface WorkRequestLogEntry {
+  message: string;
+  timestamp?: string;
+}
+
+// ワークリクエストの進捗（work-request-progress イベント、errors・logEntries は新しい分のみ）
+export interface WorkRequestProgress {
+  workRequestId: string;
+  status: string;
+  percentComplete?: number;
+  errors: WorkRequestError[];
+  logEntries: WorkRequestLogEntry[];
+  done: boolean;
+}
+
+// リソースのライフサイクル状態の進捗（lifecycle-state-progress イベント）
+export interface LifecycleStateProgress {
+  resourceId: string;
+  lifecycleState: string;
+  done: boolean;
+}
+
+// OCI エラー（バックエンドの OciError をシリアライズしたもの）
+export type OciErrorKind = "transport" | "auth" | "config" | "parse" | "wait" | "service" | "passphraseRequired";
+
+export interface OciError {
+  kind: OciErrorKind;
+  message: string;
+  status?: number;
+  code?: string;
+  opcRequestId?: string;
+}
@@ -0,0 +1,33 @@
+import type { OciError } from "../types/profile";
+
+// invoke の例外が OciError かどうか
+export function isOciError(e: unknown): e is OciError {
+  return (
+    typeof e === "object" &&
+    e !== null &&
+    "kind" in e &&
+    "message" in e
+  );
+}
+
+// サービスエラーのコードに応じた補足説明
+function hintFor(error: OciError): string | null {
+  if (error.kind !== "service") return null;
+  if (error.status === 401) return "認証に失敗しました。";
+  if (error.status === 404 || error.code === "NotAuthorizedOrNotFound")
+    return "権限がないか、リソースが見つかりません。";
+  if (error.status === 429 || error.code === "TooManyRequests")
+    return "リクエストが制限されています。しばらく待ってから再試行してください。";
+  return null;
+}
+
+// 例外を画面表示用のメッセージに変換
+export function formatError(e: unknown): string {
+  if (!isOciError(e)) return String(e);
+
+  const parts = [e.message];
+  const hint = hintFor(e);
+  if (hint) parts.unshift(hint);
+  if (e.opcRequestId) parts.push(`(opc-request-id: ${e.opcRequestId})`);
+  return parts.join(" ");
+}